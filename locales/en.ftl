@@ -0,0 +1,406 @@
+# Main window
+app-title = CT220S - V-I Curve
+menu-file = File
+open-capture = 📂 Open capture…
+open-capture-title = Open a capture file
+capture-filter = Hex capture
+compare-open = Compare two captures (A/B)…
+results-open = Test results…
+compare-title = A/B comparison
+compare-file = { $path } ({ $count } curves)
+compare-no-file = (no file)
+compare-overlay = Overlay
+menu-view = View
+panel-acquisition = Acquisition
+panel-display = Display and export
+panel-plot = Plot
+dock-close = Close
+dock-detach = Detach into a window
+layout-reset = Reset layout
+fullscreen = Presentation mode (F11)
+fullscreen-exit = F11 or Esc to leave presentation mode
+commands-unavailable = No USB device connected
+menu-recent = Recent files
+menu-recent-clear = Clear list
+mode-file = 📁 File mode: { $path }
+mode-usb = 🔌 USB device mode
+tab-close = Close tab
+tab-new-usb = 🔌 New USB tab
+tab-new-file = 📁 New file tab…
+tab-snapshot = 📷 Freeze into a new tab
+tab-snapshot-name = Snapshot of { $source }
+snapshot-info = Frozen snapshot: { $name }
+source = Source:
+source-usb = 🔌 USB
+source-file = 📁 File
+reconnect = 🔄 Reconnect
+reload-file = 🔄 Reload file
+auto-reload = Auto-reload
+playback-play = Play
+playback-pause = Pause
+step-backward = Previous curve
+step-forward = Next curve
+playback-loop = Loop
+playback-speed = Speed
+playback-position = Curve { $position } / { $total }
+language = Language:
+
+# Display
+display-mode = Mode:
+display-single-ch0 = Single CH0
+display-single-ch1 = Single CH1
+display-dual-overlay = Dual Overlay
+display-dual-side-by-side = Dual Side-by-Side
+pause = ⏸ Pause
+resume = ▶ Resume
+record-while-paused = Keep acquiring in background
+hold-reference = 📌 Hold as reference
+clear-reference = Clear reference
+reference-active = Reference
+library-header = Signature library
+library-overlay = Overlay:
+library-none = (none)
+library-name = Name:
+library-add = ➕ Add displayed curve
+library-export = 📦 Export library
+library-export-hint = All signatures (curves and descriptions) in a single .zip file, to share with other stations
+library-import = 📥 Import a library…
+library-imported = Library merged: { $added } added, { $replaced } replaced, { $renamed } renamed, { $skipped } skipped, { $unchanged } unchanged
+library-conflicts = Name already used:
+library-conflict-skip = Keep local
+library-conflict-overwrite = Overwrite
+library-conflict-rename = Rename imported
+library-added = Signature "{ $name }" added
+library-no-data = No CH{ $channel } curve to save
+library-error = Library error: { $error }
+library-match = { $channel }: { $score }%
+history-header = History
+history-size = Curves kept
+history-age = { $age } curve(s) ago — click to inspect
+history-export = Export all:
+history-export-png = One PNG image per curve, numbered from oldest to newest
+history-export-csv = One CSV file per curve, numbered from oldest to newest
+history-export-single = Single CSV
+history-export-single-hint = All curves in one CSV, with a “sweep” column (curve number)
+history-export-gif = Animated GIF of the successive curves, to show an intermittent fault
+history-gif-delay = How long each curve of the animation is shown
+history-export-sigrok = sigrok session (.sr) to open in PulseView: voltage and current of each channel as analog channels, curves placed end to end
+history-export-metrics = Metrics CSV
+history-export-metrics-hint = One row per curve: classification, slope, resistance, forward voltage, spans, estimated capacitance, score against the reference and verdict, for statistical process control
+display-frozen = Display frozen
+axis-voltage = Voltage
+axis-current = Current
+
+# Curve style
+style-header = ⚙ Curve style
+palette = Palette:
+palette-custom = Custom
+palette-colorblind = Color-blind
+color-ch0 = CH0 color:
+color-ch1 = CH1 color:
+dashed-ch1 = Dashed CH1
+grid-divisions = Divisions:
+grid-minor = Minor grid
+physical-units = Physical units
+ghosts = Ghost sweeps:
+markers = Markers:
+markers-none = None
+markers-circles = Circles
+markers-crosses = Crosses
+connect-points = Connect points
+log-current = Logarithmic current axis
+swap-axes = Current on X axis
+line-width = Line width:
+background = Background:
+background-white = White
+background-black = Black
+background-theme = Follow theme
+aspect = Aspect:
+aspect-square = Square
+aspect-fill = Fill window
+theme = Theme:
+theme-system = System
+theme-light = Light
+theme-dark = Dark
+
+# Device commands
+commands-header = ⚡ Commands
+cmd-frequency = Frequency
+cmd-resistance = Resistance
+cmd-mode = Mode
+cmd-voltage = Voltage
+cmd-applied = ✅ { $setting }: { $value }
+reapply-device-settings = Re-apply these settings on connect
+cmd-error = ❌ Command error: { $error }
+
+# Export
+save-png = 💾 Save PNG…
+save-svg = 📐 Export SVG…
+save-image-title = Save image
+video-record = ⏺ Video…
+video-hint = Record the plot as a video (MP4 or WebM, requires ffmpeg)
+video-stop = ⏹ Stop ({ $seconds } s)
+video-title = Save video
+save-csv = 📄 Export CSV
+copy-data = Copy data
+copy-data-hint = Copy the points of the displayed curves (tab-separated columns), to paste into a spreadsheet
+data-copied = Data copied to the clipboard
+save-json = 🧾 Export JSON
+save-numpy = 🐍 Export NumPy
+save-numpy-hint = .npz archive to open with np.load: normalized arrays, raw ADC values and, when calibration is enabled, physical values
+save-mat = 📐 Export MATLAB
+save-mat-hint = .mat file (v5): curve vectors, raw ADC values and a “metadata” struct (source, notes, settings, calibration)
+save-html = 🌐 Export HTML
+html-hint = Scroll: zoom · drag: pan · double-click: reset view
+export-measurements = Measurements in images
+export-measurements-hint = Write the date, device settings and measured values (R, Vf, C, match) on exported images
+export-options = ⚙ Export options…
+export-size = Plot size
+export-scale = Scale factor
+export-resolution = Single plot { $side }×{ $side } px, two plots { $width }×{ $side } px
+export-transparent = Transparent background
+export-transparent-hint = PNG and SVG images contain only the grid, axes and curves, for slides and dark documents
+export-labels = Axis and channel names
+export-labels-hint = Untick for plots without text, to be labelled in the document
+export-colors = Channel colors
+export-styles = Export styles
+export-styles-hint = Export settings saved under a name (size, colors, labels, background): choosing a style applies it, “convert --style NAME” uses it on the command line
+export-style-name = Style name
+export-style-save = 💾 Save style
+export-style-delete = 🗑 Delete
+saved = ✅ Saved
+save-error = ❌ Error: { $error }
+export-dir = Export folder:
+saved-to = ✅ Saved: { $path }
+export-dir-choose = Choose the export folder
+export-last = Last export: { $path }
+no-data-channel = No CH{ $channel } data
+
+# Acquisition status
+status-usb-connected = USB device connected
+status-usb-error = USB error: { $error }
+status-reading = Reading...
+status-file-loaded = File loaded: { $count } reports
+status-error = Error: { $error }
+connection-usb = USB connected
+connection-file = File playback
+connection-none = Disconnected
+curves-per-second = { $rate } curves/s
+curves-total = Total: { $count } curves
+curve-progress = CH{ $channel }: { $received }/{ $total }
+
+# Command palette
+palette-title = Command palette
+palette-hint = Search actions… (Ctrl+P)
+
+# Legend
+legend-toggle = Click to show or hide the channel
+legend-metrics = ΔV { $voltage } · ΔI { $current }
+
+# Annotations
+annotations-header = Annotations
+annotation-arrow = Arrow
+annotation-place = Place on plot
+annotation-click-target = Click the point the arrow targets
+annotation-click-text = Click where to place the text
+cancel = Cancel
+
+# Measurements
+panel-measurements = Measurements
+measure-resistance = R
+measure-forward-voltage = Vf
+measure-match = Match
+measure-component = Type
+measure-options = Analyses shown
+measure-none = No curve to analyse
+component-open = Open
+component-short = Short
+component-resistor = Resistor
+component-diode = Diode
+component-capacitor = Capacitor
+component-unknown = Unknown
+
+# Operator mode
+operator-next = Next test point ⏭
+operator-no-test-points = No test point: add signatures to the library
+operator-waiting = Waiting for a curve…
+operator-pass = PASS
+operator-fail = FAIL
+operator-serial = Serial no.
+results-title = Test results
+results-filter = Serial or test point:
+results-refresh = 🔄 Refresh
+results-log = Log tests
+results-log-hint = Log every completed test (serial, test point, settings, measurements, verdict, curve) into the SQLite results database
+results-count = { $count } result(s), most recent first
+results-date = Date
+results-serial = Serial
+results-test-point = Test point
+results-channel = Channel
+results-score = Score
+results-verdict = Verdict
+operator-save-results = 💾 Results ({ $count })
+operator-save-results-hint = Write the verdicts of the validated test points as JUnit XML (continuous integration, test management) and their metrics as CSV (statistical process control), then start a new series
+pass-threshold = PASS threshold
+
+# Board map
+panel-board = Board map
+board-load-image = 🖼 Board photo…
+board-open = 📂 Open map…
+board-save = 💾 Save map…
+board-edit = Add points
+board-point-name = Point name:
+board-edit-hint = Click the photo to add a point linked to the selected signature
+board-no-image = Load a photo of the board to place test points
+board-signature = Signature:
+board-filter-image = Image
+board-filter-map = Board map
+board-saved = Board map saved
+board-error = Board map error: { $error }
+
+# Teaching mode
+teaching-mode = Teaching mode
+teach-open = Flat horizontal line: no current flows, open circuit
+teach-short = Vertical line: current without voltage, short circuit
+teach-resistor = Straight sloped line: resistor, R ≈ { $value }
+teach-diode = Knee: diode, conducts from Vf ≈ { $value }
+teach-capacitor = Ellipse: capacitance (enable physical units and set the frequency to estimate C)
+teach-capacitor-value = Ellipse: capacitance, C ≈ { $value }
+teach-unknown = Composite shape: several components in parallel (e.g. diode and capacitor)
+
+# Accessibility
+delete = Delete
+tab-add = New tab
+annotation-text = Annotation text
+compare-position = Curve position
+volts-full-scale = Full-scale voltage
+milliamps-full-scale = Full-scale current
+history-thumbnail = CH{ $channel } curve, { $age } curves ago
+plot-summary-none = Plot: no curve
+plot-summary = { $name }: { $component }, R { $resistance }, Vf { $vf }
+
+# High contrast
+high-contrast = High contrast
+high-contrast-hint = Thick black-on-white or white-on-black traces and a bold grid
+
+# Print
+print = Print…
+print-sent = Page opened for printing
+print-error = Print failed: { $error } (page kept in { $path })
+print-date = Date: { $date }
+print-scale = Full scale: { $scale }
+print-normalized = normalized units
+print-device = Device: { $settings }
+
+# Session notes
+notes-header = Notes
+notes-hint = Board serial, fault description… (included in exports)
+
+# UI scale
+ui-scale = Interface scale
+
+# Reader failure
+reader-failure-title = Acquisition stopped
+reader-failure-text = The acquisition stopped because of an error:
+reader-failure-hint = Check the USB cable and reconnect the device, or replay a capture file instead.
+reader-panic = internal error ({ $message })
+close = Close
+
+# Refresh rate
+max-fps = Max refresh rate
+max-fps-unlimited = Unlimited
+max-fps-hint = The plot is redrawn only when new data arrives, at most this often
+
+# Session files
+session-open = Open session…
+session-save = Save session…
+session-filter = CT220S session
+session-error = Session file error: { $error }
+
+# PDF report
+report-pdf = PDF report
+report-verdict = Result: { $result } ({ $score } % against { $signature })
+report-no-signature = Result: no reference signature selected
+comparison-report = 📊 Comparison report
+comparison-report-hint = Curves overlaid on their reference, point-by-point deviation, statistics and PASS/FAIL verdict
+comparison-none = No active comparison: hold a reference or select a library entry
+comparison-held-reference = Held reference
+comparison-verdict = { $channel }: { $result } (score { $score } % against “{ $reference }”)
+comparison-stats = Nearest-point deviation (normalized units): mean { $mean } · RMS { $rms } · max { $max } (point { $point })
+comparison-deviation = Deviation of each point from the reference
+comparison-result = Comparison result: { $result }
+batch-title = Conversion of { $dir }
+batch-capture = Capture
+batch-summary = Measurements
+batch-files = Files
+batch-error = Error: { $error }
+batch-capture-error = Error in { $name }: { $error }
+batch-done = { $converted } captures converted, { $failed } failed
+
+# Report templates
+templates-header = Report templates
+template-html = HTML page
+template-pdf = PDF header (text)
+template-builtin = built-in
+template-choose = Choose…
+template-reset = Built-in
+template-fields = Fields: { $fields }
+template-hint = Write {"{{"} name {"}}"} in the template file to insert a field. The PDF template gives the text lines printed above the plots.
+
+# Command line measurements and checks
+stats-sweep = Sweep { $sweep } CH{ $channel }: { $points } points, V [{ $vmin }; { $vmax }], I [{ $imin }; { $imax }], area { $area } ({ $ratio } %), { $component }, R { $resistance }
+stats-forward-voltage = Vf { $vf }
+validate-summary = { $reports } reports, { $curves } complete curves (CH0: { $ch0 }, CH1: { $ch1 }), { $issues } issue(s)
+test-verdict = { $name }: { $result } ({ $score } %)
+acquire-running = Acquiring…
+config-file = File: { $path }
+config-file-missing = File: { $path } (missing)
+config-no-dir = File: configuration directory not found
+capture-running-for = Capturing for { $seconds } s…
+capture-running = Capturing until interrupted (Ctrl+C)…
+capture-saved = Capture saved: { $path } ({ $reports } reports)
+capture-received = { $reports } reports received
+capture-unreadable = unreadable line (not UTF-8 text?)
+capture-not-hex = non-hexadecimal character '{ $char }'
+capture-odd-digits = odd number of hex digits ({ $digits }), last one ignored
+capture-report-size = { $size }-byte report ({ $data } or { $read } expected): { $consequence }
+capture-curve-lost = the curve from line { $line } and the rest are lost
+capture-report-ignored = report ignored
+capture-orphans = { $count } data report(s) outside a curve (lines { $first } to { $last }): ignored
+capture-header-mid-curve = header in the middle of the curve started on line { $line } ({ $reports }/{ $total } reports): it will be read as data
+capture-unknown-channel = unknown channel { $channel } in the header
+capture-invalid-curve = invalid curve: { $error }
+capture-incomplete = incomplete curve at end of file: { $reports }/{ $total } data reports
+capture-empty = no report in the file
+
+# Interactive shell
+shell-welcome = CT220S shell: `help` lists the commands, `quit` exits
+shell-error = Error: { $error }
+shell-sent = Report sent: { $report }
+shell-missing-reports = { $count } report(s) not received within { $seconds } s
+shell-loaded = { $reports } reports, { $curves } curves
+shell-saved = Capture saved: { $path } ({ $reports } reports)
+shell-no-curves = No curve in memory (capture, acquire or load)
+shell-no-reports = No report in memory (capture or load)
+
+# Text interface
+tui-no-terminal = The text interface needs a terminal
+tui-no-device = No device connected
+tui-paused = paused
+tui-keys = [space] pause  [c] channels
+tui-keys-sweep = [←/→] sweep
+tui-keys-quit = [q] quit
+tui-status = { $connection } — { $count } curves, { $rate }/s
+tui-sweep = sweep { $position }/{ $total }
+
+# Test plans
+plan-title = Test plan: { $name }
+plan-board = Board: { $serial }
+plan-point = Point { $index }/{ $count }: { $name }
+plan-settings = Settings: { $settings }
+plan-ask-measure = [Enter] measure, [s] skip, [q] quit
+plan-ask-retry = [r] retry, [Enter] next point, [q] quit
+plan-channel-result = { $point } CH{ $channel }: { $result } ({ $score } %)
+plan-point-error = { $point }: error: { $error }
+plan-summary = { $count } points: { $pass } PASS, { $fail } FAIL, { $error } errored, { $skipped } skipped, { $untested } untested
+plan-result = Plan result: { $result }
@@ -0,0 +1,406 @@
+# Interface principale
+app-title = CT220S - Courbe V-I
+menu-file = Fichier
+open-capture = 📂 Ouvrir une capture…
+open-capture-title = Ouvrir un fichier de capture
+capture-filter = Capture hexadécimale
+compare-open = Comparer deux captures (A/B)…
+results-open = Résultats des tests…
+compare-title = Comparaison A/B
+compare-file = { $path } ({ $count } courbes)
+compare-no-file = (aucun fichier)
+compare-overlay = Superposer
+menu-view = Affichage
+panel-acquisition = Acquisition
+panel-display = Affichage et export
+panel-plot = Tracé
+dock-close = Fermer
+dock-detach = Détacher dans une fenêtre
+layout-reset = Réinitialiser la disposition
+fullscreen = Mode présentation (F11)
+fullscreen-exit = F11 ou Échap pour quitter le mode présentation
+commands-unavailable = Aucun boîtier USB connecté
+menu-recent = Fichiers récents
+menu-recent-clear = Effacer la liste
+mode-file = 📁 Mode fichier: { $path }
+mode-usb = 🔌 Mode périphérique USB
+tab-close = Fermer l'onglet
+tab-new-usb = 🔌 Nouvel onglet USB
+tab-new-file = 📁 Nouvel onglet fichier…
+tab-snapshot = 📷 Figer dans un nouvel onglet
+tab-snapshot-name = Instantané de { $source }
+snapshot-info = Instantané figé: { $name }
+source = Source:
+source-usb = 🔌 USB
+source-file = 📁 Fichier
+reconnect = 🔄 Reconnecter
+reload-file = 🔄 Recharger
+auto-reload = Recharger automatiquement
+playback-play = Lecture
+playback-pause = Pause
+step-backward = Courbe précédente
+step-forward = Courbe suivante
+playback-loop = Boucle
+playback-speed = Vitesse
+playback-position = Courbe { $position } / { $total }
+language = Langue:
+
+# Affichage
+display-mode = Mode:
+display-single-ch0 = Single CH0
+display-single-ch1 = Single CH1
+display-dual-overlay = Dual Overlay
+display-dual-side-by-side = Dual Side-by-Side
+pause = ⏸ Pause
+resume = ▶ Reprendre
+record-while-paused = Acquisition en arrière-plan
+hold-reference = 📌 Garder comme référence
+clear-reference = Effacer la référence
+reference-active = Référence
+library-header = Bibliothèque de signatures
+library-overlay = Superposer:
+library-none = (aucune)
+library-name = Nom:
+library-add = ➕ Ajouter la courbe affichée
+library-export = 📦 Exporter la bibliothèque
+library-export-hint = Toutes les signatures (courbes et descriptions) dans un seul fichier .zip, à partager avec d'autres postes
+library-import = 📥 Importer une bibliothèque…
+library-imported = Bibliothèque fusionnée : { $added } ajoutée(s), { $replaced } remplacée(s), { $renamed } renommée(s), { $skipped } ignorée(s), { $unchanged } identique(s)
+library-conflicts = Nom déjà utilisé :
+library-conflict-skip = Garder la locale
+library-conflict-overwrite = Remplacer
+library-conflict-rename = Renommer l'importée
+library-added = Signature « { $name } » ajoutée
+library-no-data = Pas de courbe CH{ $channel } à enregistrer
+library-error = Erreur bibliothèque: { $error }
+library-match = { $channel }: { $score } %
+history-header = Historique
+history-size = Courbes conservées
+history-age = Il y a { $age } courbe(s) — cliquer pour inspecter
+history-export = Tout exporter :
+history-export-png = Une image PNG par courbe, numérotées de la plus ancienne à la plus récente
+history-export-csv = Un fichier CSV par courbe, numérotés de la plus ancienne à la plus récente
+history-export-single = CSV unique
+history-export-single-hint = Toutes les courbes dans un seul CSV, avec une colonne « sweep » (numéro de la courbe)
+history-export-gif = Animation GIF des courbes successives, pour montrer un défaut intermittent
+history-gif-delay = Durée d'affichage de chaque courbe de l'animation
+history-export-sigrok = Session sigrok (.sr) à ouvrir dans PulseView : tension et courant de chaque canal en voies analogiques, courbes mises bout à bout
+history-export-metrics = Mesures CSV
+history-export-metrics-hint = Une ligne par courbe : classification, pente, résistance, tension de seuil, excursions, capacité estimée, score contre la référence et verdict, pour la maîtrise statistique des procédés
+display-frozen = Affichage figé
+axis-voltage = Tension
+axis-current = Courant
+
+# Style des courbes
+style-header = ⚙ Style des courbes
+palette = Palette:
+palette-custom = Personnalisée
+palette-colorblind = Daltonisme
+color-ch0 = Couleur CH0:
+color-ch1 = Couleur CH1:
+dashed-ch1 = CH1 en pointillés
+grid-divisions = Divisions:
+grid-minor = Grille secondaire
+physical-units = Unités physiques
+ghosts = Balayages fantômes:
+markers = Marqueurs:
+markers-none = Aucun
+markers-circles = Cercles
+markers-crosses = Croix
+connect-points = Relier les points
+log-current = Axe du courant logarithmique
+swap-axes = Courant en abscisse
+line-width = Épaisseur:
+background = Fond:
+background-white = Blanc
+background-black = Noir
+background-theme = Selon thème
+aspect = Proportions:
+aspect-square = Carré
+aspect-fill = Remplir la fenêtre
+theme = Thème:
+theme-system = Système
+theme-light = Clair
+theme-dark = Sombre
+
+# Commandes du boîtier
+commands-header = ⚡ Commandes
+cmd-frequency = Fréquence
+cmd-resistance = Résistance
+cmd-mode = Mode
+cmd-voltage = Voltage
+cmd-applied = ✅ { $setting }: { $value }
+reapply-device-settings = Réappliquer ces réglages à la connexion
+cmd-error = ❌ Erreur cmd: { $error }
+
+# Export
+save-png = 💾 Sauvegarder PNG…
+save-svg = 📐 Exporter SVG…
+save-image-title = Enregistrer l'image
+video-record = ⏺ Vidéo…
+video-hint = Enregistrer le tracé en vidéo (MP4 ou WebM, nécessite ffmpeg)
+video-stop = ⏹ Arrêter ({ $seconds } s)
+video-title = Enregistrer la vidéo
+save-csv = 📄 Exporter CSV
+copy-data = Copier les données
+copy-data-hint = Copier les points des courbes affichées (colonnes séparées par des tabulations), à coller dans un tableur
+data-copied = Données copiées dans le presse-papiers
+save-json = 🧾 Exporter JSON
+save-numpy = 🐍 Exporter NumPy
+save-numpy-hint = Archive .npz à ouvrir avec np.load : tableaux normalisés, valeurs brutes de l'ADC et, si la calibration est active, valeurs physiques
+save-mat = 📐 Exporter MATLAB
+save-mat-hint = Fichier .mat (v5) : vecteurs des courbes, valeurs brutes de l'ADC et structure « metadata » (source, notes, réglages, calibration)
+save-html = 🌐 Exporter HTML
+html-hint = Molette : zoom · glisser : déplacer · double-clic : vue initiale
+export-measurements = Mesures dans les images
+export-measurements-hint = Inscrire la date, les réglages du boîtier et les valeurs mesurées (R, Vf, C, correspondance) sur les images exportées
+export-options = ⚙ Options d'export…
+export-size = Taille du tracé
+export-scale = Facteur
+export-resolution = Un tracé { $side }×{ $side } px, deux tracés { $width }×{ $side } px
+export-transparent = Fond transparent
+export-transparent-hint = Les images PNG et SVG ne contiennent que la grille, les axes et les courbes, pour les présentations et documents sombres
+export-labels = Noms des axes et des canaux
+export-labels-hint = Décocher pour des tracés sans texte, à légender dans le document
+export-colors = Couleurs des canaux
+export-styles = Styles d'export
+export-styles-hint = Réglages d'export enregistrés sous un nom (taille, couleurs, textes, fond) : choisir un style l'applique, « convert --style NOM » l'utilise en ligne de commande
+export-style-name = Nom du style
+export-style-save = 💾 Enregistrer le style
+export-style-delete = 🗑 Supprimer
+saved = ✅ Sauvegardé
+save-error = ❌ Erreur: { $error }
+export-dir = Dossier d'export:
+saved-to = ✅ Sauvegardé : { $path }
+export-dir-choose = Choisir le dossier d'export
+export-last = Dernier export : { $path }
+no-data-channel = Pas de données CH{ $channel }
+
+# Statut de l'acquisition
+status-usb-connected = Périphérique USB connecté
+status-usb-error = Erreur USB: { $error }
+status-reading = Lecture en cours...
+status-file-loaded = Fichier chargé: { $count } rapports
+status-error = Erreur: { $error }
+connection-usb = USB connecté
+connection-file = Relecture de fichier
+connection-none = Déconnecté
+curves-per-second = { $rate } courbes/s
+curves-total = Total: { $count } courbes
+curve-progress = CH{ $channel } : { $received }/{ $total }
+
+# Palette de commandes
+palette-title = Palette de commandes
+palette-hint = Rechercher une action… (Ctrl+P)
+
+# Légende
+legend-toggle = Cliquer pour afficher ou masquer le canal
+legend-metrics = ΔV { $voltage } · ΔI { $current }
+
+# Annotations
+annotations-header = Annotations
+annotation-arrow = Flèche
+annotation-place = Placer sur le tracé
+annotation-click-target = Cliquer sur le point visé par la flèche
+annotation-click-text = Cliquer à l'emplacement du texte
+cancel = Annuler
+
+# Mesures
+panel-measurements = Mesures
+measure-resistance = R
+measure-forward-voltage = Vf
+measure-match = Correspondance
+measure-component = Type
+measure-options = Analyses affichées
+measure-none = Aucune courbe à analyser
+component-open = Circuit ouvert
+component-short = Court-circuit
+component-resistor = Résistance
+component-diode = Diode
+component-capacitor = Condensateur
+component-unknown = Inconnu
+
+# Mode opérateur
+operator-next = Point de test suivant ⏭
+operator-no-test-points = Aucun point de test : ajouter des signatures à la bibliothèque
+operator-waiting = En attente d'une courbe…
+operator-pass = PASS
+operator-fail = FAIL
+operator-serial = N° de série
+results-title = Résultats des tests
+results-filter = Série ou point de test :
+results-refresh = 🔄 Actualiser
+results-log = Enregistrer les tests
+results-log-hint = Enregistrer chaque test terminé (série, point de test, réglages, mesures, verdict, courbe) dans la base SQLite des résultats
+results-count = { $count } résultat(s), les plus récents en premier
+results-date = Date
+results-serial = Série
+results-test-point = Point de test
+results-channel = Canal
+results-score = Score
+results-verdict = Verdict
+operator-save-results = 💾 Résultats ({ $count })
+operator-save-results-hint = Écrire les verdicts des points de test validés en JUnit XML (intégration continue, gestion des tests) et leurs mesures en CSV (maîtrise statistique des procédés), puis commencer une nouvelle série
+pass-threshold = Seuil PASS
+
+# Plan de carte
+panel-board = Plan de carte
+board-load-image = 🖼 Photo de la carte…
+board-open = 📂 Ouvrir un plan…
+board-save = 💾 Enregistrer le plan…
+board-edit = Ajouter des points
+board-point-name = Nom du point :
+board-edit-hint = Cliquer sur la photo pour ajouter un point lié à la signature sélectionnée
+board-no-image = Charger une photo de la carte pour placer des points de test
+board-signature = Signature :
+board-filter-image = Image
+board-filter-map = Plan de carte
+board-saved = Plan de carte enregistré
+board-error = Erreur de plan de carte : { $error }
+
+# Mode enseignement
+teaching-mode = Mode enseignement
+teach-open = Ligne horizontale : aucun courant, circuit ouvert
+teach-short = Ligne verticale : courant sans tension, court-circuit
+teach-resistor = Droite inclinée : résistance, R ≈ { $value }
+teach-diode = Coude : diode, conduction à partir de Vf ≈ { $value }
+teach-capacitor = Ellipse : capacité (activer les unités physiques et régler la fréquence pour estimer C)
+teach-capacitor-value = Ellipse : capacité, C ≈ { $value }
+teach-unknown = Forme composée : plusieurs composants en parallèle (ex : diode et condensateur)
+
+# Accessibilité
+delete = Supprimer
+tab-add = Nouvel onglet
+annotation-text = Texte de l'annotation
+compare-position = Position dans la capture
+volts-full-scale = Tension de pleine échelle
+milliamps-full-scale = Courant de pleine échelle
+history-thumbnail = Courbe CH{ $channel }, il y a { $age } courbes
+plot-summary-none = Tracé : aucune courbe
+plot-summary = { $name } : { $component }, R { $resistance }, Vf { $vf }
+
+# Haut contraste
+high-contrast = Haut contraste
+high-contrast-hint = Traits épais noir sur blanc ou blanc sur noir et grille renforcée
+
+# Impression
+print = Imprimer…
+print-sent = Page ouverte pour impression
+print-error = Échec de l'impression : { $error } (page conservée dans { $path })
+print-date = Date : { $date }
+print-scale = Pleine échelle : { $scale }
+print-normalized = unités normalisées
+print-device = Boîtier : { $settings }
+
+# Notes de session
+notes-header = Notes
+notes-hint = Numéro de série, panne constatée… (repris dans les exports)
+
+# Échelle de l'interface
+ui-scale = Échelle de l'interface
+
+# Arrêt de la lecture
+reader-failure-title = Acquisition arrêtée
+reader-failure-text = L'acquisition s'est arrêtée sur une erreur :
+reader-failure-hint = Vérifiez le câble USB et reconnectez le boîtier, ou rejouez plutôt un fichier de capture.
+reader-panic = erreur interne ({ $message })
+close = Fermer
+
+# Fréquence de rafraîchissement
+max-fps = Rafraîchissement max.
+max-fps-unlimited = Illimité
+max-fps-hint = Le tracé n'est redessiné qu'à l'arrivée de nouvelles données, au plus à cette fréquence
+
+# Fichiers de session
+session-open = Ouvrir une session…
+session-save = Enregistrer la session…
+session-filter = Session CT220S
+session-error = Erreur de fichier de session : { $error }
+
+# Rapport PDF
+report-pdf = Rapport PDF
+report-verdict = Résultat : { $result } ({ $score } % par rapport à { $signature })
+report-no-signature = Résultat : aucune signature de référence sélectionnée
+comparison-report = 📊 Rapport de comparaison
+comparison-report-hint = Courbes superposées à leur référence, écart point par point, statistiques et verdict PASS/FAIL
+comparison-none = Aucune comparaison active : figer une référence ou sélectionner une entrée de bibliothèque
+comparison-held-reference = Référence figée
+comparison-verdict = { $channel } : { $result } (score { $score } % par rapport à « { $reference } »)
+comparison-stats = Écart au plus proche (unités normalisées) : moyen { $mean } · RMS { $rms } · max { $max } (point { $point })
+comparison-deviation = Écart de chaque point à la référence
+comparison-result = Résultat de la comparaison : { $result }
+batch-title = Conversion de { $dir }
+batch-capture = Capture
+batch-summary = Mesures
+batch-files = Fichiers
+batch-error = Erreur : { $error }
+batch-capture-error = Erreur { $name } : { $error }
+batch-done = { $converted } captures converties, { $failed } en erreur
+
+# Modèles de rapport
+templates-header = Modèles de rapport
+template-html = Page HTML
+template-pdf = En-tête PDF (texte)
+template-builtin = intégré
+template-choose = Choisir…
+template-reset = Intégré
+template-fields = Champs : { $fields }
+template-hint = Écrire {"{{"} nom {"}}"} dans le fichier modèle pour insérer un champ. Le modèle PDF donne les lignes de texte imprimées au-dessus des tracés.
+
+# Mesures et vérification en ligne de commande
+stats-sweep = Balayage { $sweep } CH{ $channel } : { $points } points, V [{ $vmin } ; { $vmax }], I [{ $imin } ; { $imax }], aire { $area } ({ $ratio } %), { $component }, R { $resistance }
+stats-forward-voltage = Vf { $vf }
+validate-summary = { $reports } rapports, { $curves } courbes complètes (CH0 : { $ch0 }, CH1 : { $ch1 }), { $issues } problème(s)
+test-verdict = { $name } : { $result } ({ $score } %)
+acquire-running = Acquisition…
+config-file = Fichier : { $path }
+config-file-missing = Fichier : { $path } (absent)
+config-no-dir = Fichier : répertoire de configuration introuvable
+capture-running-for = Capture pendant { $seconds } s…
+capture-running = Capture jusqu'à l'interruption (Ctrl+C)…
+capture-saved = Capture sauvegardée : { $path } ({ $reports } rapports)
+capture-received = { $reports } rapports reçus
+capture-unreadable = ligne illisible (texte non UTF-8 ?)
+capture-not-hex = caractère non hexadécimal '{ $char }'
+capture-odd-digits = nombre impair de chiffres hex ({ $digits }), dernier ignoré
+capture-report-size = rapport de { $size } octets ({ $data } ou { $read } attendus) : { $consequence }
+capture-curve-lost = la courbe de la ligne { $line } et la suite sont perdues
+capture-report-ignored = rapport ignoré
+capture-orphans = { $count } rapport(s) de données hors courbe (lignes { $first } à { $last }) : ignorés
+capture-header-mid-curve = en-tête au milieu de la courbe commencée ligne { $line } ({ $reports }/{ $total } rapports) : il sera lu comme données
+capture-unknown-channel = canal inconnu { $channel } dans l'en-tête
+capture-invalid-curve = courbe invalide : { $error }
+capture-incomplete = courbe incomplète en fin de fichier : { $reports }/{ $total } rapports de données
+capture-empty = aucun rapport dans le fichier
+
+# Shell interactif
+shell-welcome = Shell CT220S : `help` pour la liste des commandes, `quit` pour quitter
+shell-error = Erreur : { $error }
+shell-sent = Rapport envoyé : { $report }
+shell-missing-reports = { $count } rapport(s) non reçu(s) en { $seconds } s
+shell-loaded = { $reports } rapports, { $curves } courbes
+shell-saved = Capture sauvegardée : { $path } ({ $reports } rapports)
+shell-no-curves = Aucune courbe en mémoire (capture, acquire ou load)
+shell-no-reports = Aucun rapport en mémoire (capture ou load)
+
+# Interface texte
+tui-no-terminal = L'interface texte demande un terminal
+tui-no-device = Pas de boîtier connecté
+tui-paused = pause
+tui-keys = [espace] pause  [c] canaux
+tui-keys-sweep = [←/→] balayage
+tui-keys-quit = [q] quitter
+tui-status = { $connection } — { $count } courbes, { $rate }/s
+tui-sweep = balayage { $position }/{ $total }
+
+# Plans de test
+plan-title = Plan de test : { $name }
+plan-board = Carte : { $serial }
+plan-point = Point { $index }/{ $count } : { $name }
+plan-settings = Réglages : { $settings }
+plan-ask-measure = [Entrée] mesurer, [s] passer, [q] arrêter
+plan-ask-retry = [r] recommencer, [Entrée] point suivant, [q] arrêter
+plan-channel-result = { $point } CH{ $channel } : { $result } ({ $score } %)
+plan-point-error = { $point } : erreur : { $error }
+plan-summary = { $count } points : { $pass } PASS, { $fail } FAIL, { $error } en erreur, { $skipped } passé(s), { $untested } non testé(s)
+plan-result = Résultat du plan : { $result }
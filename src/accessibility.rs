@@ -0,0 +1,17 @@
+// src/accessibility.rs
+
+use eframe::egui;
+
+/// Nom accessible (lecteur d'écran) des contrôles sans texte : icônes,
+/// tracés, vignettes
+pub trait AccessibleName {
+    fn accessible_name(self, typ: egui::WidgetType, name: impl ToString) -> Self;
+}
+
+impl AccessibleName for egui::Response {
+    fn accessible_name(self, typ: egui::WidgetType, name: impl ToString) -> Self {
+        let name = name.to_string();
+        self.widget_info(|| egui::WidgetInfo::labeled(typ, &name));
+        self
+    }
+}
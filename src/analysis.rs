@@ -0,0 +1,244 @@
+// src/analysis.rs
+
+use crate::calibration::Calibration;
+use crate::curve::CurveData;
+use serde::{Deserialize, Serialize};
+
+/// Courant (normalisé) à partir duquel on considère que le composant conduit
+const CONDUCTION_THRESHOLD: f32 = 0.1;
+
+/// En dessous de cette excursion (normalisée), l'axe est considéré plat
+const FLAT_LIMIT: f32 = 0.05;
+
+/// Coefficient de détermination minimal pour reconnaître une résistance
+const LINEAR_R2: f32 = 0.98;
+
+/// Aire de boucle (rapportée à la boîte englobante) d'un comportement capacitif
+const CAPACITIVE_AREA: f32 = 0.2;
+
+/// Analyses affichées dans le panneau de mesures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnalysisOptions {
+    pub resistance: bool,
+    pub forward_voltage: bool,
+    pub match_score: bool,
+    pub classification: bool,
+    /// Score de correspondance minimal (%) pour un verdict PASS
+    pub pass_threshold: f32,
+}
+
+impl Default for AnalysisOptions {
+    fn default() -> Self {
+        Self {
+            resistance: true,
+            forward_voltage: true,
+            match_score: true,
+            classification: true,
+            pass_threshold: 80.0,
+        }
+    }
+}
+
+/// Type de composant déduit de la forme de la signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Component {
+    Open,
+    Short,
+    Resistor,
+    Diode,
+    Capacitor,
+    Unknown,
+}
+
+impl Component {
+    /// Clé de traduction
+    pub fn key(self) -> &'static str {
+        match self {
+            Component::Open => "component-open",
+            Component::Short => "component-short",
+            Component::Resistor => "component-resistor",
+            Component::Diode => "component-diode",
+            Component::Capacitor => "component-capacitor",
+            Component::Unknown => "component-unknown",
+        }
+    }
+}
+
+/// Résultats d'analyse d'une courbe (valeurs normalisées)
+#[derive(Debug, Clone, Copy)]
+pub struct Analysis {
+    /// Pente I/V de la droite des moindres carrés
+    pub slope: f32,
+    /// Tension de seuil : plus petite tension où le courant dépasse le seuil
+    pub forward_voltage: Option<f32>,
+    pub component: Component,
+}
+
+impl Analysis {
+    pub fn of(curve: &CurveData) -> Self {
+        let (slope, r2) = linear_fit(&curve.voltage, &curve.current);
+        let forward_voltage = forward_voltage(curve);
+        let component = classify(curve, r2, forward_voltage);
+        Self {
+            slope,
+            forward_voltage,
+            component,
+        }
+    }
+
+    /// Résistance apparente (ΔV/ΔI) ; `None` pour un circuit ouvert
+    pub fn resistance(&self) -> Option<f32> {
+        (self.slope.abs() > f32::EPSILON).then(|| 1.0 / self.slope)
+    }
+
+    /// Résistance formatée : en ohms avec calibration, sinon en rapport normalisé
+    pub fn format_resistance(&self, calibration: &Calibration) -> String {
+        let Some(ratio) = self.resistance() else {
+            return "∞".to_string();
+        };
+        if !calibration.enabled {
+            return format!("{:.3}", ratio);
+        }
+        let ohms =
+            ratio * calibration.volts_full_scale / (calibration.milliamps_full_scale / 1000.0);
+        if ohms.abs() >= 1000.0 {
+            format!("{:.2} kΩ", ohms / 1000.0)
+        } else {
+            format!("{:.0} Ω", ohms)
+        }
+    }
+
+    /// Tension de seuil formatée, en volts avec calibration
+    pub fn format_forward_voltage(&self, calibration: &Calibration) -> Option<String> {
+        let vf = self.forward_voltage?;
+        Some(if calibration.enabled {
+            format!("{:.2} V", vf * calibration.volts_full_scale)
+        } else {
+            format!("{:.2}", vf)
+        })
+    }
+}
+
+/// Capacité (F) d'une signature elliptique : I crête = 2π·f·C·V crête.
+/// Nécessite les unités physiques et la fréquence de test.
+pub fn estimate_capacitance(
+    curve: &CurveData,
+    calibration: &Calibration,
+    frequency_hz: f32,
+) -> Option<f32> {
+    if !calibration.enabled || frequency_hz <= 0.0 {
+        return None;
+    }
+    let metrics = curve.metrics();
+    let volts = metrics.voltage_span / 2.0 * calibration.volts_full_scale;
+    let amps = metrics.current_span / 2.0 * calibration.milliamps_full_scale / 1000.0;
+    (volts > f32::EPSILON).then(|| amps / (2.0 * std::f32::consts::PI * frequency_hz * volts))
+}
+
+/// Capacité avec préfixe adapté (ex: "4.7 µF", "220 nF")
+pub fn format_capacitance(farads: f32) -> String {
+    let (value, unit) = if farads >= 1e-6 {
+        (farads * 1e6, "µF")
+    } else if farads >= 1e-9 {
+        (farads * 1e9, "nF")
+    } else {
+        (farads * 1e12, "pF")
+    };
+    let text = format!("{:.3}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    format!("{} {}", text, unit)
+}
+
+/// Droite des moindres carrés I = a·V + b : renvoie (a, R²)
+fn linear_fit(x: &[f32], y: &[f32]) -> (f32, f32) {
+    let n = x.len().min(y.len());
+    if n < 2 {
+        return (0.0, 0.0);
+    }
+    let mean_x = x[..n].iter().sum::<f32>() / n as f32;
+    let mean_y = y[..n].iter().sum::<f32>() / n as f32;
+
+    let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+    for (&xi, &yi) in x.iter().zip(y) {
+        let (dx, dy) = (xi - mean_x, yi - mean_y);
+        sxx += dx * dx;
+        sxy += dx * dy;
+        syy += dy * dy;
+    }
+    if sxx <= f32::EPSILON {
+        return (0.0, 0.0);
+    }
+    let slope = sxy / sxx;
+    let r2 = if syy <= f32::EPSILON {
+        0.0
+    } else {
+        (sxy * sxy) / (sxx * syy)
+    };
+    (slope, r2)
+}
+
+/// Plus petite tension (en valeur absolue) où le courant dépasse le seuil
+fn forward_voltage(curve: &CurveData) -> Option<f32> {
+    curve
+        .voltage
+        .iter()
+        .zip(&curve.current)
+        .filter(|(_, i)| i.abs() >= CONDUCTION_THRESHOLD)
+        .map(|(&v, _)| v)
+        .min_by(|a, b| a.abs().total_cmp(&b.abs()))
+}
+
+/// Aire de la boucle décrite par la courbe (formule du lacet), en unités
+/// normalisées
+pub fn loop_area(curve: &CurveData) -> f32 {
+    let points: Vec<(f32, f32)> = curve
+        .voltage
+        .iter()
+        .copied()
+        .zip(curve.current.iter().copied())
+        .collect();
+    if points.len() < 3 {
+        return 0.0;
+    }
+    let twice_area: f32 = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| a.0 * b.1 - b.0 * a.1)
+        .sum();
+    twice_area.abs() / 2.0
+}
+
+/// Aire de la boucle rapportée à la boîte englobante de la courbe
+pub fn loop_area_ratio(curve: &CurveData) -> f32 {
+    let metrics = curve.metrics();
+    let bounding = metrics.voltage_span * metrics.current_span;
+    if bounding <= f32::EPSILON {
+        return 0.0;
+    }
+    loop_area(curve) / bounding
+}
+
+fn classify(curve: &CurveData, r2: f32, forward_voltage: Option<f32>) -> Component {
+    let metrics = curve.metrics();
+    if metrics.current_span < FLAT_LIMIT {
+        return Component::Open;
+    }
+    if metrics.voltage_span < FLAT_LIMIT {
+        return Component::Short;
+    }
+    if loop_area_ratio(curve) > CAPACITIVE_AREA {
+        return Component::Capacitor;
+    }
+    if r2 > LINEAR_R2 {
+        return Component::Resistor;
+    }
+
+    // Diode : conduction franche d'un seul côté
+    let positive = curve.current.iter().copied().fold(0.0f32, f32::max);
+    let negative = -curve.current.iter().copied().fold(0.0f32, f32::min);
+    if forward_voltage.is_some() && positive.max(negative) > 3.0 * positive.min(negative) {
+        return Component::Diode;
+    }
+    Component::Unknown
+}
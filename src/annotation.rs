@@ -0,0 +1,16 @@
+// src/annotation.rs
+
+use serde::{Deserialize, Serialize};
+
+/// Texte placé sur le tracé, éventuellement avec une flèche vers un point.
+/// Les coordonnées sont normalisées (tension, courant dans [-1, 1]) pour
+/// s'appliquer à toutes les tailles de tracé et aux exports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Annotation {
+    pub text: String,
+    /// Position du texte
+    pub position: [f32; 2],
+    /// Point désigné par la flèche
+    #[serde(default)]
+    pub target: Option<[f32; 2]>,
+}
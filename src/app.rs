@@ -1,508 +1,3372 @@
 // src/app.rs
 
-use crate::backend::{run_file_reader, run_hid_reader, HidBackend, Command};
-use crate::curve::DualCurveData;
-use crate::image_export::{save_curve_as_png, save_dual_curves_as_png};
+use crate::accessibility::AccessibleName;
+use crate::analysis::{estimate_capacitance, format_capacitance, Analysis, Component};
+use crate::annotation::Annotation;
+use crate::board::{BoardView, TestPoint};
+use crate::backend::{frequency_hz, DeviceSetting, HidBackend, Playback, DEVICE_SETTINGS};
+use crate::cli::ViewOptions;
+use crate::command_palette::CommandPalette;
+use crate::compare::{CaptureFile, Comparison, STEP_INTERVAL};
+use crate::comparison_report::{save_comparison_report, ChannelDeviation, ReportFormat};
+use crate::compression::Compression;
+use crate::curve::{CurveData, DualCurveData};
+use crate::html_export::save_curves_as_html;
+use crate::i18n::{self, tr, tr_args, Language};
+use crate::json_export::save_curves_as_json;
+use crate::junit::{save_junit, TestCase};
+use crate::image_export::{
+    plot_image, save_animation, save_curve, save_dual_curves, save_overlay_curves, save_page,
+    ImageFormat, ImageText,
+};
+use crate::layout::{Panel, PanelLayout};
+use crate::legend::{self, LegendEntry};
+use crate::library::{match_score, Library, LibraryEntry, MergePolicy};
+use crate::mat_export::save_curves_as_mat;
+use crate::notifications::{Level, Notifications};
+use crate::numpy_export::save_curves_as_npz;
+use crate::plot_renderer::{self, PlotFrame, PlotStyle, REFERENCE_COLOR};
+use crate::print::{open_document, print_file};
+use crate::report::{self, plot_summary};
+use crate::results_db::{ResultsBrowser, ResultsDb, TestRecord};
+use crate::settings::{
+    Background, DisplayMode, Markers, Palette, PlotAspect, Settings, Theme,
+};
+use crate::config::REPORTS_PER_CURVE;
+use crate::csv_export::{
+    curves_as_tsv, save_curves_as_csv, save_metrics_as_csv, save_sweeps_as_csv, MetricsRow,
+};
+use crate::session::{Session, SessionFile, RELOAD_CHECK_INTERVAL};
+use crate::sigrok_export::save_sweeps_as_sigrok;
+use crate::status::Connection;
+use crate::template::FIELDS;
+use crate::timestamp::{file_timestamp, format_timestamp};
+use crate::video::{self, VideoRecorder};
 
 use eframe::egui;
-use std::path::Path;
+use egui_dock::{DockArea, Style as DockStyle, TabViewer};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{error, info, warn};
+
+/// Action exécutable depuis la palette de commandes
+#[derive(Debug, Clone, Copy)]
+enum Action {
+    SetDisplayMode(DisplayMode),
+    TogglePause,
+    ToggleFullscreen,
+    ExportImage(ImageFormat),
+    ExportCsv,
+    CopyData,
+    ExportJson,
+    ExportNumpy,
+    ExportMat,
+    ExportHtml,
+    Print,
+    ExportReport,
+    ExportComparison(ReportFormat),
+    /// Réglage du boîtier : (index dans DEVICE_SETTINGS, index de l'option)
+    DeviceSetting(usize, usize),
+}
+
+/// Fichiers écrits par l'export de l'historique
+#[derive(Debug, Clone, Copy)]
+enum HistoryExport {
+    /// Une image PNG par courbe
+    Png,
+    /// Un fichier CSV par courbe
+    Csv,
+    /// Toutes les courbes dans un seul CSV
+    SingleCsv,
+    /// Animation GIF, une image par balayage
+    Gif,
+    /// Session sigrok (PulseView), balayages mis bout à bout
+    Sigrok,
+    /// Mesures de chaque courbe dans un seul CSV
+    Metrics,
+}
+
+/// Choix proposés après l'arrêt d'un thread de lecture
+enum Recovery {
+    Reconnect,
+    OpenCapture,
+    Dismiss,
+}
+
+/// Traitements des conflits à l'import d'une bibliothèque et leur clé de
+/// traduction
+const MERGE_POLICIES: [(MergePolicy, &str); 3] = [
+    (MergePolicy::Skip, "library-conflict-skip"),
+    (MergePolicy::Overwrite, "library-conflict-overwrite"),
+    (MergePolicy::Rename, "library-conflict-rename"),
+];
+
+/// En-têtes des colonnes du navigateur de résultats
+const RESULT_COLUMNS: [&str; 6] = [
+    "results-date",
+    "results-serial",
+    "results-test-point",
+    "results-channel",
+    "results-score",
+    "results-verdict",
+];
+
+/// Extensions des fichiers de session : TOML, compressé par gzip ou zstd
+const SESSION_EXTENSIONS: &[&str] = &["toml", "gz", "zst"];
+
+/// Couleur de la courbe de bibliothèque superposée (tracée en pointillés)
+const LIBRARY_COLOR: egui::Color32 = egui::Color32::from_rgb(140, 140, 140);
+
+/// Modes d'affichage et leur clé de traduction
+pub const DISPLAY_MODES: [(DisplayMode, &str); 4] = [
+    (DisplayMode::SingleCh0, "display-single-ch0"),
+    (DisplayMode::SingleCh1, "display-single-ch1"),
+    (DisplayMode::DualOverlay, "display-dual-overlay"),
+    (DisplayMode::DualSideBySide, "display-dual-side-by-side"),
+];
+
+/// Dernier mode utilisé, restauré au lancement suivant (la géométrie de la
+/// fenêtre est conservée par eframe)
+#[derive(Serialize, Deserialize)]
+struct SessionState {
+    use_file_mode: bool,
+    file_path: String,
+    /// Annotations de l'onglet actif
+    #[serde(default)]
+    annotations: Vec<Annotation>,
+    /// Notes de l'onglet actif
+    #[serde(default)]
+    notes: String,
+}
 
 pub struct CT220SApp {
-    pub curve_data: Arc<Mutex<DualCurveData>>,
-    pub error_message: Arc<Mutex<Option<String>>>,
-    pub running: Arc<Mutex<bool>>,
-    pub use_file_mode: bool,
-    pub file_path: String,
-    pub dual_mode: bool,
-    pub hid_backend: Option<Arc<Mutex<HidBackend>>>,
+    /// Onglets ouverts, chacun avec sa propre source de données
+    tabs: Vec<Session>,
+    active: usize,
+    /// Bibliothèque de signatures et entrée superposée au tracé
+    library: Library,
+    library_selection: Option<usize>,
+    /// Nom saisi pour la prochaine entrée de bibliothèque
+    library_name: String,
+    pub settings: Settings,
+    command_palette: CommandPalette,
+    /// Fenêtre de comparaison A/B de deux captures
+    comparison: Comparison,
+    /// Plan de carte avec ses points de test
+    board: BoardView,
+    notifications: Notifications,
+    /// Texte de la prochaine annotation et présence d'une flèche
+    annotation_text: String,
+    annotation_arrow: bool,
+    /// Placement en cours : le prochain clic sur le tracé pose l'annotation
+    placing_annotation: bool,
+    /// Point visé par la flèche, choisi par le premier clic
+    pending_target: Option<[f32; 2]>,
+    /// Mode présentation : tracé seul, fenêtre en plein écran
+    fullscreen: bool,
+    /// Mode opérateur (ligne de production) : pas de réglages, seulement le
+    /// tracé, le verdict et le passage au point de test suivant
+    operator: bool,
+    /// Échelle de l'interface en vigueur, pour détecter un zoom au clavier
+    applied_ui_scale: f32,
+    /// Arrêt d'un thread de lecture : onglet concerné et message d'erreur
+    reader_failure: Option<(usize, String)>,
+    /// Fenêtre des options d'export d'images ouverte
+    export_options_open: bool,
+    /// Nom du style d'export choisi ou en cours de saisie
+    export_style_name: String,
+    /// Chemin complet du dernier fichier exporté
+    last_export: Option<String>,
+    /// Enregistrement vidéo du tracé en cours
+    recorder: Option<VideoRecorder>,
+    /// Verdicts des points de test validés en mode opérateur, pour le
+    /// rapport JUnit
+    test_results: Vec<TestCase>,
+    /// Courbes et scores des mêmes points de test, pour le tableau de mesures
+    test_metrics: Vec<MetricsRow>,
+    /// Début du point de test en cours
+    test_started: Instant,
+    /// Numéro de série de la carte testée, enregistré avec les résultats
+    board_serial: String,
+    results: ResultsBrowser,
 }
 
 impl CT220SApp {
-    pub fn new(_cc: &eframe::CreationContext<'_>, file_arg: Option<String>) -> Self {
-        let curve_data = Arc::new(Mutex::new(DualCurveData::new()));
-        let error_message = Arc::new(Mutex::new(None));
-        let running = Arc::new(Mutex::new(true));
+    pub fn new(
+        cc: &eframe::CreationContext<'_>,
+        file_arg: Option<String>,
+        operator: bool,
+        view: &ViewOptions,
+    ) -> Self {
+        let settings = Settings::load();
+        i18n::set_language(settings.language);
 
-        let (use_file_mode, file_path) = if let Some(path) = file_arg {
-            (true, path)
-        } else {
-            let default_path = "capture.txt".to_string();
-            let exists = Path::new(&default_path).exists();
-            (exists, default_path)
-        };
-
-        let dual_mode = use_file_mode;
-
-        let curve_data_clone = Arc::clone(&curve_data);
-        let error_clone = Arc::clone(&error_message);
-        let running_clone = Arc::clone(&running);
-        let file_path_clone = file_path.clone();
-
-        let hid_backend = if !use_file_mode {
-            // Mode USB : créer le backend HID
-            match HidBackend::new() {
-                Ok(backend) => {
-                    let backend_arc = Arc::new(Mutex::new(backend));
-                    let device = backend_arc.lock().unwrap().clone_device();
-                    
-                    *error_message.lock().unwrap() = Some("Périphérique USB connecté".to_string());
-                    
-                    // Lancer le thread de lecture
-                    thread::spawn(move || {
-                        println!("Mode périphérique USB - lecture démarrée");
-                        if let Err(e) = run_hid_reader(
-                            device,
-                            curve_data_clone,
-                            error_clone,
-                            running_clone,
-                        ) {
-                            eprintln!("Erreur HID reader: {}", e);
-                        }
-                    });
-                    
-                    Some(backend_arc)
-                }
-                Err(e) => {
-                    eprintln!("Impossible de créer le backend HID: {}", e);
-                    *error_message.lock().unwrap() = Some(format!("Erreur USB: {}", e));
-                    None
-                }
+        let last_session: Option<SessionState> = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, eframe::APP_KEY));
+
+        let (annotations, notes) = last_session
+            .as_ref()
+            .map(|last| (last.annotations.clone(), last.notes.clone()))
+            .unwrap_or_default();
+        let (use_file_mode, file_path) = match (file_arg, last_session) {
+            (Some(path), _) => (true, path),
+            (None, Some(last)) if !last.use_file_mode || Path::new(&last.file_path).exists() => {
+                (last.use_file_mode, last.file_path)
+            }
+            _ => {
+                let default_path = "capture.txt".to_string();
+                let exists = Path::new(&default_path).exists();
+                (exists, default_path)
             }
-        } else {
-            // Mode fichier
-            thread::spawn(move || {
-                println!("Mode fichier: lecture de {}", file_path_clone);
-                if let Err(e) =
-                    run_file_reader(&file_path_clone, curve_data_clone, error_clone, running_clone)
-                {
-                    eprintln!("Erreur lecture fichier: {}", e);
-                }
-            });
-            None
         };
 
-        Self {
-            curve_data,
-            error_message,
-            running,
-            use_file_mode,
-            file_path,
-            dual_mode,
-            hid_backend,
+        let mut app = Self {
+            tabs: vec![Session::new(file_path.clone(), &settings)],
+            active: 0,
+            library: Library::load(),
+            library_selection: None,
+            library_name: String::new(),
+            settings,
+            command_palette: CommandPalette::default(),
+            comparison: Comparison::default(),
+            board: BoardView::default(),
+            notifications: Notifications::default(),
+            annotation_text: String::new(),
+            annotation_arrow: false,
+            placing_annotation: false,
+            pending_target: None,
+            fullscreen: false,
+            operator,
+            applied_ui_scale: 1.0,
+            reader_failure: None,
+            export_options_open: false,
+            export_style_name: String::new(),
+            last_export: None,
+            recorder: None,
+            test_results: Vec::new(),
+            test_metrics: Vec::new(),
+            test_started: Instant::now(),
+            board_serial: String::new(),
+            results: ResultsBrowser::default(),
+        };
+        app.tabs[0].annotations = annotations;
+        app.tabs[0].notes = notes;
+        if let Some(path) = app.settings.board_map.clone() {
+            match BoardView::open(path.into()) {
+                Ok(board) => app.board = board,
+                Err(e) => warn!("Plan de carte non rouvert: {}", e),
+            }
+        }
+        if operator {
+            // L'opérateur commence au premier point de test
+            app.next_test_point();
         }
-    }
-
-    fn draw_single_channel(&self, ui: &mut egui::Ui, channel: u8, size: f32) {
-        let desired_size = egui::vec2(size, size);
-        let (response, painter) = ui.allocate_painter(desired_size, egui::Sense::hover());
-        let rect = response.rect;
-
-        painter.rect_filled(rect, 0.0, egui::Color32::WHITE);
-
-        let center = rect.center();
-        let scale = rect.width().min(rect.height()) * 0.45;
 
-        let grid_color = egui::Color32::from_gray(200);
-        for i in -10..=10 {
-            let offset = (i as f32) * scale / 10.0;
-            painter.line_segment(
-                [
-                    egui::pos2(center.x + offset, rect.top()),
-                    egui::pos2(center.x + offset, rect.bottom()),
-                ],
-                egui::Stroke::new(0.5, grid_color),
-            );
-            painter.line_segment(
-                [
-                    egui::pos2(rect.left(), center.y + offset),
-                    egui::pos2(rect.right(), center.y + offset),
-                ],
-                egui::Stroke::new(0.5, grid_color),
-            );
+        view.apply(&app.tabs[0], &mut app.settings);
+        if use_file_mode {
+            app.start_file_reader(file_path);
+        } else {
+            app.start_usb_reader();
         }
+        app
+    }
 
-        let axis_color = egui::Color32::BLACK;
-        painter.line_segment(
-            [egui::pos2(rect.left(), center.y), egui::pos2(rect.right(), center.y)],
-            egui::Stroke::new(1.0, axis_color),
-        );
-        painter.line_segment(
-            [egui::pos2(center.x, rect.top()), egui::pos2(center.x, rect.bottom())],
-            egui::Stroke::new(1.0, axis_color),
-        );
+    /// Onglet actif
+    fn tab(&self) -> &Session {
+        &self.tabs[self.active]
+    }
 
-        if let Ok(data) = self.curve_data.lock() {
-            let curve_opt = if channel == 0 {
-                &data.channel0
-            } else {
-                &data.channel1
-            };
+    fn tab_mut(&mut self) -> &mut Session {
+        &mut self.tabs[self.active]
+    }
 
-            if let Some(curve) = curve_opt {
-                let points: Vec<egui::Pos2> = curve
-                    .voltage
-                    .iter()
-                    .zip(curve.current.iter())
-                    .map(|(&v, &i)| egui::pos2(center.x + v * scale, center.y - i * scale))
-                    .collect();
+    fn start_usb_reader(&mut self) {
+        self.tabs[self.active].start_usb_reader(&self.settings);
+    }
 
-                if points.len() > 1 {
-                    let color = if channel == 0 {
-                        egui::Color32::from_rgb(255, 100, 0)
-                    } else {
-                        egui::Color32::BLUE
-                    };
+    fn start_file_reader(&mut self, path: String) {
+        self.tabs[self.active].start_file_reader(path, &mut self.settings);
+    }
 
-                    painter.add(egui::Shape::line(points, egui::Stroke::new(1.5, color)));
-                }
-            }
-        }
+    fn displayed_data(&self) -> DualCurveData {
+        self.tab().displayed_data()
+    }
 
-        let channel_name = if channel == 0 { "CH0" } else { "CH1" };
-        painter.text(
-            egui::pos2(rect.left() + 30.0, rect.top() + 15.0),
-            egui::Align2::LEFT_TOP,
-            channel_name,
-            egui::FontId::proportional(16.0),
-            egui::Color32::BLACK,
-        );
-        painter.text(
-            egui::pos2(rect.right() - 60.0, center.y - 15.0),
-            egui::Align2::CENTER_CENTER,
-            "Tension",
-            egui::FontId::default(),
-            egui::Color32::BLACK,
-        );
-        painter.text(
-            egui::pos2(center.x + 15.0, rect.top() + 20.0),
-            egui::Align2::CENTER_CENTER,
-            "Courant",
-            egui::FontId::default(),
-            egui::Color32::BLACK,
-        );
+    fn set_paused(&mut self, paused: bool) {
+        self.tabs[self.active].set_paused(paused, self.settings.record_while_paused);
     }
 
-    fn draw_dual_overlay(&self, ui: &mut egui::Ui, size: f32) {
-        let desired_size = egui::vec2(size, size);
-        let (response, painter) = ui.allocate_painter(desired_size, egui::Sense::hover());
-        let rect = response.rect;
+    fn update_discard(&self) {
+        self.tab().update_discard(self.settings.record_while_paused);
+    }
 
-        painter.rect_filled(rect, 0.0, egui::Color32::WHITE);
+    fn inspect_curve(&mut self, curve: CurveData) {
+        self.tabs[self.active].inspect_curve(curve, self.settings.record_while_paused);
+    }
 
-        let center = rect.center();
-        let scale = rect.width().min(rect.height()) * 0.45;
+    /// Ouvrir un nouvel onglet et l'activer
+    fn open_tab(&mut self, session: Session) {
+        self.tabs.push(session);
+        self.active = self.tabs.len() - 1;
+    }
 
-        let grid_color = egui::Color32::from_gray(200);
-        for i in -10..=10 {
-            let offset = (i as f32) * scale / 10.0;
-            painter.line_segment(
-                [
-                    egui::pos2(center.x + offset, rect.top()),
-                    egui::pos2(center.x + offset, rect.bottom()),
-                ],
-                egui::Stroke::new(0.5, grid_color),
-            );
-            painter.line_segment(
-                [
-                    egui::pos2(rect.left(), center.y + offset),
-                    egui::pos2(rect.right(), center.y + offset),
-                ],
-                egui::Stroke::new(0.5, grid_color),
-            );
+    /// Fermer un onglet (son thread de lecture s'arrête) ; le dernier reste ouvert
+    fn close_tab(&mut self, index: usize) {
+        if self.tabs.len() <= 1 {
+            return;
         }
+        self.tabs.remove(index);
+        if self.active > index || self.active >= self.tabs.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+    }
 
-        let axis_color = egui::Color32::BLACK;
-        painter.line_segment(
-            [egui::pos2(rect.left(), center.y), egui::pos2(rect.right(), center.y)],
-            egui::Stroke::new(1.0, axis_color),
-        );
-        painter.line_segment(
-            [egui::pos2(center.x, rect.top()), egui::pos2(center.x, rect.bottom())],
-            egui::Stroke::new(1.0, axis_color),
-        );
+    /// Contenu d'un onglet, quel que soit son emplacement
+    fn draw_panel(&mut self, ui: &mut egui::Ui, panel: Panel) {
+        match panel {
+            Panel::Plot => {
+                self.draw_title_row(ui);
+                self.draw_plot(ui);
+            }
+            Panel::Acquisition => self.draw_acquisition_panel(ui),
+            Panel::Display => self.draw_display_panel(ui),
+            Panel::Commands => self.draw_commands_panel(ui),
+            Panel::History => self.draw_history_panel(ui),
+            Panel::Library => self.draw_library_panel(ui),
+            Panel::Measurements => self.draw_measurements_panel(ui),
+            Panel::Board => self.draw_board_panel(ui),
+        }
+    }
 
-        if let Ok(data) = self.curve_data.lock() {
-            if let Some(curve) = &data.channel0 {
-                let points: Vec<egui::Pos2> = curve
-                    .voltage
-                    .iter()
-                    .zip(curve.current.iter())
-                    .map(|(&v, &i)| egui::pos2(center.x + v * scale, center.y - i * scale))
-                    .collect();
+    /// Tracé selon le mode d'affichage ; un clic y pose l'annotation en attente
+    fn draw_plot(&mut self, ui: &mut egui::Ui) {
+        let available = ui.available_size();
+        let click = match self.settings.display_mode {
+            DisplayMode::SingleCh0 => self.draw_single_channel(ui, 0, self.plot_size(available)),
+            DisplayMode::SingleCh1 => self.draw_single_channel(ui, 1, self.plot_size(available)),
+            DisplayMode::DualOverlay => self.draw_dual_overlay(ui, self.plot_size(available)),
+            DisplayMode::DualSideBySide => {
+                // Même taille pour les deux tracés : échelles synchronisées
+                let half = egui::vec2(
+                    (available.x - ui.spacing().item_spacing.x) / 2.0,
+                    available.y,
+                );
+                let size = self.plot_size(half);
+                ui.horizontal(|ui| {
+                    let left = self.draw_single_channel(ui, 0, size);
+                    let right = self.draw_single_channel(ui, 1, size);
+                    left.or(right)
+                })
+                .inner
+            }
+        };
+        if let Some(pos) = click {
+            self.handle_plot_click(pos);
+        }
+    }
 
-                if points.len() > 1 {
-                    painter.add(egui::Shape::line(
-                        points,
-                        egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 100, 0)),
-                    ));
+    /// Photo de la carte : un clic sur un point de test sélectionne sa signature
+    fn draw_board_panel(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(tr("board-load-image")).clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter(tr("board-filter-image"), &["png", "jpg", "jpeg", "bmp"])
+                    .pick_file()
+                {
+                    self.board = BoardView::default();
+                    self.board.set_image(path.to_string_lossy().into_owned());
                 }
             }
+            if ui.button(tr("board-open")).clicked() {
+                self.open_board_map();
+            }
+            if ui
+                .add_enabled(!self.board.map.image.is_empty(), egui::Button::new(tr("board-save")))
+                .clicked()
+            {
+                self.save_board_map();
+            }
+        });
 
-            if let Some(curve) = &data.channel1 {
-                let points: Vec<egui::Pos2> = curve
-                    .voltage
-                    .iter()
-                    .zip(curve.current.iter())
-                    .map(|(&v, &i)| egui::pos2(center.x + v * scale, center.y - i * scale))
-                    .collect();
+        ui.checkbox(&mut self.board.editing, tr("board-edit"));
+        if self.board.editing {
+            ui.horizontal(|ui| {
+                let label = ui.label(tr("board-point-name"));
+                ui.text_edit_singleline(&mut self.board.new_point)
+                    .labelled_by(label.id);
+            });
+            ui.weak(tr("board-edit-hint"));
+        }
 
-                if points.len() > 1 {
-                    painter.add(egui::Shape::line(
-                        points,
-                        egui::Stroke::new(2.0, egui::Color32::BLUE),
-                    ));
-                }
+        let texture = match self.board.texture(ui.ctx()) {
+            Ok(texture) => texture,
+            Err(e) => {
+                self.notifications
+                    .push(Level::Error, tr_args("board-error", &[("error", e.into())]));
+                None
+            }
+        };
+        match texture {
+            Some(texture) => self.draw_board_image(ui, &texture),
+            None => {
+                ui.label(tr("board-no-image"));
             }
         }
 
-        painter.text(
-            egui::pos2(rect.left() + 30.0, rect.top() + 15.0),
-            egui::Align2::LEFT_TOP,
-            "CH0",
-            egui::FontId::proportional(16.0),
-            egui::Color32::from_rgb(255, 100, 0),
-        );
-        painter.text(
-            egui::pos2(rect.left() + 30.0, rect.top() + 35.0),
-            egui::Align2::LEFT_TOP,
-            "CH1",
-            egui::FontId::proportional(16.0),
-            egui::Color32::BLUE,
-        );
-
-        painter.text(
-            egui::pos2(rect.right() - 60.0, center.y - 15.0),
-            egui::Align2::CENTER_CENTER,
-            "Tension",
-            egui::FontId::default(),
-            egui::Color32::BLACK,
-        );
-        painter.text(
-            egui::pos2(center.x + 15.0, rect.top() + 20.0),
-            egui::Align2::CENTER_CENTER,
-            "Courant",
-            egui::FontId::default(),
-            egui::Color32::BLACK,
-        );
+        self.draw_test_point_list(ui);
     }
-}
 
-impl eframe::App for CT220SApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("CT220S - Courbe V-I");
+    /// Photo redimensionnée à la largeur du panneau, avec les points de test
+    fn draw_board_image(&mut self, ui: &mut egui::Ui, texture: &egui::TextureHandle) {
+        let image_size = texture.size_vec2();
+        let size = image_size * (ui.available_width() / image_size.x).min(1.0);
+        let (response, painter) = ui.allocate_painter(size, egui::Sense::click());
+        let response = response.accessible_name(egui::WidgetType::Other, tr("panel-board"));
+        let rect = response.rect;
+        let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+        painter.image(texture.id(), rect, uv, egui::Color32::WHITE);
 
-            if self.use_file_mode {
-                ui.label(format!("📁 Mode fichier: {}", self.file_path));
+        for (i, point) in self.board.map.points.iter().enumerate() {
+            let pos = rect.min + egui::vec2(point.position[0], point.position[1]) * rect.size();
+            let color = if self.board.active == Some(i) {
+                egui::Color32::YELLOW
             } else {
-                ui.label("🔌 Mode périphérique USB");
+                egui::Color32::RED
+            };
+            painter.circle(pos, 6.0, color, egui::Stroke::new(1.5, egui::Color32::BLACK));
+            painter.text(
+                pos + egui::vec2(8.0, 0.0),
+                egui::Align2::LEFT_CENTER,
+                &point.name,
+                egui::FontId::proportional(13.0),
+                color,
+            );
+        }
+
+        let Some(pointer) = response.interact_pointer_pos().filter(|_| response.clicked())
+        else {
+            return;
+        };
+        let relative = (pointer - rect.min) / rect.size();
+        let position = [relative.x, relative.y];
+        match self.board.map.point_at(position) {
+            Some(i) => self.select_test_point(i),
+            None if self.board.editing => {
+                let name = self.board.new_point.trim();
+                let name = if name.is_empty() {
+                    format!("TP{}", self.board.map.points.len() + 1)
+                } else {
+                    name.to_string()
+                };
+                let signature = self.selected_library_entry().map(|e| e.name.clone());
+                self.board.map.points.push(TestPoint {
+                    name,
+                    position,
+                    signature,
+                });
+                self.board.new_point.clear();
             }
+            None => {}
+        }
+    }
 
+    /// Liste des points : sélection, signature associée et suppression
+    fn draw_test_point_list(&mut self, ui: &mut egui::Ui) {
+        let mut selected = None;
+        let mut removed = None;
+        let names: Vec<String> = self.library.entries.iter().map(|e| e.name.clone()).collect();
+
+        for (i, point) in self.board.map.points.iter_mut().enumerate() {
             ui.horizontal(|ui| {
-                ui.label("Mode:");
-                ui.radio_value(&mut self.dual_mode, false, "Single CH1");
-                ui.radio_value(&mut self.dual_mode, true, "Dual Overlay");
+                if ui.selectable_label(self.board.active == Some(i), &point.name).clicked() {
+                    selected = Some(i);
+                }
+                let current = point.signature.clone().unwrap_or_else(|| tr("library-none"));
+                egui::ComboBox::from_id_source(("test_point_signature", i))
+                    .selected_text(current)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut point.signature, None, tr("library-none"));
+                        for name in &names {
+                            ui.selectable_value(&mut point.signature, Some(name.clone()), name);
+                        }
+                    })
+                    .response
+                    .accessible_name(
+                        egui::WidgetType::ComboBox,
+                        format!("{} {}", tr("board-signature"), point.name),
+                    );
+                if self.board.editing
+                    && ui
+                        .small_button("🗑")
+                        .accessible_name(egui::WidgetType::Button, tr("delete"))
+                        .clicked()
+                {
+                    removed = Some(i);
+                }
             });
+        }
 
-            // Panneau de commandes USB (uniquement en mode USB)
-            if let Some(backend) = &self.hid_backend {
-                ui.separator();
-                ui.heading("⚡ Commandes");
+        if let Some(i) = selected {
+            self.select_test_point(i);
+        }
+        if let Some(i) = removed {
+            self.board.map.points.remove(i);
+            self.board.active = None;
+        }
+    }
 
-                ui.horizontal(|ui| {
-                    ui.label("Fréquence:");
-                    if ui.button("10Hz").clicked() {
-                        if let Err(e) = backend.lock().unwrap().send_cmd(Command::SetFreq(0)) {
-                            *self.error_message.lock().unwrap() =
-                                Some(format!("❌ Erreur cmd: {}", e));
-                        } else {
-                            *self.error_message.lock().unwrap() =
-                                Some("✅ Fréquence: 100Hz".to_string());
-                        }
-                    }
-                    if ui.button("100Hz").clicked() {
-                        if let Err(e) = backend.lock().unwrap().send_cmd(Command::SetFreq(1)) {
-                            *self.error_message.lock().unwrap() =
-                                Some(format!("❌ Erreur cmd: {}", e));
-                        } else {
-                            *self.error_message.lock().unwrap() =
-                                Some("✅ Fréquence: 1kHz".to_string());
-                        }
-                    }
-                    if ui.button("500Hz").clicked() {
-                        if let Err(e) = backend.lock().unwrap().send_cmd(Command::SetFreq(2)) {
-                            *self.error_message.lock().unwrap() =
-                                Some(format!("❌ Erreur cmd: {}", e));
-                        } else {
-                            *self.error_message.lock().unwrap() =
-                                Some("✅ Fréquence: 10kHz".to_string());
-                        }
-                    }
-                    if ui.button("2kHz").clicked() {
-                        if let Err(e) = backend.lock().unwrap().send_cmd(Command::SetFreq(3)) {
-                            *self.error_message.lock().unwrap() =
-                                Some(format!("❌ Erreur cmd: {}", e));
-                        } else {
-                            *self.error_message.lock().unwrap() =
-                                Some("✅ Fréquence: 10kHz".to_string());
-                        }
-                    }
+    /// Activer un point de test et superposer sa signature de référence
+    fn select_test_point(&mut self, index: usize) {
+        self.board.active = Some(index);
+        let signature = self.board.map.points[index].signature.as_deref();
+        self.library_selection = signature
+            .and_then(|name| self.library.entries.iter().position(|e| e.name == name));
+    }
 
+    /// Rouvrir une session enregistrée dans un nouvel onglet
+    fn open_session_file(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(tr("session-filter"), SESSION_EXTENSIONS)
+            .pick_file()
+        else {
+            return;
+        };
+        match SessionFile::load(&path) {
+            Ok(file) => {
+                let name = path
+                    .file_stem()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                self.open_tab(Session::from_file(name, file, &self.settings));
+            }
+            Err(e) => self
+                .notifications
+                .push(Level::Error, tr_args("session-error", &[("error", e.into())])),
+        }
+    }
 
-                });
+    /// Enregistrer l'onglet actif : courbes, référence, réglages, annotations, notes
+    fn save_session_file(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(tr("session-filter"), SESSION_EXTENSIONS)
+            .set_file_name(format!("session.toml{}", self.settings.session_compression.suffix()))
+            .save_file()
+        else {
+            return;
+        };
+        match self.tab().to_file().save(&path) {
+            Ok(()) => {
+                // Le choix fait dans la boîte de dialogue devient la proposition suivante
+                self.settings.session_compression = Compression::from_path(&path);
+                self.notifications.push(Level::Success, tr("saved"));
+            }
+            Err(e) => self
+                .notifications
+                .push(Level::Error, tr_args("session-error", &[("error", e.into())])),
+        }
+    }
 
-                ui.horizontal(|ui| {
-                    ui.label("Résistance:");
-                    if ui.button("47R").clicked() {
-                        if let Err(e) = backend.lock().unwrap().send_cmd(Command::SetRes(2)) {
-                            *self.error_message.lock().unwrap() =
-                                Some(format!("❌ Erreur cmd: {}", e));
-                        } else {
-                            *self.error_message.lock().unwrap() =
-                                Some("✅ Résolution: Basse".to_string());
-                        }
-                    }
-                    if ui.button("1K").clicked() {
-                        if let Err(e) = backend.lock().unwrap().send_cmd(Command::SetRes(1)) {
-                            *self.error_message.lock().unwrap() =
-                                Some(format!("❌ Erreur cmd: {}", e));
-                        } else {
-                            *self.error_message.lock().unwrap() =
-                                Some("✅ Résolution: Haute".to_string());
-                        }
-                    }
-                    if ui.button("10K").clicked() {
-                        if let Err(e) = backend.lock().unwrap().send_cmd(Command::SetRes(0)) {
-                            *self.error_message.lock().unwrap() =
-                                Some(format!("❌ Erreur cmd: {}", e));
-                        } else {
-                            *self.error_message.lock().unwrap() =
-                                Some("✅ Résolution: Basse".to_string());
-                        }
-                    }
-                    if ui.button("offset").clicked() {
-                        if let Err(e) = backend.lock().unwrap().send_cmd(Command::SetRes(0)) {
-                            *self.error_message.lock().unwrap() =
-                                Some(format!("❌ Erreur cmd: {}", e));
-                        } else {
-                            *self.error_message.lock().unwrap() =
-                                Some("✅ Résolution: Haute".to_string());
-                        }
-                    }
+    fn open_board_map(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(tr("board-filter-map"), &["toml"])
+            .pick_file()
+        else {
+            return;
+        };
+        match BoardView::open(path.clone()) {
+            Ok(board) => {
+                self.board = board;
+                self.settings.board_map = Some(path.to_string_lossy().into_owned());
+            }
+            Err(e) => self
+                .notifications
+                .push(Level::Error, tr_args("board-error", &[("error", e.into())])),
+        }
+    }
 
-                });
+    fn save_board_map(&mut self) {
+        let path = match self.board.path.clone() {
+            Some(path) => path,
+            None => {
+                let Some(path) = rfd::FileDialog::new()
+                    .add_filter(tr("board-filter-map"), &["toml"])
+                    .set_file_name("board.toml")
+                    .save_file()
+                else {
+                    return;
+                };
+                path
+            }
+        };
+        match self.board.map.save(&path) {
+            Ok(()) => {
+                self.settings.board_map = Some(path.to_string_lossy().into_owned());
+                self.board.path = Some(path);
+                self.notifications.push(Level::Success, tr("board-saved"));
+            }
+            Err(e) => self
+                .notifications
+                .push(Level::Error, tr_args("board-error", &[("error", e.into())])),
+        }
+    }
 
-                ui.horizontal(|ui| {
-                    ui.label("Mode:");
-                    if ui.button("Simple").clicked() {
-                        if let Err(e) = backend.lock().unwrap().send_cmd(Command::SetMode(0)) {
-                            *self.error_message.lock().unwrap() =
-                                Some(format!("❌ Erreur cmd: {}", e));
-                        } else {
-                            *self.error_message.lock().unwrap() =
-                                Some("✅ Mode: Simple".to_string());
-                        }
-                    }
-                    if ui.button("Dual").clicked() {
-                        if let Err(e) = backend.lock().unwrap().send_cmd(Command::SetMode(1)) {
-                            *self.error_message.lock().unwrap() =
-                                Some(format!("❌ Erreur cmd: {}", e));
-                        } else {
-                            *self.error_message.lock().unwrap() =
-                                Some("✅ Mode: Dual".to_string());
-                        }
-                    }
-                });
+    /// Résultats d'analyse des courbes affichées, en gros caractères
+    fn draw_measurements_panel(&mut self, ui: &mut egui::Ui) {
+        let data = self.displayed_data();
+        let dark = self.plot_is_dark(ui.visuals());
+        let options = self.settings.analysis.clone();
+        let calibration = &self.settings.calibration;
+        let value = |text: String| egui::RichText::new(text).size(28.0).strong();
 
-                ui.horizontal(|ui| {
-                    ui.label("Voltage:");
-                    if ui.button("2.5").clicked() {
-                        if let Err(e) = backend.lock().unwrap().send_cmd(Command::SetVolt(0)) {
-                            *self.error_message.lock().unwrap() =
-                                Some(format!("❌ Erreur cmd: {}", e));
-                        } else {
-                            *self.error_message.lock().unwrap() =
-                                Some("✅ Voltage: 3.3V".to_string());
-                        }
+        let mut shown = false;
+        for channel in self.displayed_channels() {
+            let Some(curve) = data.channel(channel) else {
+                continue;
+            };
+            shown = true;
+            let analysis = Analysis::of(curve);
+            ui.colored_label(
+                self.channel_color(channel, dark),
+                egui::RichText::new(format!("CH{}", channel)).heading(),
+            );
+            egui::Grid::new(("measurements", channel))
+                .num_columns(2)
+                .show(ui, |ui| {
+                    if options.resistance {
+                        ui.label(tr("measure-resistance"));
+                        ui.label(value(analysis.format_resistance(calibration)));
+                        ui.end_row();
                     }
-                    if ui.button("5V").clicked() {
-                        if let Err(e) = backend.lock().unwrap().send_cmd(Command::SetVolt(1)) {
-                            *self.error_message.lock().unwrap() =
-                                Some(format!("❌ Erreur cmd: {}", e));
-                        } else {
-                            *self.error_message.lock().unwrap() =
-                                Some("✅ Voltage: 5V".to_string());
-                        }
+                    if options.forward_voltage {
+                        let vf = analysis.format_forward_voltage(calibration);
+                        ui.label(tr("measure-forward-voltage"));
+                        ui.label(value(vf.unwrap_or_else(|| "—".to_string())));
+                        ui.end_row();
                     }
-                    if ui.button("10V").clicked() {
-                        if let Err(e) = backend.lock().unwrap().send_cmd(Command::SetVolt(2)) {
-                            *self.error_message.lock().unwrap() =
-                                Some(format!("❌ Erreur cmd: {}", e));
-                        } else {
-                            *self.error_message.lock().unwrap() =
-                                Some("✅ Voltage: 5V".to_string());
-                        }
-                    }                    
-                    if ui.button("20V").clicked() {
-                        if let Err(e) = backend.lock().unwrap().send_cmd(Command::SetVolt(3)) {
-                            *self.error_message.lock().unwrap() =
-                                Some(format!("❌ Erreur cmd: {}", e));
-                        } else {
-                            *self.error_message.lock().unwrap() =
-                                Some("✅ Voltage: 5V".to_string());
-                        }
+                    if options.match_score {
+                        let score = self
+                            .match_target(channel)
+                            .map(|target| format!("{:.0} %", match_score(curve, target)));
+                        ui.label(tr("measure-match"));
+                        ui.label(value(score.unwrap_or_else(|| "—".to_string())));
+                        ui.end_row();
+                    }
+                    if options.classification {
+                        ui.label(tr("measure-component"));
+                        ui.label(value(tr(analysis.component.key())));
+                        ui.end_row();
                     }
                 });
-            }
+            ui.separator();
+        }
+        if !shown {
+            ui.label(tr("measure-none"));
+        }
+
+        ui.collapsing(tr("measure-options"), |ui| {
+            let options = &mut self.settings.analysis;
+            ui.checkbox(&mut options.resistance, tr("measure-resistance"));
+            ui.checkbox(&mut options.forward_voltage, tr("measure-forward-voltage"));
+            ui.checkbox(&mut options.match_score, tr("measure-match"));
+            ui.checkbox(&mut options.classification, tr("measure-component"));
+            ui.add(
+                egui::Slider::new(&mut options.pass_threshold, 0.0..=100.0)
+                    .suffix(" %")
+                    .text(tr("pass-threshold")),
+            );
+        });
+    }
+
+    /// Canaux visibles dans le mode d'affichage courant
+    fn displayed_channels(&self) -> Vec<u8> {
+        match self.settings.display_mode {
+            DisplayMode::SingleCh0 => vec![0],
+            DisplayMode::SingleCh1 => vec![1],
+            DisplayMode::DualOverlay | DisplayMode::DualSideBySide => vec![0, 1],
+        }
+        .into_iter()
+        .filter(|&channel| self.channel_visible(channel))
+        .collect()
+    }
+
+    /// Verdict du point de test : scores de chaque canal affiché contre
+    /// l'entrée sélectionnée ; `None` sans entrée ou sans courbe
+    fn test_verdict(&self) -> Option<(bool, f32)> {
+        let entry = self.selected_library_entry()?;
+        report::verdict(
+            &self.displayed_data(),
+            &self.displayed_channels(),
+            &entry.curve,
+            self.settings.analysis.pass_threshold,
+        )
+    }
+
+    /// Bandeau du mode opérateur : point de test, verdict et bouton suivant
+    fn draw_operator_bar(&mut self, ui: &mut egui::Ui) {
+        let big = |text: String| egui::RichText::new(text).size(32.0).strong();
+        ui.horizontal(|ui| {
+            let label = ui.label(big(tr("operator-serial")));
+            let serial = egui::TextEdit::singleline(&mut self.board_serial)
+                .font(egui::FontId::proportional(32.0))
+                .desired_width(200.0);
+            ui.add(serial).labelled_by(label.id);
+            ui.separator();
 
+            let Some(entry) = self.selected_library_entry() else {
+                ui.label(big(tr("operator-no-test-points")));
+                return;
+            };
+            ui.label(big(entry.name.clone()));
             ui.separator();
 
-            if ui.button("💾 Sauvegarder PNG").clicked() {
-                if let Ok(data) = self.curve_data.lock() {
-                    let result = if self.dual_mode {
-                        save_dual_curves_as_png(&data, "curves_export.png")
-                    } else if let Some(ch1) = &data.channel1 {
-                        save_curve_as_png(ch1, "curve_ch1_export.png")
+            match self.test_verdict() {
+                Some((pass, score)) => {
+                    let (key, color) = if pass {
+                        ("operator-pass", egui::Color32::from_rgb(0, 150, 0))
                     } else {
-                        Err("Pas de données CH1".to_string())
+                        ("operator-fail", egui::Color32::from_rgb(200, 0, 0))
                     };
+                    egui::Frame::none()
+                        .fill(color)
+                        .inner_margin(egui::Margin::symmetric(16.0, 4.0))
+                        .rounding(4.0)
+                        .show(ui, |ui| {
+                            ui.label(big(tr(key)).color(egui::Color32::WHITE));
+                        });
+                    ui.label(big(format!("{:.0} %", score)));
+                }
+                None => {
+                    ui.label(big(tr("operator-waiting")));
+                }
+            }
 
-                    match result {
-                        Ok(_) => {
-                            *self.error_message.lock().unwrap() =
-                                Some("✅ Sauvegardé".to_string());
-                        }
-                        Err(e) => {
-                            *self.error_message.lock().unwrap() =
-                                Some(format!("❌ Erreur: {}", e));
-                        }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button(big(tr("operator-next"))).clicked() {
+                    self.record_test_result();
+                    self.next_test_point();
+                }
+                if !self.test_results.is_empty() {
+                    let count = self.test_results.len();
+                    let label = tr_args("operator-save-results", &[("count", count.into())]);
+                    if ui.button(label).on_hover_text(tr("operator-save-results-hint")).clicked()
+                    {
+                        self.save_test_results();
                     }
                 }
+            });
+        });
+    }
+
+    /// Conserver le verdict de chaque canal affiché contre le point de test en
+    /// cours, validé par le passage au suivant, et l'enregistrer dans la base
+    /// de résultats si elle est activée
+    fn record_test_result(&mut self) {
+        let Some(entry) = self.selected_library_entry() else {
+            return;
+        };
+        let (test_point, signature) = (entry.name.clone(), entry.curve.clone());
+        let data = self.displayed_data();
+        let curves: Vec<&CurveData> = self
+            .displayed_channels()
+            .iter()
+            .filter_map(|&c| data.channel(c))
+            .collect();
+        let threshold = self.settings.analysis.pass_threshold;
+        let time = self.test_started.elapsed().as_secs_f32();
+        let cases: Vec<TestCase> = curves
+            .iter()
+            .map(|curve| {
+                let score = match_score(curve, &signature);
+                TestCase {
+                    name: format!("{} CH{}", test_point, curve.channel),
+                    classname: self.tab().title(),
+                    score,
+                    pass: score >= threshold,
+                    threshold,
+                    time,
+                }
+            })
+            .collect();
+
+        if self.settings.log_results {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            let device = TestRecord::settings_text(&self.tab().applied_device, &self.settings);
+            let records: Vec<TestRecord> = curves
+                .iter()
+                .zip(&cases)
+                .map(|(curve, case)| TestRecord {
+                    time: now,
+                    serial: self.board_serial.clone(),
+                    test_point: test_point.clone(),
+                    channel: curve.channel,
+                    settings: device.clone(),
+                    score: case.score,
+                    pass: case.pass,
+                    curve: (*curve).clone(),
+                })
+                .collect();
+            let recorded = ResultsDb::open().and_then(|mut db| db.record(&records, &self.settings));
+            if let Err(e) = recorded {
+                self.notifications.push(Level::Warning, e);
             }
+        }
+        let metrics = curves.iter().zip(&cases).map(|(curve, case)| MetricsRow {
+            label: test_point.clone(),
+            curve: (*curve).clone(),
+            score: Some(case.score),
+            pass: Some(case.pass),
+        });
+        self.test_metrics.extend(metrics);
+        self.test_results.extend(cases);
+    }
 
-            ui.separator();
+    /// Écrire les verdicts conservés en JUnit XML et leurs mesures en CSV
+    /// dans le dossier d'export, puis commencer une nouvelle série
+    fn save_test_results(&mut self) {
+        let settings = &self.settings;
+        let threshold = settings.analysis.pass_threshold;
+        let result = self
+            .export_path("results_metrics", "csv")
+            .and_then(|path| {
+                let data = self.displayed_data();
+                let channels = self.displayed_channels();
+                let header = report::header(self.tab(), &data, &channels, settings);
+                let (rows, frequency) = (&self.test_metrics, self.test_frequency());
+                save_metrics_as_csv(rows, "test_point", settings, frequency, &header, &path)
+            })
+            .and_then(|_| self.export_path("results", "xml"))
+            .and_then(|path| {
+                save_junit(env!("CARGO_PKG_NAME"), &self.test_results, threshold, &path)
+                    .map(|_| path)
+            });
+        if result.is_ok() {
+            self.test_results.clear();
+            self.test_metrics.clear();
+        }
+        self.export_done(result);
+    }
+
+    /// Passer à l'entrée suivante de la bibliothèque (en boucle)
+    fn next_test_point(&mut self) {
+        let count = self.library.entries.len();
+        if count == 0 {
+            return;
+        }
+        self.library_selection = Some(self.library_selection.map_or(0, |i| (i + 1) % count));
+        self.test_started = Instant::now();
+    }
+
+    /// Courbe de comparaison du score : l'entrée de bibliothèque sélectionnée,
+    /// sinon la référence du canal
+    fn match_target(&self, channel: u8) -> Option<&CurveData> {
+        self.selected_library_entry()
+            .map(|entry| &entry.curve)
+            .or_else(|| self.tab().reference.as_ref().and_then(|r| r.channel(channel)))
+    }
 
-            if let Ok(err) = self.error_message.lock() {
-                if let Some(msg) = &*err {
-                    ui.colored_label(egui::Color32::from_rgb(0, 150, 0), msg);
+    /// Source de données, relecture, pause et référence
+    fn draw_acquisition_panel(&mut self, ui: &mut egui::Ui) {
+        if let Some(name) = self.tab().snapshot_name.clone() {
+            ui.label(tr_args("snapshot-info", &[("name", name.into())]));
+        } else {
+            ui.horizontal(|ui| {
+                ui.label(tr("source"));
+                let mut use_file_mode = self.tab().use_file_mode;
+                ui.radio_value(&mut use_file_mode, false, tr("source-usb"));
+                ui.radio_value(&mut use_file_mode, true, tr("source-file"));
+                if use_file_mode != self.tab().use_file_mode {
+                    self.switch_source(use_file_mode);
                 }
-            }
+            });
 
-            ui.separator();
+            ui.horizontal(|ui| {
+                if self.tab().use_file_mode {
+                    let path = self.tab().file_path.clone();
+                    ui.label(tr_args("mode-file", &[("path", path.as_str().into())]));
+                    if ui.button(tr("reload-file")).clicked() {
+                        self.start_file_reader(self.tab().file_path.clone());
+                    }
+                    ui.checkbox(&mut self.settings.auto_reload, tr("auto-reload"));
+                } else {
+                    ui.label(tr("mode-usb"));
+                    if self.tab().hid_backend.is_none()
+                        && ui.button(tr("reconnect")).clicked()
+                    {
+                        self.start_usb_reader();
+                    }
+                }
+                if ui.button(tr("open-capture")).clicked() {
+                    self.open_capture_dialog();
+                }
+            });
 
-            if self.dual_mode {
-                self.draw_dual_overlay(ui, 600.0);
-            } else {
-                self.draw_single_channel(ui, 1, 600.0);
+            if self.tab().use_file_mode {
+                self.draw_transport(ui);
+            }
+        }
+
+        ui.horizontal(|ui| {
+            let label = if self.tab().paused { tr("resume") } else { tr("pause") };
+            if ui.button(label).clicked() {
+                self.set_paused(!self.tab().paused);
+            }
+            if ui
+                .checkbox(&mut self.settings.record_while_paused, tr("record-while-paused"))
+                .changed()
+            {
+                self.update_discard();
+            }
+            if self.tab().paused {
+                ui.colored_label(egui::Color32::from_rgb(200, 120, 0), tr("display-frozen"));
             }
         });
 
-        ctx.request_repaint();
+        ui.collapsing(tr("notes-header"), |ui| {
+            ui.add(
+                egui::TextEdit::multiline(&mut self.tab_mut().notes)
+                    .hint_text(tr("notes-hint"))
+                    .desired_rows(3)
+                    .desired_width(f32::INFINITY),
+            )
+            .accessible_name(egui::WidgetType::TextEdit, tr("notes-header"));
+        });
+
+        ui.horizontal(|ui| {
+            if ui.button(tr("hold-reference")).clicked() {
+                self.tab_mut().reference = Some(self.displayed_data());
+            }
+            if self.tab().reference.is_some() {
+                ui.colored_label(REFERENCE_COLOR, "━");
+                ui.label(tr("reference-active"));
+                if ui.button(tr("clear-reference")).clicked() {
+                    self.tab_mut().reference = None;
+                }
+            }
+        });
     }
 
-    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
-        *self.running.lock().unwrap() = false;
+    /// Mode d'affichage, style des courbes et export
+    fn draw_display_panel(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("display-mode"));
+            for (mode, key) in DISPLAY_MODES {
+                ui.radio_value(&mut self.settings.display_mode, mode, tr(key));
+            }
+        });
+
+        ui.collapsing(tr("style-header"), |ui| {
+            self.draw_style_settings(ui);
+        });
+
+        ui.horizontal(|ui| {
+            if ui.button(tr("save-png")).clicked() {
+                self.export_image(ImageFormat::Png);
+            }
+            if ui.button(tr("save-svg")).clicked() {
+                self.export_image(ImageFormat::Svg);
+            }
+            if ui.button(tr("save-csv")).clicked() {
+                self.export_csv();
+            }
+            if ui.button(tr("copy-data")).on_hover_text(tr("copy-data-hint")).clicked() {
+                self.copy_data(ui.ctx());
+            }
+            if ui.button(tr("save-json")).clicked() {
+                self.export_json();
+            }
+            if ui.button(tr("save-numpy")).on_hover_text(tr("save-numpy-hint")).clicked() {
+                self.export_numpy();
+            }
+            if ui.button(tr("save-mat")).on_hover_text(tr("save-mat-hint")).clicked() {
+                self.export_mat();
+            }
+            if ui.button(tr("save-html")).clicked() {
+                self.export_html();
+            }
+            ui.add_enabled_ui(self.comparison_active(), |ui| {
+                ui.menu_button(tr("comparison-report"), |ui| {
+                    for format in ReportFormat::ALL {
+                        if ui.button(format.extension().to_uppercase()).clicked() {
+                            ui.close_menu();
+                            self.export_comparison(format);
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(tr("comparison-report-hint"))
+                .on_disabled_hover_text(tr("comparison-none"));
+            });
+            let recording = self.recorder.as_ref().map(|r| r.elapsed().as_secs());
+            if let Some(secs) = recording {
+                let label = tr_args("video-stop", &[("seconds", secs.into())]);
+                if ui.button(label).clicked() {
+                    self.stop_video();
+                }
+            } else if ui.button(tr("video-record")).on_hover_text(tr("video-hint")).clicked() {
+                self.start_video();
+            }
+            if ui.button(tr("export-options")).clicked() {
+                self.export_options_open = true;
+            }
+            let label = ui.label(tr("export-dir"));
+            ui.text_edit_singleline(&mut self.settings.export_dir)
+                .labelled_by(label.id);
+            if ui.button("📁").on_hover_text(tr("export-dir-choose")).clicked() {
+                if let Some(dir) = rfd::FileDialog::new()
+                    .set_directory(&self.settings.export_dir)
+                    .pick_folder()
+                {
+                    self.settings.export_dir = dir.display().to_string();
+                }
+            }
+        });
+        if let Some(path) = &self.last_export {
+            ui.label(tr_args("export-last", &[("path", path.clone().into())]));
+        }
+
+        ui.collapsing(tr("templates-header"), |ui| {
+            let settings = &mut self.settings;
+            let html = &mut settings.html_template;
+            draw_template_choice(ui, "template-html", &["html", "htm"], html);
+            draw_template_choice(ui, "template-pdf", &["txt"], &mut settings.pdf_template);
+            ui.label(tr_args("template-fields", &[("fields", FIELDS.join(", ").into())]))
+                .on_hover_text(tr("template-hint"));
+        });
+
+        ui.collapsing(tr("annotations-header"), |ui| {
+            self.draw_annotation_settings(ui);
+        });
+    }
+
+    /// Saisie, placement et liste des annotations de l'onglet actif
+    fn draw_annotation_settings(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut self.annotation_text)
+                .accessible_name(egui::WidgetType::TextEdit, tr("annotation-text"));
+            ui.checkbox(&mut self.annotation_arrow, tr("annotation-arrow"));
+        });
+        ui.horizontal(|ui| {
+            let can_place = !self.annotation_text.trim().is_empty();
+            if ui
+                .add_enabled(can_place, egui::Button::new(tr("annotation-place")))
+                .clicked()
+            {
+                self.placing_annotation = true;
+                self.pending_target = None;
+            }
+            if self.placing_annotation {
+                let hint = if self.annotation_arrow && self.pending_target.is_none() {
+                    tr("annotation-click-target")
+                } else {
+                    tr("annotation-click-text")
+                };
+                ui.label(hint);
+                if ui.button(tr("cancel")).clicked() {
+                    self.placing_annotation = false;
+                    self.pending_target = None;
+                }
+            }
+        });
+
+        let mut removed = None;
+        for (i, annotation) in self.tab().annotations.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui
+                    .small_button("🗑")
+                    .accessible_name(egui::WidgetType::Button, tr("delete"))
+                    .clicked()
+                {
+                    removed = Some(i);
+                }
+                let arrow = if annotation.target.is_some() { "↗ " } else { "" };
+                ui.label(format!("{}{}", arrow, annotation.text));
+            });
+        }
+        if let Some(i) = removed {
+            self.tab_mut().annotations.remove(i);
+        }
+    }
+
+    /// Clic sur le tracé (coordonnées normalisées) pendant le placement d'une annotation
+    fn handle_plot_click(&mut self, pos: [f32; 2]) {
+        if !self.placing_annotation {
+            return;
+        }
+        if self.annotation_arrow && self.pending_target.is_none() {
+            self.pending_target = Some(pos);
+            return;
+        }
+        let annotation = Annotation {
+            text: self.annotation_text.trim().to_string(),
+            position: pos,
+            target: self.pending_target.take(),
+        };
+        self.tab_mut().annotations.push(annotation);
+        self.annotation_text.clear();
+        self.placing_annotation = false;
+    }
+
+    /// Historique des courbes reçues
+    fn draw_history_panel(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .add(
+                egui::Slider::new(&mut self.settings.history_size, 1..=100)
+                    .text(tr("history-size")),
+            )
+            .changed()
+        {
+            for tab in &self.tabs {
+                tab.history.lock().unwrap().capacity = self.settings.history_size;
+            }
+        }
+        ui.horizontal(|ui| {
+            ui.label(tr("history-export"));
+            if ui.button("PNG").on_hover_text(tr("history-export-png")).clicked() {
+                self.export_history(HistoryExport::Png);
+            }
+            if ui.button("CSV").on_hover_text(tr("history-export-csv")).clicked() {
+                self.export_history(HistoryExport::Csv);
+            }
+            if ui
+                .button(tr("history-export-single"))
+                .on_hover_text(tr("history-export-single-hint"))
+                .clicked()
+            {
+                self.export_history(HistoryExport::SingleCsv);
+            }
+            if ui.button("GIF").on_hover_text(tr("history-export-gif")).clicked() {
+                self.export_history(HistoryExport::Gif);
+            }
+            ui.add(
+                egui::DragValue::new(&mut self.settings.animation_delay_ms)
+                    .clamp_range(50..=5000)
+                    .speed(10)
+                    .suffix(" ms"),
+            )
+            .on_hover_text(tr("history-gif-delay"));
+            if ui.button("sigrok").on_hover_text(tr("history-export-sigrok")).clicked() {
+                self.export_history(HistoryExport::Sigrok);
+            }
+            if ui
+                .button(tr("history-export-metrics"))
+                .on_hover_text(tr("history-export-metrics-hint"))
+                .clicked()
+            {
+                self.export_history(HistoryExport::Metrics);
+            }
+        });
+        if let Some(curve) = self.draw_history(ui) {
+            self.inspect_curve(curve);
+        }
+    }
+
+    /// Réglages du boîtier, disponibles uniquement avec un boîtier connecté
+    fn draw_commands_panel(&mut self, ui: &mut egui::Ui) {
+        let Some(backend) = self.tab().hid_backend.clone() else {
+            ui.label(tr("commands-unavailable"));
+            return;
+        };
+
+        for setting in &DEVICE_SETTINGS {
+            self.draw_command_row(ui, &backend, setting);
+        }
+        ui.checkbox(
+            &mut self.settings.reapply_device_settings,
+            tr("reapply-device-settings"),
+        );
+    }
+
+    /// Onglets de la disposition, autour du tracé
+    fn draw_dock(&mut self, ctx: &egui::Context) {
+        // Copie de la disposition : les onglets ont besoin de `self` en entier
+        let mut dock = self.settings.layout.dock.clone();
+        let menu = &mut dock.translations.tab_context_menu;
+        menu.close_button = tr("dock-close");
+        menu.eject_button = tr("dock-detach");
+        DockArea::new(&mut dock)
+            .style(DockStyle::from_egui(ctx.style().as_ref()))
+            .show(ctx, &mut PanelViewer { app: self });
+        self.settings.layout.dock = dock;
+    }
+
+    /// Menu Affichage : onglets ouverts ou fermés
+    fn draw_view_menu(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.settings.teaching, tr("teaching-mode"));
+        ui.separator();
+        for panel in Panel::ALL {
+            let mut open = self.settings.layout.is_open(panel);
+            if ui.checkbox(&mut open, tr(panel.key())).changed() {
+                self.settings.layout.set_open(panel, open);
+            }
+        }
+        ui.separator();
+        if ui.button(tr("layout-reset")).clicked() {
+            self.settings.layout = PanelLayout::default();
+            ui.close_menu();
+        }
+        if ui.button(tr("fullscreen")).clicked() {
+            ui.close_menu();
+            self.toggle_fullscreen(ui.ctx());
+        }
+    }
+
+    /// Entrer ou sortir du mode présentation
+    fn toggle_fullscreen(&mut self, ctx: &egui::Context) {
+        self.fullscreen = !self.fullscreen;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(self.fullscreen));
+        if self.fullscreen {
+            self.notifications.push(Level::Info, tr("fullscreen-exit"));
+        }
+    }
+
+    /// Titre de l'application et choix de la langue
+    fn draw_title_row(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading(tr("app-title"));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                egui::ComboBox::from_id_source("language")
+                    .selected_text(self.settings.language.native_name())
+                    .show_ui(ui, |ui| {
+                        for language in Language::ALL {
+                            ui.selectable_value(
+                                &mut self.settings.language,
+                                language,
+                                language.native_name(),
+                            );
+                        }
+                    })
+                    .response
+                    .accessible_name(egui::WidgetType::ComboBox, tr("language"));
+                ui.label(tr("language"));
+            });
+        });
+        i18n::set_language(self.settings.language);
+    }
+
+    /// Barre d'onglets : sélection, fermeture et création
+    fn draw_tab_bar(&mut self, ui: &mut egui::Ui) {
+        let mut close = None;
+        ui.horizontal(|ui| {
+            for (i, tab) in self.tabs.iter().enumerate() {
+                if ui.selectable_label(i == self.active, tab.title()).clicked() {
+                    self.active = i;
+                }
+                if self.tabs.len() > 1
+                    && ui
+                        .small_button("✖")
+                        .on_hover_text(tr("tab-close"))
+                        .accessible_name(egui::WidgetType::Button, tr("tab-close"))
+                        .clicked()
+                {
+                    close = Some(i);
+                }
+                ui.separator();
+            }
+
+            let add = ui.menu_button("➕", |ui| {
+                if ui.button(tr("tab-new-usb")).clicked() {
+                    ui.close_menu();
+                    self.open_tab(Session::new(String::new(), &self.settings));
+                    self.start_usb_reader();
+                }
+                if ui.button(tr("tab-new-file")).clicked() {
+                    ui.close_menu();
+                    if let Some(path) = self.pick_capture_file() {
+                        self.open_tab(Session::new(path.clone(), &self.settings));
+                        self.start_file_reader(path);
+                    }
+                }
+                if ui.button(tr("tab-snapshot")).clicked() {
+                    ui.close_menu();
+                    let name = tr_args(
+                        "tab-snapshot-name",
+                        &[("source", self.tab().title().into())],
+                    );
+                    let data = self.displayed_data();
+                    self.open_tab(Session::snapshot(name, data, &self.settings));
+                }
+            });
+            add.response.accessible_name(egui::WidgetType::Button, tr("tab-add"));
+        });
+        if let Some(i) = close {
+            self.close_tab(i);
+        }
+    }
+
+    /// Barre d'état : connexion, débit, nombre de courbes et dernière erreur
+    fn draw_status_bar(&self, ui: &mut egui::Ui) {
+        let status = self.tab().status.lock().unwrap();
+        ui.horizontal(|ui| {
+            let (key, color) = match status.connection {
+                Connection::Usb => ("connection-usb", egui::Color32::from_rgb(0, 150, 0)),
+                Connection::File => ("connection-file", egui::Color32::from_rgb(0, 120, 200)),
+                Connection::Disconnected => ("connection-none", egui::Color32::GRAY),
+            };
+            ui.colored_label(color, "●");
+            ui.label(tr(key));
+            ui.separator();
+            let rate = status.curves_per_second();
+            ui.label(tr_args("curves-per-second", &[("rate", format!("{:.1}", rate).into())]));
+            // Le débit retombe à zéro sans nouvelle courbe : suivre sa décroissance
+            if rate > 0.0 {
+                ui.ctx().request_repaint_after(Duration::from_millis(500));
+            }
+            ui.separator();
+            ui.label(tr_args(
+                "curves-total",
+                &[("count", status.total_curves.into())],
+            ));
+            if let Some(progress) = status.assembling {
+                ui.separator();
+                let text = tr_args(
+                    "curve-progress",
+                    &[
+                        ("channel", progress.channel.into()),
+                        ("received", progress.reports.into()),
+                        ("total", REPORTS_PER_CURVE.into()),
+                    ],
+                );
+                ui.add(
+                    egui::ProgressBar::new(progress.reports as f32 / REPORTS_PER_CURVE as f32)
+                        .desired_width(140.0)
+                        .text(text),
+                );
+            }
+            if let Some(error) = &status.last_error {
+                ui.separator();
+                ui.colored_label(egui::Color32::from_rgb(200, 0, 0), error);
+            }
+        });
+    }
+
+    /// Contrôles de relecture : lecture/pause, pas à pas, boucle et vitesse
+    fn draw_transport(&self, ui: &mut egui::Ui) {
+        let mut playback = self.tab().playback.lock().unwrap();
+        ui.horizontal(|ui| {
+            if ui
+                .button("⏮")
+                .on_hover_text(tr("step-backward"))
+                .accessible_name(egui::WidgetType::Button, tr("step-backward"))
+                .clicked()
+            {
+                playback.playing = false;
+                playback.step = -1;
+            }
+            let (icon, hint) = if playback.playing {
+                ("⏸", "playback-pause")
+            } else {
+                ("▶", "playback-play")
+            };
+            if ui
+                .button(icon)
+                .on_hover_text(tr(hint))
+                .accessible_name(egui::WidgetType::Button, tr(hint))
+                .clicked()
+            {
+                playback.playing = !playback.playing;
+            }
+            if ui
+                .button("⏭")
+                .on_hover_text(tr("step-forward"))
+                .accessible_name(egui::WidgetType::Button, tr("step-forward"))
+                .clicked()
+            {
+                playback.playing = false;
+                playback.step = 1;
+            }
+            ui.checkbox(&mut playback.looping, tr("playback-loop"));
+            ui.add(
+                egui::Slider::new(&mut playback.speed, Playback::SPEED_RANGE)
+                    .logarithmic(true)
+                    .suffix("×")
+                    .text(tr("playback-speed")),
+            );
+            if playback.total > 0 {
+                ui.label(tr_args(
+                    "playback-position",
+                    &[
+                        ("position", (playback.position + 1).into()),
+                        ("total", playback.total.into()),
+                    ],
+                ));
+            }
+        });
+    }
+
+    /// Basculer entre acquisition USB et relecture de fichier
+    fn switch_source(&mut self, use_file_mode: bool) {
+        if !use_file_mode {
+            self.start_usb_reader();
+        } else if Path::new(&self.tab().file_path).exists() {
+            self.start_file_reader(self.tab().file_path.clone());
+        } else {
+            self.open_capture_dialog();
+        }
+    }
+
+    /// Boîte de dialogue après l'arrêt d'un thread de lecture : erreur et
+    /// actions proposées (reconnexion, passage en mode fichier)
+    fn draw_reader_failure(&mut self, ctx: &egui::Context) {
+        let Some((tab, error)) = self.reader_failure.clone() else {
+            return;
+        };
+        if tab >= self.tabs.len() {
+            self.reader_failure = None;
+            return;
+        }
+
+        let mut action = None;
+        egui::Window::new(tr("reader-failure-title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(tr("reader-failure-text"));
+                ui.colored_label(ui.visuals().error_fg_color, &error);
+                ui.add_space(4.0);
+                ui.label(tr("reader-failure-hint"));
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr("reconnect")).clicked() {
+                        action = Some(Recovery::Reconnect);
+                    }
+                    if ui.button(tr("open-capture")).clicked() {
+                        action = Some(Recovery::OpenCapture);
+                    }
+                    if ui.button(tr("close")).clicked() {
+                        action = Some(Recovery::Dismiss);
+                    }
+                });
+            });
+
+        let Some(action) = action else {
+            return;
+        };
+        self.reader_failure = None;
+        self.active = tab;
+        match action {
+            Recovery::Reconnect => self.start_usb_reader(),
+            Recovery::OpenCapture => self.open_capture_dialog(),
+            Recovery::Dismiss => {}
+        }
+    }
+
+    /// Choisir un fichier de capture via le sélecteur natif
+    fn open_capture_dialog(&mut self) {
+        if let Some(path) = self.pick_capture_file() {
+            self.start_file_reader(path);
+        }
+    }
+
+    /// Sélecteur natif de fichier de capture, ouvert dans le dossier courant
+    fn pick_capture_file(&self) -> Option<String> {
+        let mut dialog = rfd::FileDialog::new()
+            .set_title(tr("open-capture-title"))
+            .add_filter(tr("capture-filter"), &["txt", "hex", "log"]);
+        if let Some(dir) = Path::new(&self.tab().file_path).parent().filter(|d| d.is_dir()) {
+            dialog = dialog.set_directory(dir);
+        }
+        dialog
+            .pick_file()
+            .map(|path| path.to_string_lossy().into_owned())
+    }
+
+    /// Charger une capture dans l'emplacement A (`false`) ou B (`true`) de la comparaison
+    fn load_comparison_file(&mut self, slot_b: bool) {
+        let Some(path) = self.pick_capture_file() else {
+            return;
+        };
+        match CaptureFile::load(&path) {
+            Ok(capture) => {
+                if slot_b {
+                    self.comparison.b = Some(capture);
+                } else {
+                    self.comparison.a = Some(capture);
+                }
+                self.comparison.position = 0;
+            }
+            Err(e) => self
+                .tab()
+                .status
+                .lock()
+                .unwrap()
+                .set_error(tr_args("status-error", &[("error", e.into())])),
+        }
+    }
+
+    /// Fenêtre des options d'export d'images : résolution et mesures inscrites
+    fn draw_export_options(&mut self, ctx: &egui::Context) {
+        let mut open = self.export_options_open;
+        let (settings, style_name) = (&mut self.settings, &mut self.export_style_name);
+        egui::Window::new(tr("export-options"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("export-size"));
+                    for (size, label) in Settings::EXPORT_SIZES {
+                        ui.radio_value(&mut settings.export_size, size, label);
+                    }
+                    ui.add(
+                        egui::DragValue::new(&mut settings.export_size)
+                            .clamp_range(100..=Settings::EXPORT_MAX_SIDE)
+                            .suffix(" px"),
+                    )
+                    .accessible_name(egui::WidgetType::DragValue, tr("export-size"));
+                });
+                ui.add(
+                    egui::Slider::new(&mut settings.export_scale, Settings::EXPORT_SCALE_RANGE)
+                        .suffix("×")
+                        .text(tr("export-scale")),
+                );
+                let side = (800.0 * settings.export_factor()).round() as u32;
+                ui.label(tr_args(
+                    "export-resolution",
+                    &[("side", side.into()), ("width", (2 * side).into())],
+                ));
+                ui.checkbox(&mut settings.export_measurements, tr("export-measurements"))
+                    .on_hover_text(tr("export-measurements-hint"));
+                ui.checkbox(&mut settings.export_transparent, tr("export-transparent"))
+                    .on_hover_text(tr("export-transparent-hint"));
+                ui.checkbox(&mut settings.export_labels, tr("export-labels"))
+                    .on_hover_text(tr("export-labels-hint"));
+                ui.horizontal(|ui| {
+                    let mut custom = settings.export_colors.is_some();
+                    ui.checkbox(&mut custom, tr("export-colors"));
+                    if custom != settings.export_colors.is_some() {
+                        settings.export_colors = custom.then_some(Settings::EXPORT_COLORS);
+                    }
+                    if let Some(colors) = &mut settings.export_colors {
+                        for (channel, color) in colors.iter_mut().enumerate() {
+                            ui.label(format!("CH{}", channel));
+                            ui.color_edit_button_srgb(color);
+                        }
+                    }
+                });
+
+                ui.separator();
+                ui.label(tr("export-styles")).on_hover_text(tr("export-styles-hint"));
+                ui.horizontal(|ui| {
+                    let mut chosen = None;
+                    egui::ComboBox::from_id_source("export-style")
+                        .selected_text(style_name.as_str())
+                        .show_ui(ui, |ui| {
+                            for style in &settings.export_styles {
+                                let selected = style.name == *style_name;
+                                if ui.selectable_label(selected, &style.name).clicked() {
+                                    chosen = Some(style.name.clone());
+                                }
+                            }
+                        });
+                    if let Some(name) = chosen {
+                        let _ = settings.use_export_style(&name);
+                        *style_name = name;
+                    }
+                    ui.text_edit_singleline(style_name)
+                        .accessible_name(egui::WidgetType::TextEdit, tr("export-style-name"));
+                    let name = style_name.trim();
+                    let save = egui::Button::new(tr("export-style-save"));
+                    if ui.add_enabled(!name.is_empty(), save).clicked() {
+                        settings.save_export_style(name);
+                    }
+                    let exists = settings.export_styles.iter().any(|s| s.name == name);
+                    let delete = egui::Button::new(tr("export-style-delete"));
+                    if ui.add_enabled(exists, delete).clicked() {
+                        settings.export_styles.retain(|s| s.name != name);
+                    }
+                });
+            });
+        self.export_options_open = open;
+    }
+
+    /// Fenêtre des résultats de test enregistrés, filtrés par numéro de série
+    /// ou point de test
+    fn draw_results(&mut self, ctx: &egui::Context) {
+        let mut open = self.results.open;
+        let (results, settings) = (&mut self.results, &mut self.settings);
+        egui::Window::new(tr("results-title"))
+            .open(&mut open)
+            .default_size([600.0, 400.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let label = ui.label(tr("results-filter"));
+                    let filter = ui.text_edit_singleline(&mut results.filter).labelled_by(label.id);
+                    if filter.changed() || ui.button(tr("results-refresh")).clicked() {
+                        results.refresh();
+                    }
+                    ui.checkbox(&mut settings.log_results, tr("results-log"))
+                        .on_hover_text(tr("results-log-hint"));
+                });
+                if let Some(error) = &results.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                ui.label(tr_args("results-count", &[("count", results.rows.len().into())]));
+                ui.separator();
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("results_grid").striped(true).show(ui, |ui| {
+                        for key in RESULT_COLUMNS {
+                            ui.strong(tr(key));
+                        }
+                        ui.end_row();
+                        for row in &results.rows {
+                            ui.label(format_timestamp(row.time));
+                            ui.label(&row.serial);
+                            ui.label(&row.test_point);
+                            ui.label(format!("CH{}", row.channel));
+                            ui.label(format!("{:.1} %", row.score));
+                            let (key, color) = if row.pass {
+                                ("operator-pass", egui::Color32::from_rgb(0, 150, 0))
+                            } else {
+                                ("operator-fail", egui::Color32::from_rgb(200, 0, 0))
+                            };
+                            ui.colored_label(color, tr(key));
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+        self.results.open = open;
+    }
+
+    /// Fenêtre de comparaison A/B : deux captures, une position de lecture commune
+    fn draw_comparison(&mut self, ctx: &egui::Context) {
+        self.comparison.tick();
+        if self.comparison.playing {
+            ctx.request_repaint_after(STEP_INTERVAL);
+        }
+
+        let mut open = self.comparison.open;
+        let mut load_slot = None;
+        egui::Window::new(tr("compare-title"))
+            .open(&mut open)
+            .default_size(egui::vec2(800.0, 500.0))
+            .show(ctx, |ui| {
+                for (slot_b, name) in [(false, "A"), (true, "B")] {
+                    let capture = if slot_b {
+                        &self.comparison.b
+                    } else {
+                        &self.comparison.a
+                    };
+                    ui.horizontal(|ui| {
+                        ui.strong(name);
+                        if ui.button(tr("open-capture")).clicked() {
+                            load_slot = Some(slot_b);
+                        }
+                        match capture {
+                            Some(c) => ui.label(tr_args(
+                                "compare-file",
+                                &[
+                                    ("path", c.path.as_str().into()),
+                                    ("count", c.curves.len().into()),
+                                ],
+                            )),
+                            None => ui.label(tr("compare-no-file")),
+                        };
+                    });
+                }
+
+                let len = self.comparison.len();
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("⏮")
+                        .on_hover_text(tr("step-backward"))
+                        .accessible_name(egui::WidgetType::Button, tr("step-backward"))
+                        .clicked()
+                    {
+                        self.comparison.playing = false;
+                        self.comparison.step(-1);
+                    }
+                    let (icon, hint) = if self.comparison.playing {
+                        ("⏸", "playback-pause")
+                    } else {
+                        ("▶", "playback-play")
+                    };
+                    if ui
+                        .button(icon)
+                        .on_hover_text(tr(hint))
+                        .accessible_name(egui::WidgetType::Button, tr(hint))
+                        .clicked()
+                    {
+                        self.comparison.playing = !self.comparison.playing;
+                    }
+                    if ui
+                        .button("⏭")
+                        .on_hover_text(tr("step-forward"))
+                        .accessible_name(egui::WidgetType::Button, tr("step-forward"))
+                        .clicked()
+                    {
+                        self.comparison.playing = false;
+                        self.comparison.step(1);
+                    }
+                    if len > 0 {
+                        ui.add(
+                            egui::Slider::new(&mut self.comparison.position, 0..=len - 1)
+                                .custom_formatter(|v, _| format!("{}", v as usize + 1)),
+                        )
+                        .accessible_name(egui::WidgetType::Slider, tr("compare-position"));
+                    }
+                    ui.checkbox(&mut self.comparison.overlay, tr("compare-overlay"));
+                });
+                ui.separator();
+
+                let position = self.comparison.position;
+                let a = self.comparison.a.as_ref().and_then(|c| c.curve_at(position));
+                let b = self.comparison.b.as_ref().and_then(|c| c.curve_at(position));
+                let available = ui.available_size();
+                if self.comparison.overlay {
+                    self.draw_comparison_plot(ui, self.plot_size(available), &[a, b]);
+                } else {
+                    let half = egui::vec2(
+                        (available.x - ui.spacing().item_spacing.x) / 2.0,
+                        available.y,
+                    );
+                    let size = self.plot_size(half);
+                    ui.horizontal(|ui| {
+                        self.draw_comparison_plot(ui, size, &[a, None]);
+                        self.draw_comparison_plot(ui, size, &[None, b]);
+                    });
+                }
+            });
+        self.comparison.open = open;
+
+        if let Some(slot_b) = load_slot {
+            self.load_comparison_file(slot_b);
+        }
+    }
+
+    /// Tracé de comparaison : A avec la couleur CH0, B avec la couleur CH1
+    fn draw_comparison_plot(
+        &self,
+        ui: &mut egui::Ui,
+        size: egui::Vec2,
+        curves: &[Option<&CurveData>; 2],
+    ) {
+        let (response, mut painter) = ui.allocate_painter(size, egui::Sense::hover());
+        let rect = response.rect;
+
+        let dark = self.plot_is_dark(ui.visuals());
+        let (background, grid_color, axis_color) = self.plot_colors(dark);
+        painter.rect_filled(rect, 0.0, background);
+
+        let frame = PlotFrame::new(rect);
+        let style = self.plot_style(grid_color, axis_color);
+        plot_renderer::draw_grid(&mut painter, &self.settings, frame, &style);
+
+        for (slot, (curve, name)) in curves.iter().zip(["A", "B"]).enumerate() {
+            let Some(curve) = curve else {
+                continue;
+            };
+            let color = self.channel_color(slot as u8, dark);
+            let stroke = egui::Stroke::new(self.settings.plot.trace_width(), color);
+            let axes = &self.settings.axes;
+            plot_renderer::draw_curve(&mut painter, curve, axes, frame, stroke, false);
+            painter.text(
+                egui::pos2(rect.left() + 30.0, rect.top() + 15.0 + 20.0 * slot as f32),
+                egui::Align2::LEFT_TOP,
+                format!("{} (CH{})", name, curve.channel),
+                egui::FontId::proportional(16.0),
+                color,
+            );
+        }
+        let named: Vec<(String, &CurveData)> = curves
+            .iter()
+            .zip(["A", "B"])
+            .filter_map(|(curve, name)| curve.map(|c| (name.to_string(), c)))
+            .collect();
+        response.accessible_name(egui::WidgetType::Other, plot_summary(&named, &self.settings));
+    }
+
+    /// Barre de menus (ouverture de capture, fichiers récents)
+    fn draw_menu_bar(&mut self, ui: &mut egui::Ui) {
+        egui::menu::bar(ui, |ui| {
+            ui.menu_button(tr("menu-file"), |ui| {
+                if ui.button(tr("open-capture")).clicked() {
+                    ui.close_menu();
+                    self.open_capture_dialog();
+                }
+                if ui.button(tr("compare-open")).clicked() {
+                    ui.close_menu();
+                    self.comparison.open = true;
+                }
+                if ui.button(tr("results-open")).clicked() {
+                    ui.close_menu();
+                    self.results.open = true;
+                    self.results.refresh();
+                }
+                ui.separator();
+                if ui.button(tr("session-open")).clicked() {
+                    ui.close_menu();
+                    self.open_session_file();
+                }
+                if ui.button(tr("session-save")).clicked() {
+                    ui.close_menu();
+                    self.save_session_file();
+                }
+                ui.separator();
+                if ui.button(tr("print")).clicked() {
+                    ui.close_menu();
+                    self.print_view();
+                }
+                if ui.button(tr("report-pdf")).clicked() {
+                    ui.close_menu();
+                    self.export_report();
+                }
+                ui.add_enabled_ui(!self.settings.recent_files.is_empty(), |ui| {
+                    ui.menu_button(tr("menu-recent"), |ui| {
+                        let mut chosen = None;
+                        for path in &self.settings.recent_files {
+                            if ui.button(path).clicked() {
+                                chosen = Some(path.clone());
+                            }
+                        }
+                        ui.separator();
+                        if ui.button(tr("menu-recent-clear")).clicked() {
+                            self.settings.recent_files.clear();
+                            ui.close_menu();
+                        }
+                        if let Some(path) = chosen {
+                            self.start_file_reader(path);
+                            ui.close_menu();
+                        }
+                    });
+                });
+            });
+            ui.menu_button(tr("menu-view"), |ui| {
+                self.draw_view_menu(ui);
+            });
+        });
+    }
+
+    /// Vignettes de l'historique ; renvoie la courbe cliquée
+    fn draw_history(&self, ui: &mut egui::Ui) -> Option<CurveData> {
+        const THUMBNAIL_SIZE: f32 = 64.0;
+
+        let history = self.tab().history.lock().unwrap();
+        let dark = self.plot_is_dark(ui.visuals());
+        let (background, _, axis_color) = self.plot_colors(dark);
+        let mut clicked = None;
+
+        for channel in [0u8, 1] {
+            let curves = history.channel(channel);
+            if curves.is_empty() {
+                continue;
+            }
+            ui.label(format!("CH{}", channel));
+            egui::ScrollArea::horizontal()
+                .id_source(("history", channel))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        // La plus récente à gauche
+                        for (age, curve) in curves.iter().rev().enumerate() {
+                            let (response, mut painter) = ui.allocate_painter(
+                                egui::vec2(THUMBNAIL_SIZE, THUMBNAIL_SIZE),
+                                egui::Sense::click(),
+                            );
+                            let rect = response.rect;
+                            painter.rect_filled(rect, 2.0, background);
+                            let stroke = if response.hovered() { 2.0 } else { 1.0 };
+                            painter.rect_stroke(rect, 2.0, egui::Stroke::new(stroke, axis_color));
+                            let frame = PlotFrame::new(rect);
+                            self.paint_trace(&mut painter, curve, channel, frame, dark);
+                            let name = tr_args(
+                                "history-thumbnail",
+                                &[("channel", channel.into()), ("age", age.into())],
+                            );
+                            if response
+                                .on_hover_text(tr_args("history-age", &[("age", age.into())]))
+                                .accessible_name(egui::WidgetType::ImageButton, name)
+                                .clicked()
+                            {
+                                clicked = Some(curve.clone());
+                            }
+                        }
+                    });
+                });
+        }
+        clicked
+    }
+
+    /// Appliquer l'échelle de l'interface une fois le curseur relâché (le
+    /// tracé changerait de taille sous la souris) ; un zoom au clavier
+    /// (Ctrl +/-) est repris dans les préférences
+    fn apply_ui_scale(&mut self, ctx: &egui::Context) {
+        let current = ctx.zoom_factor();
+        if current != self.applied_ui_scale {
+            self.applied_ui_scale = current;
+            self.settings.ui_scale = current;
+        } else if self.settings.ui_scale != current && !ctx.input(|i| i.pointer.any_down()) {
+            ctx.set_zoom_factor(self.settings.ui_scale);
+        }
+    }
+
+    /// Appliquer le thème de l'interface (système, clair ou sombre)
+    fn apply_theme(&self, ctx: &egui::Context, frame: &eframe::Frame) {
+        let dark = match self.settings.theme {
+            Theme::System => frame
+                .info()
+                .system_theme
+                .is_none_or(|theme| theme == eframe::Theme::Dark),
+            Theme::Light => false,
+            Theme::Dark => true,
+        };
+        if ctx.style().visuals.dark_mode != dark {
+            ctx.set_visuals(if dark {
+                egui::Visuals::dark()
+            } else {
+                egui::Visuals::light()
+            });
+        }
+    }
+
+    /// Le tracé est-il sur fond sombre ?
+    fn plot_is_dark(&self, visuals: &egui::Visuals) -> bool {
+        match self.settings.plot.background {
+            Background::White => false,
+            Background::Black => true,
+            Background::FollowTheme => visuals.dark_mode,
+        }
+    }
+
+    /// Couleurs (fond, grille, axes/texte) selon le fond du tracé
+    fn plot_colors(&self, dark: bool) -> (egui::Color32, egui::Color32, egui::Color32) {
+        if self.settings.plot.high_contrast {
+            let (background, foreground) = if dark {
+                (egui::Color32::BLACK, egui::Color32::WHITE)
+            } else {
+                (egui::Color32::WHITE, egui::Color32::BLACK)
+            };
+            return (background, egui::Color32::from_gray(128), foreground);
+        }
+        if dark {
+            (
+                egui::Color32::from_gray(20),
+                egui::Color32::from_gray(70),
+                egui::Color32::from_gray(230),
+            )
+        } else {
+            (
+                egui::Color32::WHITE,
+                egui::Color32::from_gray(200),
+                egui::Color32::BLACK,
+            )
+        }
+    }
+
+    /// Couleur d'un canal, éclaircie sur fond sombre pour rester lisible ;
+    /// blanc ou noir en haut contraste
+    fn channel_color(&self, channel: u8, dark: bool) -> egui::Color32 {
+        if self.settings.plot.high_contrast {
+            return if dark {
+                egui::Color32::WHITE
+            } else {
+                egui::Color32::BLACK
+            };
+        }
+        let [r, g, b] = self.settings.plot.channel_rgb(channel);
+        if dark {
+            let lighten = |c: u8| c + (255 - c) / 3;
+            egui::Color32::from_rgb(lighten(r), lighten(g), lighten(b))
+        } else {
+            egui::Color32::from_rgb(r, g, b)
+        }
+    }
+
+    fn channel_visible(&self, channel: u8) -> bool {
+        if channel == 0 {
+            self.settings.show_ch0
+        } else {
+            self.settings.show_ch1
+        }
+    }
+
+    fn toggle_channel(&mut self, channel: u8) {
+        let shown = if channel == 0 {
+            &mut self.settings.show_ch0
+        } else {
+            &mut self.settings.show_ch1
+        };
+        *shown = !*shown;
+    }
+
+    /// Légende du tracé : un clic sur un canal l'affiche ou le masque
+    fn draw_legend(
+        &mut self,
+        ctx: &egui::Context,
+        rect: egui::Rect,
+        channels: &[u8],
+        data: &DualCurveData,
+        dark: bool,
+    ) {
+        let entries: Vec<LegendEntry> = channels
+            .iter()
+            .map(|&channel| {
+                let metrics = data.channel(channel).map(|curve| {
+                    let metrics = curve.metrics();
+                    tr_args(
+                        "legend-metrics",
+                        &[
+                            ("voltage", format!("{:.2}", metrics.voltage_span).into()),
+                            ("current", format!("{:.2}", metrics.current_span).into()),
+                        ],
+                    )
+                });
+                LegendEntry {
+                    name: format!("CH{}", channel),
+                    color: self.channel_color(channel, dark),
+                    metrics: metrics.unwrap_or_default(),
+                    visible: self.channel_visible(channel),
+                }
+            })
+            .collect();
+
+        let id = egui::Id::new("legend").with(channels);
+        if let Some(i) = legend::show(ctx, id, rect.left_top() + egui::vec2(8.0, 8.0), &entries) {
+            self.toggle_channel(channels[i]);
+        }
+    }
+
+    fn draw_style_settings(&mut self, ui: &mut egui::Ui) {
+        let style = &mut self.settings.plot;
+        ui.checkbox(&mut style.high_contrast, tr("high-contrast"))
+            .on_hover_text(tr("high-contrast-hint"));
+        ui.horizontal(|ui| {
+            ui.label(tr("palette"));
+            ui.radio_value(&mut style.palette, Palette::Custom, tr("palette-custom"));
+            ui.radio_value(&mut style.palette, Palette::ColorBlind, tr("palette-colorblind"));
+        });
+        if style.palette == Palette::Custom {
+            ui.horizontal(|ui| {
+                let label = ui.label(tr("color-ch0"));
+                ui.color_edit_button_srgb(&mut style.ch0_color)
+                    .labelled_by(label.id);
+                let label = ui.label(tr("color-ch1"));
+                ui.color_edit_button_srgb(&mut style.ch1_color)
+                    .labelled_by(label.id);
+            });
+        }
+        ui.checkbox(&mut style.dashed_ch1, tr("dashed-ch1"));
+        ui.horizontal(|ui| {
+            let label = ui.label(tr("grid-divisions"));
+            ui.add(egui::Slider::new(&mut self.settings.grid.divisions, 1..=20))
+                .labelled_by(label.id);
+            ui.checkbox(&mut self.settings.grid.minor, tr("grid-minor"));
+        });
+        let calibration = &mut self.settings.calibration;
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut calibration.enabled, tr("physical-units"));
+            ui.add_enabled(
+                calibration.enabled,
+                egui::DragValue::new(&mut calibration.volts_full_scale)
+                    .clamp_range(0.1..=50.0)
+                    .speed(0.1)
+                    .suffix(" V"),
+            )
+            .accessible_name(egui::WidgetType::DragValue, tr("volts-full-scale"));
+            ui.add_enabled(
+                calibration.enabled,
+                egui::DragValue::new(&mut calibration.milliamps_full_scale)
+                    .clamp_range(0.01..=1000.0)
+                    .speed(0.1)
+                    .suffix(" mA"),
+            )
+            .accessible_name(egui::WidgetType::DragValue, tr("milliamps-full-scale"));
+        });
+        ui.horizontal(|ui| {
+            let label = ui.label(tr("line-width"));
+            ui.add(egui::Slider::new(&mut style.line_width, 0.5..=6.0))
+                .labelled_by(label.id);
+        });
+        ui.horizontal(|ui| {
+            let label = ui.label(tr("ghosts"));
+            ui.add(egui::Slider::new(&mut style.ghosts, 0..=20))
+                .labelled_by(label.id);
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("markers"));
+            ui.radio_value(&mut style.markers, Markers::None, tr("markers-none"));
+            ui.radio_value(&mut style.markers, Markers::Circles, tr("markers-circles"));
+            ui.radio_value(&mut style.markers, Markers::Crosses, tr("markers-crosses"));
+            ui.add_enabled(
+                style.markers != Markers::None,
+                egui::Checkbox::new(&mut style.connect_points, tr("connect-points")),
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.settings.axes.log_current, tr("log-current"));
+            ui.checkbox(&mut self.settings.axes.swap, tr("swap-axes"));
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("background"));
+            ui.radio_value(&mut style.background, Background::White, tr("background-white"));
+            ui.radio_value(&mut style.background, Background::Black, tr("background-black"));
+            ui.radio_value(
+                &mut style.background,
+                Background::FollowTheme,
+                tr("background-theme"),
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("aspect"));
+            ui.radio_value(&mut style.aspect, PlotAspect::Square, tr("aspect-square"));
+            ui.radio_value(&mut style.aspect, PlotAspect::Fill, tr("aspect-fill"));
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("theme"));
+            ui.radio_value(&mut self.settings.theme, Theme::System, tr("theme-system"));
+            ui.radio_value(&mut self.settings.theme, Theme::Light, tr("theme-light"));
+            ui.radio_value(&mut self.settings.theme, Theme::Dark, tr("theme-dark"));
+        });
+        ui.horizontal(|ui| {
+            let label = ui.label(tr("max-fps"));
+            ui.add(
+                egui::Slider::new(&mut self.settings.max_fps, 0..=240).custom_formatter(|v, _| {
+                    if v == 0.0 {
+                        tr("max-fps-unlimited")
+                    } else {
+                        format!("{} Hz", v)
+                    }
+                }),
+            )
+            .labelled_by(label.id)
+            .on_hover_text(tr("max-fps-hint"));
+        });
+        ui.horizontal(|ui| {
+            let label = ui.label(tr("ui-scale"));
+            ui.add(
+                egui::Slider::new(&mut self.settings.ui_scale, Settings::UI_SCALE_RANGE)
+                    .step_by(0.05)
+                    .suffix("×"),
+            )
+            .labelled_by(label.id);
+        });
+    }
+
+    /// Apparence de la grille et des axes à l'écran
+    fn plot_style(&self, grid_color: egui::Color32, axis_color: egui::Color32) -> PlotStyle {
+        let (grid_width, axis_width) = if self.settings.plot.high_contrast {
+            (1.5, 3.0)
+        } else {
+            (0.5, 1.0)
+        };
+        PlotStyle {
+            grid: grid_color,
+            minor_grid: grid_color.gamma_multiply(0.4),
+            axis: axis_color,
+            grid_width,
+            axis_width,
+            tick_size: 11.0,
+            name_size: 14.0,
+        }
+    }
+
+    /// Tracer la courbe d'un canal dans sa couleur
+    fn paint_trace(
+        &self,
+        painter: &mut egui::Painter,
+        curve: &CurveData,
+        channel: u8,
+        frame: PlotFrame,
+        dark: bool,
+    ) {
+        let color = self.channel_color(channel, dark);
+        let stroke = egui::Stroke::new(self.settings.plot.trace_width(), color);
+        plot_renderer::draw_trace(painter, curve, &self.settings, frame, stroke);
+    }
+
+    /// Tracer les balayages précédents d'un canal, de plus en plus transparents
+    fn paint_ghosts(&self, painter: &mut egui::Painter, channel: u8, frame: PlotFrame, dark: bool) {
+        let count = self.settings.plot.ghosts;
+        // Affichage figé : pas de fantômes qui continuent de défiler
+        if count == 0 || self.tab().paused {
+            return;
+        }
+
+        let history = self.tab().history.lock().unwrap();
+        let color = self.channel_color(channel, dark);
+        let width = self.settings.plot.trace_width() * 0.75;
+        // La plus récente est la courbe live, déjà tracée
+        let ghosts: Vec<&CurveData> =
+            history.channel(channel).iter().rev().skip(1).take(count).collect();
+        for (age, curve) in ghosts.iter().enumerate().rev() {
+            let alpha = 0.6 * (1.0 - age as f32 / count as f32);
+            let stroke = egui::Stroke::new(width, color.gamma_multiply(alpha));
+            plot_renderer::draw_curve(painter, curve, &self.settings.axes, frame, stroke, false);
+        }
+    }
+
+    /// Entrée de bibliothèque sélectionnée
+    fn selected_library_entry(&self) -> Option<&LibraryEntry> {
+        self.library_selection.and_then(|i| self.library.entries.get(i))
+    }
+
+    /// Superposer l'entrée de bibliothèque sélectionnée et son score de
+    /// correspondance avec les courbes affichées
+    fn paint_library_overlay(
+        &self,
+        painter: &mut egui::Painter,
+        frame: PlotFrame,
+        curves: &[(u8, &CurveData)],
+        text_color: egui::Color32,
+    ) {
+        let Some(entry) = self.selected_library_entry() else {
+            return;
+        };
+        let stroke = egui::Stroke::new(self.settings.plot.trace_width(), LIBRARY_COLOR);
+        let axes = &self.settings.axes;
+        plot_renderer::draw_curve(painter, &entry.curve, axes, frame, stroke, true);
+
+        let mut lines = vec![entry.name.clone()];
+        for &(channel, curve) in curves {
+            let score = match_score(&entry.curve, curve);
+            lines.push(tr_args(
+                "library-match",
+                &[
+                    ("channel", format!("CH{}", channel).into()),
+                    ("score", format!("{:.0}", score).into()),
+                ],
+            ));
+        }
+        painter.text(
+            frame.rect.right_top() + egui::vec2(-10.0, 10.0),
+            egui::Align2::RIGHT_TOP,
+            lines.join("\n"),
+            egui::FontId::proportional(14.0),
+            text_color,
+        );
+    }
+
+    /// Panneau de la bibliothèque : sélection et ajout de la courbe affichée
+    fn draw_library_panel(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let label = ui.label(tr("library-overlay"));
+            let selected_text = self
+                .selected_library_entry()
+                .map(|e| e.name.clone())
+                .unwrap_or_else(|| tr("library-none"));
+            egui::ComboBox::from_id_source("library_selection")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.library_selection, None, tr("library-none"));
+                    for (i, entry) in self.library.entries.iter().enumerate() {
+                        ui.selectable_value(&mut self.library_selection, Some(i), &entry.name)
+                            .on_hover_text(&entry.description);
+                    }
+                })
+                .response
+                .labelled_by(label.id);
+        });
+
+        ui.horizontal(|ui| {
+            let label = ui.label(tr("library-name"));
+            ui.text_edit_singleline(&mut self.library_name)
+                .labelled_by(label.id);
+            let name = self.library_name.trim().to_string();
+            if ui
+                .add_enabled(!name.is_empty(), egui::Button::new(tr("library-add")))
+                .clicked()
+            {
+                self.add_to_library(&name);
+            }
+        });
+
+        ui.horizontal(|ui| {
+            let export = egui::Button::new(tr("library-export"));
+            if ui
+                .add_enabled(!self.library.entries.is_empty(), export)
+                .on_hover_text(tr("library-export-hint"))
+                .clicked()
+            {
+                let result = self.export_path("library", "zip").and_then(|path| {
+                    self.library.export_bundle(&path).map(|_| path)
+                });
+                self.export_done(result);
+            }
+            if ui.button(tr("library-import")).clicked() {
+                self.import_library_bundle();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("library-conflicts"));
+            let merge = &mut self.settings.library_merge;
+            for (policy, key) in MERGE_POLICIES {
+                ui.radio_value(merge, policy, tr(key));
+            }
+        });
+    }
+
+    /// Ajouter à la bibliothèque les entrées d'un paquet choisi par l'utilisateur
+    fn import_library_bundle(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(tr("library-import"))
+            .add_filter("ZIP", &["zip"])
+            .pick_file()
+        else {
+            return;
+        };
+        // La sélection suit l'entrée choisie, dont l'index peut changer
+        let selected = self.selected_library_entry().map(|e| e.name.clone());
+        match self.library.import_bundle(&path, self.settings.library_merge) {
+            Ok(summary) => {
+                let args = [
+                    ("added", summary.added.into()),
+                    ("replaced", summary.replaced.into()),
+                    ("renamed", summary.renamed.into()),
+                    ("skipped", summary.skipped.into()),
+                    ("unchanged", summary.unchanged.into()),
+                ];
+                self.notifications.push(Level::Success, tr_args("library-imported", &args));
+            }
+            Err(e) => self
+                .tab()
+                .status
+                .lock()
+                .unwrap()
+                .set_error(tr_args("library-error", &[("error", e.into())])),
+        }
+        self.library_selection =
+            selected.and_then(|name| self.library.entries.iter().position(|e| e.name == name));
+    }
+
+    /// Enregistrer la courbe affichée (CH0 en mode CH0, CH1 sinon) dans la bibliothèque
+    fn add_to_library(&mut self, name: &str) {
+        let channel = u8::from(self.settings.display_mode != DisplayMode::SingleCh0);
+        let Some(curve) = self.displayed_data().channel(channel).cloned() else {
+            self.notifications
+                .push(Level::Warning, tr_args("library-no-data", &[("channel", channel.into())]));
+            return;
+        };
+
+        match self.library.add(LibraryEntry::new(name, curve)) {
+            Ok(path) => {
+                info!("Entrée de bibliothèque enregistrée : {}", path.display());
+                self.library_selection =
+                    self.library.entries.iter().position(|e| e.name == name);
+                self.library_name.clear();
+                self.notifications
+                    .push(Level::Success, tr_args("library-added", &[("name", name.into())]));
+            }
+            Err(e) => self
+                .tab()
+                .status
+                .lock()
+                .unwrap()
+                .set_error(tr_args("library-error", &[("error", e.into())])),
+        }
+    }
+
+    /// Mode enseignement : explication de la forme de chaque courbe en bas du
+    /// tracé, et repère du seuil de conduction des diodes
+    fn paint_teaching(
+        &self,
+        painter: &egui::Painter,
+        frame: PlotFrame,
+        curves: &[(u8, &CurveData)],
+        dark: bool,
+    ) {
+        if !self.settings.teaching {
+            return;
+        }
+        let rect = frame.rect;
+        let calibration = &self.settings.calibration;
+        let frequency = self.test_frequency();
+
+        let mut galleys = Vec::new();
+        for &(channel, curve) in curves {
+            let analysis = Analysis::of(curve);
+            let value = |text: String| [("value", text.into())];
+            let text = match analysis.component {
+                Component::Open => tr("teach-open"),
+                Component::Short => tr("teach-short"),
+                Component::Resistor => tr_args(
+                    "teach-resistor",
+                    &value(analysis.format_resistance(calibration)),
+                ),
+                Component::Diode => {
+                    let vf = analysis.format_forward_voltage(calibration);
+                    tr_args("teach-diode", &value(vf.unwrap_or_default()))
+                }
+                Component::Capacitor => {
+                    match frequency.and_then(|f| estimate_capacitance(curve, calibration, f)) {
+                        Some(farads) => {
+                            tr_args("teach-capacitor-value", &value(format_capacitance(farads)))
+                        }
+                        None => tr("teach-capacitor"),
+                    }
+                }
+                Component::Unknown => tr("teach-unknown"),
+            };
+            let color = self.channel_color(channel, dark);
+
+            if let (Component::Diode, Some(vf)) = (analysis.component, analysis.forward_voltage) {
+                let (x, y) = self.settings.axes.project(vf, 0.0);
+                let knee = frame.screen_pos([x, y]);
+                painter.circle_stroke(knee, 6.0, egui::Stroke::new(2.0, color));
+                painter.text(
+                    knee + egui::vec2(0.0, 10.0),
+                    egui::Align2::CENTER_TOP,
+                    "Vf",
+                    egui::FontId::proportional(13.0),
+                    color,
+                );
+            }
+
+            galleys.push(painter.layout(
+                format!("CH{}: {}", channel, text),
+                egui::FontId::proportional(14.0),
+                color,
+                rect.width() - 24.0,
+            ));
+        }
+        if galleys.is_empty() {
+            return;
+        }
+
+        // Cadre semi-opaque en bas à gauche, lisible sur la grille
+        let height: f32 = galleys.iter().map(|g| g.size().y + 2.0).sum();
+        let width = galleys.iter().map(|g| g.size().x).fold(0.0, f32::max);
+        let frame = egui::Rect::from_min_size(
+            rect.left_bottom() + egui::vec2(8.0, -height - 16.0),
+            egui::vec2(width + 8.0, height + 8.0),
+        );
+        let (background, _, _) = self.plot_colors(dark);
+        painter.rect_filled(frame, 4.0, background.gamma_multiply(0.85));
+        let mut pos = frame.min + egui::vec2(4.0, 4.0);
+        for galley in galleys {
+            let height = galley.size().y;
+            painter.galley(pos, galley);
+            pos.y += height + 2.0;
+        }
+    }
+
+    /// Tracer les annotations (texte et flèche) de l'onglet actif
+    fn paint_annotations(
+        &self,
+        painter: &mut egui::Painter,
+        frame: PlotFrame,
+        color: egui::Color32,
+    ) {
+        let stroke = egui::Stroke::new(1.5, color);
+        plot_renderer::draw_annotations(painter, &self.tab().annotations, frame, stroke, 14.0);
+        if let Some(target) = self.pending_target {
+            painter.circle_stroke(frame.screen_pos(target), 4.0, stroke);
+        }
+    }
+
+    /// Tracer la courbe de référence d'un canal, si elle existe
+    fn paint_reference(&self, painter: &mut egui::Painter, channel: u8, frame: PlotFrame) {
+        let Some(curve) = self.tab().reference.as_ref().and_then(|r| r.channel(channel)) else {
+            return;
+        };
+        let stroke = egui::Stroke::new(self.settings.plot.trace_width(), REFERENCE_COLOR);
+        plot_renderer::draw_curve(painter, curve, &self.settings.axes, frame, stroke, false);
+    }
+
+    /// Ligne de boutons envoyant une commande au boîtier
+    fn draw_command_row(
+        &mut self,
+        ui: &mut egui::Ui,
+        backend: &Arc<Mutex<HidBackend>>,
+        setting: &DeviceSetting,
+    ) {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", tr(setting.key)));
+            let current = self.tab().applied_device.get(setting.id).cloned();
+            for &(label, index) in setting.options {
+                let selected = current.as_deref() == Some(label);
+                if ui.selectable_label(selected, label).clicked() {
+                    self.apply_device_setting(backend, setting, label, index);
+                }
+            }
+        });
+    }
+
+    /// Envoyer un réglage au boîtier et afficher le résultat
+    fn apply_device_setting(
+        &mut self,
+        backend: &Arc<Mutex<HidBackend>>,
+        setting: &DeviceSetting,
+        label: &str,
+        index: u8,
+    ) {
+        let result = backend.lock().unwrap().send_cmd((setting.command)(index));
+        match result {
+            Ok(()) => {
+                self.settings
+                    .device
+                    .insert(setting.id.to_string(), label.to_string());
+                self.tab_mut()
+                    .applied_device
+                    .insert(setting.id.to_string(), label.to_string());
+                self.notifications.push(
+                    Level::Success,
+                    tr_args(
+                        "cmd-applied",
+                        &[("setting", tr(setting.key).into()), ("value", label.into())],
+                    ),
+                );
+            }
+            Err(e) => self
+                .tab()
+                .status
+                .lock()
+                .unwrap()
+                .set_error(tr_args("cmd-error", &[("error", e.into())])),
+        }
+    }
+
+    /// Exporter les courbes affichées en image, à l'emplacement et au format
+    /// choisis dans la boîte « Enregistrer sous » (`format` proposé d'abord)
+    fn export_image(&mut self, format: ImageFormat) {
+        let data = self.displayed_data();
+        // Canal affiché seul ; `None` dans les modes doubles
+        let single = match self.settings.display_mode {
+            DisplayMode::SingleCh0 => Some(0),
+            DisplayMode::SingleCh1 => Some(1),
+            DisplayMode::DualOverlay | DisplayMode::DualSideBySide => None,
+        };
+        let stem = match (self.settings.display_mode, single) {
+            (DisplayMode::DualOverlay, _) => "curves_overlay".to_string(),
+            (_, None) => "curves".to_string(),
+            (_, Some(channel)) => {
+                if data.channel(channel).is_none() {
+                    // Rien à exporter : simple avertissement, pas une erreur
+                    let args = [("channel", channel.into())];
+                    self.notifications.push(Level::Warning, tr_args("no-data-channel", &args));
+                    return;
+                }
+                format!("curve_ch{}", channel)
+            }
+        };
+        let Some((path, format)) = self.pick_image_path(&stem, format) else {
+            return;
+        };
+        // La prochaine boîte s'ouvre dans le dossier choisi
+        if let Some(dir) = path.parent() {
+            self.settings.export_dir = dir.display().to_string();
+        }
+
+        let tab = self.tab();
+        let channels = single.map_or(vec![0, 1], |channel| vec![channel]);
+        let target = |channel| self.match_target(channel);
+        let caption = if self.settings.export_measurements {
+            report::image_caption(tab, &data, &channels, &self.settings, target)
+        } else {
+            Vec::new()
+        };
+        let metadata = report::png_metadata(tab, &data, &channels, &self.settings, target);
+        let text = ImageText {
+            notes: &tab.notes,
+            caption: &caption,
+            metadata: &metadata,
+        };
+        let (annotations, settings) = (&tab.annotations, &self.settings);
+        let result = match (settings.display_mode, single.and_then(|c| data.channel(c))) {
+            (DisplayMode::DualOverlay, _) => {
+                save_overlay_curves(&data, settings, annotations, &text, format, &path)
+            }
+            (_, None) => save_dual_curves(&data, settings, annotations, &text, format, &path),
+            (_, Some(curve)) => save_curve(curve, settings, annotations, &text, format, &path),
+        };
+        self.export_done(result.map(|_| path));
+    }
+
+    /// Boîte « Enregistrer sous » des images, dans le dossier d'export avec un
+    /// nom horodaté ; le format suit l'extension choisie (`format` sinon)
+    fn pick_image_path(&self, stem: &str, format: ImageFormat) -> Option<(PathBuf, ImageFormat)> {
+        let mut dialog = rfd::FileDialog::new()
+            .set_title(tr("save-image-title"))
+            .set_file_name(format!("{}.{}", timestamped(stem), format.extension()));
+        let dir = Path::new(&self.settings.export_dir);
+        if dir.is_dir() {
+            dialog = dialog.set_directory(dir);
+        }
+        // Format demandé en tête de la liste des types
+        let others = ImageFormat::ALL.into_iter().filter(|f| *f != format);
+        for choice in std::iter::once(format).chain(others) {
+            let extension = choice.extension();
+            dialog = dialog.add_filter(extension.to_uppercase(), &[extension]);
+        }
+
+        let mut path = dialog.save_file()?;
+        let format = ImageFormat::from_path(&path).unwrap_or_else(|| {
+            path.set_extension(format.extension());
+            format
+        });
+        Some((path, format))
+    }
+
+    /// Fichier d'export horodaté dans le dossier d'export (créé au besoin) :
+    /// les exports successifs ne s'écrasent pas
+    fn export_path(&self, stem: &str, extension: &str) -> Result<PathBuf, String> {
+        let dir = Path::new(&self.settings.export_dir);
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Impossible de créer {}: {}", dir.display(), e))?;
+        let name = timestamped(stem);
+        // Deux exports dans la même seconde : suffixe numéroté
+        let mut path = dir.join(format!("{}.{}", name, extension));
+        let mut n = 2;
+        while path.exists() {
+            path = dir.join(format!("{}-{}.{}", name, n, extension));
+            n += 1;
+        }
+        Ok(std::path::absolute(&path).unwrap_or(path))
+    }
+
+    /// Démarrer l'enregistrement vidéo des canaux affichés, dans un fichier
+    /// MP4 ou WebM choisi par l'utilisateur
+    fn start_video(&mut self) {
+        let mut dialog = rfd::FileDialog::new()
+            .set_title(tr("video-title"))
+            .set_file_name(format!("{}.mp4", timestamped("plot")))
+            .add_filter("MP4", &["mp4"])
+            .add_filter("WebM", &["webm"]);
+        let dir = Path::new(&self.settings.export_dir);
+        if dir.is_dir() {
+            dialog = dialog.set_directory(dir);
+        }
+        let Some(mut path) = dialog.save_file() else {
+            return;
+        };
+        if path.extension().is_none() {
+            path.set_extension("mp4");
+        }
+        if let Some(dir) = path.parent() {
+            self.settings.export_dir = dir.display().to_string();
+        }
+
+        match VideoRecorder::start(&path, self.displayed_channels()) {
+            Ok(recorder) => self.recorder = Some(recorder),
+            Err(e) => self.export_done(Err(e)),
+        }
+    }
+
+    /// Ajouter à la vidéo les images dues depuis la précédente
+    fn record_video_frame(&mut self, ctx: &egui::Context) {
+        let Some(mut recorder) = self.recorder.take() else {
+            return;
+        };
+        let due = recorder.frames_due();
+        if due > 0 {
+            let data = self.displayed_data();
+            let curves: Vec<Option<&CurveData>> =
+                recorder.channels.iter().map(|&c| data.channel(c)).collect();
+            let image = plot_image(&curves, &self.settings);
+            if let Err(e) = recorder.push(image.as_raw(), due) {
+                // L'erreur de ffmpeg explique mieux l'arrêt que le tube fermé
+                let result = recorder.finish().and(Err(e));
+                self.export_done(result);
+                return;
+            }
+        }
+        self.recorder = Some(recorder);
+        ctx.request_repaint_after(Duration::from_secs(1) / video::FPS);
+    }
+
+    /// Arrêter l'enregistrement vidéo et finaliser le fichier
+    fn stop_video(&mut self) {
+        if let Some(recorder) = self.recorder.take() {
+            let result = recorder.finish();
+            self.export_done(result);
+        }
+    }
+
+    /// Fréquence de test (Hz) acceptée par le boîtier, sinon la dernière
+    /// enregistrée
+    fn test_frequency(&self) -> Option<f32> {
+        self.tab()
+            .applied_device
+            .get("frequency")
+            .or_else(|| self.settings.device.get("frequency"))
+            .and_then(|label| frequency_hz(label))
+    }
+
+    /// Dossier horodaté créé dans le dossier d'export, pour les exports par lot
+    fn export_folder(&self, stem: &str) -> Result<PathBuf, String> {
+        let dir = Path::new(&self.settings.export_dir).join(timestamped(stem));
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Impossible de créer {}: {}", dir.display(), e))?;
+        Ok(std::path::absolute(&dir).unwrap_or(dir))
+    }
+
+    /// Exporter toutes les courbes de l'historique des canaux affichés,
+    /// numérotées par canal de la plus ancienne (1) à la plus récente
+    fn export_history(&mut self, kind: HistoryExport) {
+        let channels = self.displayed_channels();
+        let sweeps: Vec<(usize, CurveData)> = {
+            let history = self.tab().history.lock().unwrap();
+            channels
+                .iter()
+                .flat_map(|&channel| history.channel(channel).iter().cloned().enumerate())
+                .map(|(i, curve)| (i + 1, curve))
+                .collect()
+        };
+        if sweeps.is_empty() {
+            self.notifications.push(Level::Warning, tr("measure-none"));
+            return;
+        }
+
+        let settings = &self.settings;
+        let result = match kind {
+            HistoryExport::SingleCsv => self.export_path("history", "csv").and_then(|path| {
+                let data = self.displayed_data();
+                let header = report::header(self.tab(), &data, &channels, settings);
+                save_sweeps_as_csv(&sweeps, settings, &header, &path).map(|_| path)
+            }),
+            HistoryExport::Gif => self.export_path("history", "gif").and_then(|path| {
+                let frames = animation_frames(&sweeps);
+                save_animation(&frames, settings, settings.animation_delay_ms, &path)
+                    .map(|_| path)
+            }),
+            HistoryExport::Sigrok => self.export_path("history", "sr").and_then(|path| {
+                let frequency = self.test_frequency();
+                save_sweeps_as_sigrok(&sweeps, settings, frequency, &path).map(|_| path)
+            }),
+            HistoryExport::Metrics => self.export_path("history_metrics", "csv").and_then(|path| {
+                let threshold = settings.analysis.pass_threshold;
+                let rows: Vec<MetricsRow> = sweeps
+                    .iter()
+                    .map(|(sweep, curve)| {
+                        let score = self.match_target(curve.channel).map(|t| match_score(curve, t));
+                        MetricsRow {
+                            label: sweep.to_string(),
+                            curve: curve.clone(),
+                            score,
+                            pass: score.map(|s| s >= threshold),
+                        }
+                    })
+                    .collect();
+                let data = self.displayed_data();
+                let header = report::header(self.tab(), &data, &channels, settings);
+                let frequency = self.test_frequency();
+                save_metrics_as_csv(&rows, "sweep", settings, frequency, &header, &path)
+                    .map(|_| path)
+            }),
+            HistoryExport::Png | HistoryExport::Csv => {
+                self.export_folder("history").and_then(|dir| {
+                    for (sweep, curve) in &sweeps {
+                        let name = format!("ch{}_{:03}", curve.channel, sweep);
+                        if let HistoryExport::Png = kind {
+                            let path = dir.join(name + ".png");
+                            let text = ImageText::default();
+                            save_curve(curve, settings, &[], &text, ImageFormat::Png, &path)?;
+                        } else {
+                            let mut data = DualCurveData::new();
+                            data.store(curve.clone());
+                            let channels = [curve.channel];
+                            let header = report::header(self.tab(), &data, &channels, settings);
+                            let path = dir.join(name + ".csv");
+                            save_curves_as_csv(&data, &channels, settings, &header, &path)?;
+                        }
+                    }
+                    Ok(dir)
+                })
+            }
+        };
+        self.export_done(result);
+    }
+
+    /// Résultat d'un export : chemin complet du fichier écrit, ou erreur
+    fn export_done(&mut self, result: Result<PathBuf, String>) {
+        match result {
+            Ok(path) => {
+                let path = path.display().to_string();
+                let args = [("path", path.clone().into())];
+                self.notifications.push(Level::Success, tr_args("saved-to", &args));
+                self.last_export = Some(path);
+            }
+            Err(e) => self
+                .tab()
+                .status
+                .lock()
+                .unwrap()
+                .set_error(tr_args("save-error", &[("error", e.into())])),
+        }
+    }
+
+    /// Exporter les points des canaux affichés en CSV
+    fn export_csv(&mut self) {
+        let data = self.displayed_data();
+        let channels = self.displayed_channels();
+        if channels.iter().all(|&c| data.channel(c).is_none()) {
+            self.notifications.push(Level::Warning, tr("measure-none"));
+            return;
+        }
+        let header = report::header(self.tab(), &data, &channels, &self.settings);
+        let result = self.export_path("curves", "csv").and_then(|path| {
+            save_curves_as_csv(&data, &channels, &self.settings, &header, &path).map(|_| path)
+        });
+        self.export_done(result);
+    }
+
+    /// Copier les points des courbes affichées dans le presse-papiers
+    fn copy_data(&mut self, ctx: &egui::Context) {
+        let data = self.displayed_data();
+        let channels = self.displayed_channels();
+        if channels.iter().all(|&c| data.channel(c).is_none()) {
+            self.notifications.push(Level::Warning, tr("measure-none"));
+            return;
+        }
+        let text = curves_as_tsv(&data, &channels, &self.settings);
+        ctx.output_mut(|o| o.copied_text = text);
+        self.notifications.push(Level::Success, tr("data-copied"));
+    }
+
+    /// Exporter les tableaux des courbes affichées en archive NumPy (.npz)
+    fn export_numpy(&mut self) {
+        let data = self.displayed_data();
+        let channels = self.displayed_channels();
+        if channels.iter().all(|&c| data.channel(c).is_none()) {
+            self.notifications.push(Level::Warning, tr("measure-none"));
+            return;
+        }
+        let result = self.export_path("curves", "npz").and_then(|path| {
+            save_curves_as_npz(&data, &channels, &self.settings, &path).map(|_| path)
+        });
+        self.export_done(result);
+    }
+
+    /// Exporter les courbes affichées et leurs métadonnées en fichier MATLAB
+    fn export_mat(&mut self) {
+        let data = self.displayed_data();
+        let channels = self.displayed_channels();
+        if channels.iter().all(|&c| data.channel(c).is_none()) {
+            self.notifications.push(Level::Warning, tr("measure-none"));
+            return;
+        }
+        let result = self.export_path("curves", "mat").and_then(|path| {
+            save_curves_as_mat(&data, &channels, &self.settings, self.tab(), &path).map(|_| path)
+        });
+        self.export_done(result);
+    }
+
+    /// Exporter les courbes affichées et leurs métadonnées en JSON
+    fn export_json(&mut self) {
+        let data = self.displayed_data();
+        let channels = self.displayed_channels();
+        if channels.iter().all(|&c| data.channel(c).is_none()) {
+            self.notifications.push(Level::Warning, tr("measure-none"));
+            return;
+        }
+        let scores: Vec<(u8, f32)> = channels
+            .iter()
+            .filter_map(|&c| {
+                let curve = data.channel(c)?;
+                Some((c, match_score(curve, self.match_target(c)?)))
+            })
+            .collect();
+        let result = self.export_path("curves", "json").and_then(|path| {
+            save_curves_as_json(&data, &channels, &self.settings, self.tab(), &scores, &path)
+                .map(|_| path)
+        });
+        self.export_done(result);
+    }
+
+    /// Exporter les courbes affichées dans une page HTML interactive autonome
+    fn export_html(&mut self) {
+        let data = self.displayed_data();
+        let channels = self.displayed_channels();
+        if channels.iter().all(|&c| data.channel(c).is_none()) {
+            self.notifications.push(Level::Warning, tr("measure-none"));
+            return;
+        }
+        let header = report::header(self.tab(), &data, &channels, &self.settings);
+        let fields = report::fields(
+            self.tab(),
+            &data,
+            &channels,
+            &self.settings,
+            self.selected_library_entry(),
+        );
+        let result = self.export_path("curves", "html").and_then(|path| {
+            save_curves_as_html(&data, &channels, &self.settings, &header, fields, &path)
+                .map(|_| path)
+        });
+        self.export_done(result);
+    }
+
+    /// Rapport PDF de la vue courante, avec le verdict contre l'entrée de
+    /// bibliothèque sélectionnée
+    fn export_report(&mut self) {
+        let data = self.displayed_data();
+        let channels = self.displayed_channels();
+        if channels.iter().all(|&c| data.channel(c).is_none()) {
+            self.notifications.push(Level::Warning, tr("measure-none"));
+            return;
+        }
+        let signature = self.selected_library_entry();
+        let result = self.export_path("report", "pdf").and_then(|path| {
+            report::save_report(self.tab(), &data, &channels, &self.settings, signature, &path)
+                .map(|_| path)
+        });
+        self.export_done(result);
+    }
+
+    /// Une courbe affichée a une référence : entrée de bibliothèque
+    /// sélectionnée ou référence figée
+    fn comparison_active(&self) -> bool {
+        let data = self.displayed_data();
+        self.displayed_channels()
+            .iter()
+            .any(|&c| data.channel(c).is_some() && self.match_target(c).is_some())
+    }
+
+    /// Rapport de comparaison des courbes affichées à leur référence : tracés
+    /// superposés, écart, statistiques et verdict
+    fn export_comparison(&mut self, format: ReportFormat) {
+        let data = self.displayed_data();
+        let channels = self.displayed_channels();
+        let signature = self.selected_library_entry();
+        let reference_name = signature
+            .map(|entry| entry.name.clone())
+            .unwrap_or_else(|| tr("comparison-held-reference"));
+        let threshold = self.settings.analysis.pass_threshold;
+        let deviations: Vec<ChannelDeviation> = channels
+            .iter()
+            .filter_map(|&c| {
+                let (live, reference) = (data.channel(c)?, self.match_target(c)?);
+                Some(ChannelDeviation::new(live, reference, &reference_name, threshold))
+            })
+            .collect();
+        if deviations.is_empty() {
+            self.notifications.push(Level::Warning, tr("comparison-none"));
+            return;
+        }
+        let header = report::header(self.tab(), &data, &channels, &self.settings);
+        let fields = report::fields(self.tab(), &data, &channels, &self.settings, signature);
+        let result = self.export_path("comparison", format.extension()).and_then(|path| {
+            save_comparison_report(&deviations, &header, fields, &self.settings, format, &path)
+                .map(|_| path)
+        });
+        self.export_done(result);
+    }
+
+    /// Imprimer la vue courante : tracé et métadonnées sur une page A4 en PDF,
+    /// ouverte dans le lecteur par défaut pour sa boîte d'impression
+    fn print_view(&mut self) {
+        let data = self.displayed_data();
+        let channels = self.displayed_channels();
+        if channels.iter().all(|&c| data.channel(c).is_none()) {
+            self.notifications.push(Level::Warning, tr("measure-none"));
+            return;
+        }
+
+        let path = match print_file() {
+            Ok(path) => path,
+            Err(e) => {
+                self.tab().status.lock().unwrap().set_error(e);
+                return;
+            }
+        };
+        let header = report::header(self.tab(), &data, &channels, &self.settings);
+        let result = save_page(
+            &data,
+            &channels,
+            &self.settings,
+            &self.tab().annotations,
+            &header,
+            ImageFormat::Pdf,
+            &path,
+        )
+        .and_then(|_| open_document(&path));
+
+        match result {
+            Ok(()) => self.notifications.push(Level::Success, tr("print-sent")),
+            Err(e) => {
+                let args = [("error", e.into()), ("path", path.display().to_string().into())];
+                self.tab().status.lock().unwrap().set_error(tr_args("print-error", &args));
+            }
+        }
+    }
+
+    /// Actions proposées par la palette de commandes
+    fn palette_actions(&self) -> Vec<(String, Action)> {
+        let mut actions: Vec<(String, Action)> = DISPLAY_MODES
+            .iter()
+            .map(|&(mode, key)| {
+                let label = format!("{} {}", tr("display-mode"), tr(key));
+                (label, Action::SetDisplayMode(mode))
+            })
+            .collect();
+
+        let pause_label = if self.tab().paused { tr("resume") } else { tr("pause") };
+        actions.push((pause_label, Action::TogglePause));
+        actions.push((tr("fullscreen"), Action::ToggleFullscreen));
+        actions.push((tr("save-png"), Action::ExportImage(ImageFormat::Png)));
+        actions.push((tr("save-svg"), Action::ExportImage(ImageFormat::Svg)));
+        actions.push((tr("save-csv"), Action::ExportCsv));
+        actions.push((tr("copy-data"), Action::CopyData));
+        actions.push((tr("save-json"), Action::ExportJson));
+        actions.push((tr("save-numpy"), Action::ExportNumpy));
+        actions.push((tr("save-mat"), Action::ExportMat));
+        actions.push((tr("save-html"), Action::ExportHtml));
+        actions.push((tr("print"), Action::Print));
+        actions.push((tr("report-pdf"), Action::ExportReport));
+        if self.comparison_active() {
+            for format in ReportFormat::ALL {
+                let extension = format.extension().to_uppercase();
+                let label = format!("{} ({})", tr("comparison-report"), extension);
+                actions.push((label, Action::ExportComparison(format)));
+            }
+        }
+
+        if self.tab().hid_backend.is_some() {
+            for (si, setting) in DEVICE_SETTINGS.iter().enumerate() {
+                for (oi, (label, _)) in setting.options.iter().enumerate() {
+                    let text = format!("⚡ {}: {}", tr(setting.key), label);
+                    actions.push((text, Action::DeviceSetting(si, oi)));
+                }
+            }
+        }
+        actions
+    }
+
+    fn run_action(&mut self, ctx: &egui::Context, action: Action) {
+        match action {
+            Action::SetDisplayMode(mode) => self.settings.display_mode = mode,
+            Action::TogglePause => self.set_paused(!self.tab().paused),
+            Action::ToggleFullscreen => self.toggle_fullscreen(ctx),
+            Action::ExportImage(format) => self.export_image(format),
+            Action::ExportCsv => self.export_csv(),
+            Action::CopyData => self.copy_data(ctx),
+            Action::ExportJson => self.export_json(),
+            Action::ExportNumpy => self.export_numpy(),
+            Action::ExportMat => self.export_mat(),
+            Action::ExportHtml => self.export_html(),
+            Action::Print => self.print_view(),
+            Action::ExportReport => self.export_report(),
+            Action::ExportComparison(format) => self.export_comparison(format),
+            Action::DeviceSetting(si, oi) => {
+                if let Some(backend) = self.tab().hid_backend.clone() {
+                    let setting = &DEVICE_SETTINGS[si];
+                    let (label, index) = setting.options[oi];
+                    self.apply_device_setting(&backend, setting, label, index);
+                }
+            }
+        }
+    }
+
+    /// Taille du tracé dans l'espace disponible, selon les proportions choisies
+    fn plot_size(&self, available: egui::Vec2) -> egui::Vec2 {
+        let available = available.max(egui::vec2(200.0, 200.0));
+        match self.settings.plot.aspect {
+            PlotAspect::Square => egui::Vec2::splat(available.min_elem()),
+            PlotAspect::Fill => available,
+        }
+    }
+
+    /// Résumé textuel des courbes tracées, lu par les lecteurs d'écran
+    fn summary(&self, curves: &[(u8, &CurveData)]) -> String {
+        let named: Vec<(String, &CurveData)> = curves
+            .iter()
+            .map(|(channel, curve)| (format!("CH{}", channel), *curve))
+            .collect();
+        plot_summary(&named, &self.settings)
+    }
+
+    /// Tracé d'un canal ; renvoie la position normalisée d'un clic
+    fn draw_single_channel(
+        &mut self,
+        ui: &mut egui::Ui,
+        channel: u8,
+        size: egui::Vec2,
+    ) -> Option<[f32; 2]> {
+        let (response, mut painter) = ui.allocate_painter(size, egui::Sense::click());
+        let rect = response.rect;
+
+        let dark = self.plot_is_dark(ui.visuals());
+        let (background, grid_color, axis_color) = self.plot_colors(dark);
+        painter.rect_filled(rect, 0.0, background);
+
+        let frame = PlotFrame::new(rect);
+        let style = self.plot_style(grid_color, axis_color);
+        plot_renderer::draw_grid(&mut painter, &self.settings, frame, &style);
+
+        let visible = self.channel_visible(channel);
+        if visible {
+            self.paint_reference(&mut painter, channel, frame);
+            self.paint_ghosts(&mut painter, channel, frame, dark);
+        }
+
+        let data = self.displayed_data();
+        let shown = data.channel(channel).filter(|_| visible);
+        let curves: Vec<(u8, &CurveData)> = shown.map(|c| (channel, c)).into_iter().collect();
+        self.paint_library_overlay(&mut painter, frame, &curves, axis_color);
+
+        if let Some(curve) = shown {
+            self.paint_trace(&mut painter, curve, channel, frame, dark);
+        }
+        plot_renderer::draw_axis_names(&mut painter, &self.settings.axes, frame, &style);
+
+        self.paint_teaching(&painter, frame, &curves, dark);
+        self.paint_annotations(&mut painter, frame, axis_color);
+        self.draw_legend(ui.ctx(), rect, &[channel], &data, dark);
+        let response = response.accessible_name(egui::WidgetType::Other, self.summary(&curves));
+        plot_click(&response, frame)
+    }
+
+    /// Tracé superposé des deux canaux ; renvoie la position normalisée d'un clic
+    fn draw_dual_overlay(&mut self, ui: &mut egui::Ui, size: egui::Vec2) -> Option<[f32; 2]> {
+        let (response, mut painter) = ui.allocate_painter(size, egui::Sense::click());
+        let rect = response.rect;
+
+        let dark = self.plot_is_dark(ui.visuals());
+        let (background, grid_color, axis_color) = self.plot_colors(dark);
+        painter.rect_filled(rect, 0.0, background);
+
+        let frame = PlotFrame::new(rect);
+        let style = self.plot_style(grid_color, axis_color);
+        plot_renderer::draw_grid(&mut painter, &self.settings, frame, &style);
+
+        let data = self.displayed_data();
+        let ch0 = data.channel0.as_ref().filter(|_| self.settings.show_ch0);
+        let ch1 = data.channel1.as_ref().filter(|_| self.settings.show_ch1);
+
+        if self.settings.show_ch0 {
+            self.paint_reference(&mut painter, 0, frame);
+            self.paint_ghosts(&mut painter, 0, frame, dark);
+        }
+        if self.settings.show_ch1 {
+            self.paint_reference(&mut painter, 1, frame);
+            self.paint_ghosts(&mut painter, 1, frame, dark);
+        }
+
+        let curves: Vec<(u8, &CurveData)> = [(0, ch0), (1, ch1)]
+            .into_iter()
+            .filter_map(|(channel, curve)| curve.map(|c| (channel, c)))
+            .collect();
+        self.paint_library_overlay(&mut painter, frame, &curves, axis_color);
+
+        if let Some(curve) = ch0 {
+            self.paint_trace(&mut painter, curve, 0, frame, dark);
+        }
+
+        if let Some(curve) = ch1 {
+            self.paint_trace(&mut painter, curve, 1, frame, dark);
+        }
+
+        plot_renderer::draw_axis_names(&mut painter, &self.settings.axes, frame, &style);
+
+        self.paint_teaching(&painter, frame, &curves, dark);
+        self.paint_annotations(&mut painter, frame, axis_color);
+        self.draw_legend(ui.ctx(), rect, &[0, 1], &data, dark);
+        let response = response.accessible_name(egui::WidgetType::Other, self.summary(&curves));
+        plot_click(&response, frame)
+    }
+}
+
+/// Images d'une animation : le n-ième balayage de chaque canal, les plus
+/// récents alignés quand les canaux n'ont pas le même nombre de courbes
+fn animation_frames(sweeps: &[(usize, CurveData)]) -> Vec<Vec<CurveData>> {
+    let by_channel: Vec<Vec<&CurveData>> = [0, 1]
+        .iter()
+        .map(|&c| sweeps.iter().map(|(_, curve)| curve).filter(|k| k.channel == c).collect())
+        .filter(|curves: &Vec<&CurveData>| !curves.is_empty())
+        .collect();
+    let count = by_channel.iter().map(Vec::len).min().unwrap_or(0);
+    (0..count)
+        .map(|i| {
+            by_channel
+                .iter()
+                .map(|curves| curves[curves.len() - count + i].clone())
+                .collect()
+        })
+        .collect()
+}
+
+/// Nom de fichier d'export horodaté
+fn timestamped(stem: &str) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    format!("{}_{}", stem, file_timestamp(now))
+}
+
+/// Modèle de rapport : fichier choisi ou modèle intégré
+fn draw_template_choice(
+    ui: &mut egui::Ui,
+    key: &str,
+    extensions: &[&str],
+    template: &mut Option<String>,
+) {
+    ui.horizontal(|ui| {
+        ui.label(tr(key));
+        let name = template
+            .as_deref()
+            .and_then(|path| Path::new(path).file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| tr("template-builtin"));
+        ui.label(name);
+        if ui.button(tr("template-choose")).clicked() {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter(tr(key), extensions)
+                .pick_file()
+            {
+                *template = Some(path.display().to_string());
+            }
+        }
+        if template.is_some() && ui.button(tr("template-reset")).clicked() {
+            *template = None;
+        }
+    });
+}
+
+/// Position normalisée (V, I) d'un clic sur un tracé
+fn plot_click(response: &egui::Response, frame: PlotFrame) -> Option<[f32; 2]> {
+    if !response.clicked() {
+        return None;
+    }
+    Some(frame.plot_pos(response.interact_pointer_pos()?))
+}
+
+/// Contenu et comportement des onglets de la disposition
+struct PanelViewer<'a> {
+    app: &'a mut CT220SApp,
+}
+
+impl TabViewer for PanelViewer<'_> {
+    type Tab = Panel;
+
+    fn title(&mut self, panel: &mut Panel) -> egui::WidgetText {
+        tr(panel.key()).into()
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, panel: &mut Panel) {
+        self.app.draw_panel(ui, *panel);
+    }
+
+    /// Identifiant stable : le titre change avec la langue
+    fn id(&mut self, panel: &mut Panel) -> egui::Id {
+        egui::Id::new(("panel", *panel))
+    }
+
+    fn closeable(&mut self, panel: &mut Panel) -> bool {
+        *panel != Panel::Plot
+    }
+
+    /// Le tracé occupe exactement l'onglet ; les panneaux défilent verticalement
+    fn scroll_bars(&self, panel: &Panel) -> [bool; 2] {
+        [false, *panel != Panel::Plot]
+    }
+}
+
+impl eframe::App for CT220SApp {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        self.apply_theme(ctx, frame);
+        self.apply_ui_scale(ctx);
+        for tab in &mut self.tabs {
+            tab.check_file_changed(&mut self.settings);
+        }
+        self.record_video_frame(ctx);
+
+        // La palette donne accès aux réglages du boîtier : absente en mode opérateur
+        if !self.operator
+            && ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::P))
+        {
+            self.command_palette.toggle();
+        }
+        if self.command_palette.open {
+            let actions = self.palette_actions();
+            let labels: Vec<String> = actions.iter().map(|(label, _)| label.clone()).collect();
+            if let Some(chosen) = self.command_palette.show(ctx, &labels) {
+                self.run_action(ctx, actions[chosen].1);
+            }
+        }
+
+        let leave_fullscreen = self.fullscreen
+            && !self.command_palette.open
+            && ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape));
+        let toggle_fullscreen =
+            ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::F11));
+        if leave_fullscreen || toggle_fullscreen {
+            self.toggle_fullscreen(ctx);
+        }
+
+        // Barres et panneaux de réglage, masqués en présentation et en mode opérateur
+        let show_controls = !self.fullscreen && !self.operator;
+        if show_controls {
+            egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
+                self.draw_menu_bar(ui);
+            });
+        }
+        if self.operator {
+            egui::TopBottomPanel::top("operator_bar").show(ctx, |ui| {
+                self.draw_operator_bar(ui);
+            });
+        }
+
+        let frame_interval = self.settings.frame_interval();
+        for (i, tab) in self.tabs.iter().enumerate() {
+            let mut status = tab.status.lock().unwrap();
+            status.set_repaint(ctx, frame_interval);
+            for (level, text) in status.take_pending() {
+                self.notifications.push(level, text);
+            }
+            if let Some(failure) = status.failure.take() {
+                self.reader_failure = Some((i, failure));
+            }
+        }
+        self.draw_reader_failure(ctx);
+        self.notifications.show(ctx);
+
+        if self.comparison.open {
+            self.draw_comparison(ctx);
+        }
+        if self.export_options_open {
+            self.draw_export_options(ctx);
+        }
+        if self.results.open {
+            self.draw_results(ctx);
+        }
+
+        if show_controls {
+            egui::TopBottomPanel::top("tab_bar").show(ctx, |ui| {
+                self.draw_tab_bar(ui);
+            });
+        }
+        if !self.fullscreen {
+            egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+                self.draw_status_bar(ui);
+            });
+        }
+        if show_controls {
+            self.settings.layout.ensure_plot();
+            self.draw_dock(ctx);
+        } else {
+            egui::CentralPanel::default().show(ctx, |ui| self.draw_plot(ui));
+        }
+
+        // Rafraîchissement à l'arrivée des données (voir AcquisitionStatus) ;
+        // seule la surveillance du fichier rejoué demande un réveil périodique
+        if self.settings.auto_reload && self.tabs.iter().any(|tab| tab.use_file_mode) {
+            ctx.request_repaint_after(RELOAD_CHECK_INTERVAL);
+        }
+    }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        let session = SessionState {
+            use_file_mode: self.tab().use_file_mode,
+            file_path: self.tab().file_path.clone(),
+            annotations: self.tab().annotations.clone(),
+            notes: self.tab().notes.clone(),
+        };
+        eframe::set_value(storage, eframe::APP_KEY, &session);
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.stop_video();
+        // Fermer les onglets arrête leurs threads de lecture
+        self.tabs.clear();
+        if let Err(e) = self.settings.save() {
+            error!("Erreur sauvegarde configuration: {}", e);
+        }
     }
 }
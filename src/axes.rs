@@ -0,0 +1,131 @@
+// src/axes.rs
+
+use crate::calibration::{format_value, Calibration};
+use crate::settings::GridStyle;
+use serde::{Deserialize, Serialize};
+
+/// Nombre de décades de courant visibles en échelle logarithmique
+pub const LOG_DECADES: i32 = 3;
+
+/// Graduation : position dans [-1, 1] et libellé
+pub type Tick = (f32, String);
+
+/// Échelles des axes, communes au tracé et aux exports
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlotAxes {
+    /// Courant en échelle logarithmique symétrique : les fuites (0,1 % de la
+    /// pleine échelle) et la conduction franche restent visibles ensemble
+    pub log_current: bool,
+    /// Courant en abscisse et tension en ordonnée (convention transposée)
+    pub swap: bool,
+}
+
+impl PlotAxes {
+    /// Position sur le tracé (x, y dans [-1, 1]) d'un point (V, I) normalisé
+    pub fn project(&self, voltage: f32, current: f32) -> (f32, f32) {
+        let current = self.current_position(current);
+        if self.swap {
+            (current, voltage)
+        } else {
+            (voltage, current)
+        }
+    }
+
+    fn current_position(&self, current: f32) -> f32 {
+        if !self.log_current {
+            return current;
+        }
+        // Une décade par 1/LOG_DECADES ; en dessous de la dernière, sur l'axe
+        let decades = LOG_DECADES as f32;
+        let magnitude = (current.abs().log10() + decades) / decades;
+        current.signum() * magnitude.clamp(0.0, 1.0)
+    }
+
+    /// Lignes verticales de la grille
+    pub fn x_lines(&self, grid: &GridStyle) -> Vec<(f32, bool)> {
+        if self.swap {
+            self.current_lines(grid)
+        } else {
+            grid.lines()
+        }
+    }
+
+    /// Lignes horizontales de la grille
+    pub fn y_lines(&self, grid: &GridStyle) -> Vec<(f32, bool)> {
+        if self.swap {
+            grid.lines()
+        } else {
+            self.current_lines(grid)
+        }
+    }
+
+    /// Lignes de l'axe courant : une par décade en échelle log
+    fn current_lines(&self, grid: &GridStyle) -> Vec<(f32, bool)> {
+        if !self.log_current {
+            return grid.lines();
+        }
+        self.current_decades()
+            .into_iter()
+            .map(|(pos, _)| (pos, true))
+            .collect()
+    }
+
+    /// Clés de traduction des noms des axes (abscisse, ordonnée)
+    pub fn name_keys(&self) -> (&'static str, &'static str) {
+        if self.swap {
+            ("axis-current", "axis-voltage")
+        } else {
+            ("axis-voltage", "axis-current")
+        }
+    }
+
+    /// Graduations (position dans [-1, 1], libellé) des axes horizontal et
+    /// vertical : unités physiques avec calibration, décades en échelle log
+    pub fn tick_labels(
+        &self,
+        calibration: &Calibration,
+        grid: &GridStyle,
+    ) -> (Vec<Tick>, Vec<Tick>) {
+        let voltage_ticks = if calibration.enabled {
+            calibration.voltage_ticks(grid)
+        } else {
+            Vec::new()
+        };
+
+        // Décades toujours graduées en échelle log, sinon illisibles
+        let current_ticks: Vec<Tick> = if self.log_current {
+            self.current_decades()
+                .into_iter()
+                .map(|(pos, value)| {
+                    let label = if calibration.enabled {
+                        format_value(value * calibration.milliamps_full_scale, "mA")
+                    } else {
+                        format!("{:+}", value)
+                    };
+                    (pos, label)
+                })
+                .collect()
+        } else if calibration.enabled {
+            calibration.current_ticks(grid)
+        } else {
+            Vec::new()
+        };
+        if self.swap {
+            (current_ticks, voltage_ticks)
+        } else {
+            (voltage_ticks, current_ticks)
+        }
+    }
+
+    /// Décades de l'axe courant : (position dans [-1, 1], courant normalisé)
+    pub fn current_decades(&self) -> Vec<(f32, f32)> {
+        (-LOG_DECADES..=LOG_DECADES)
+            .filter(|k| *k != 0)
+            .map(|k| {
+                let value = 10f32.powi(k.abs() - LOG_DECADES) * k.signum() as f32;
+                (k as f32 / LOG_DECADES as f32, value)
+            })
+            .collect()
+    }
+}
@@ -1,17 +1,22 @@
 // src/backend.rs
 
 use crate::config::*;
-use crate::curve::{parse_and_normalize_curve_data, CurveData, DualCurveData};
+use crate::curve::{parse_curve, CurveData, CurveHistory, DualCurveData};
+use crate::i18n::{tr, tr_args};
+use crate::notifications::Level;
+use crate::status::{AcquisitionStatus, Connection, CurveProgress};
 
 use hidapi::{HidApi, HidDevice};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
 
 /// Commandes disponibles pour le CT220S
 #[derive(Debug, Clone, Copy)]
+#[allow(clippy::enum_variant_names)]
 pub enum Command {
     SetFreq(u8), // FC
     SetRes(u8),  // FB
@@ -19,6 +24,125 @@ pub enum Command {
     SetVolt(u8), // FD
 }
 
+/// Réglages proposés par le panneau de commandes : (libellé, index)
+pub const FREQUENCIES: [(&str, u8); 4] = [("10Hz", 0), ("100Hz", 1), ("500Hz", 2), ("2kHz", 3)];
+pub const RESISTANCES: [(&str, u8); 4] = [("47R", 2), ("1K", 1), ("10K", 0), ("offset", 0)];
+pub const MODES: [(&str, u8); 2] = [("Simple", 0), ("Dual", 1)];
+pub const VOLTAGES: [(&str, u8); 4] = [("2.5V", 0), ("5V", 1), ("10V", 2), ("20V", 3)];
+
+/// Fréquence de test (Hz) correspondant à un libellé de `FREQUENCIES`
+pub fn frequency_hz(label: &str) -> Option<f32> {
+    let label = label.strip_suffix("Hz")?;
+    match label.strip_suffix('k') {
+        Some(kilo) => kilo.parse::<f32>().ok().map(|f| f * 1000.0),
+        None => label.parse().ok(),
+    }
+}
+
+/// Intervalle entre deux courbes en relecture, à vitesse 1
+const PLAYBACK_INTERVAL_MS: f32 = 50.0;
+
+/// État de relecture partagé entre l'interface et le thread de lecture fichier
+#[derive(Debug, Clone)]
+pub struct Playback {
+    pub playing: bool,
+    /// Reprendre au début en fin de fichier
+    pub looping: bool,
+    /// Facteur de vitesse (1.0 = une courbe toutes les 50 ms)
+    pub speed: f32,
+    /// Pas demandés par l'interface (+1 avance, -1 recule), consommés par le lecteur
+    pub step: i32,
+    /// Courbe affichée et nombre total de courbes, tenus à jour par le lecteur
+    pub position: usize,
+    pub total: usize,
+    /// Courbe affichée au chargement du fichier (bornée au nombre de courbes)
+    pub start: usize,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Self {
+            playing: true,
+            looping: true,
+            speed: 1.0,
+            step: 0,
+            position: 0,
+            total: 0,
+            start: 0,
+        }
+    }
+}
+
+impl Playback {
+    pub const SPEED_RANGE: std::ops::RangeInclusive<f32> = 0.1..=10.0;
+}
+
+/// Réglage du boîtier : identifiant, clé de traduction, options et commande associée
+pub struct DeviceSetting {
+    pub id: &'static str,
+    pub key: &'static str,
+    pub options: &'static [(&'static str, u8)],
+    pub command: fn(u8) -> Command,
+}
+
+pub const DEVICE_SETTINGS: [DeviceSetting; 4] = [
+    DeviceSetting {
+        id: "frequency",
+        key: "cmd-frequency",
+        options: &FREQUENCIES,
+        command: Command::SetFreq,
+    },
+    DeviceSetting {
+        id: "resistance",
+        key: "cmd-resistance",
+        options: &RESISTANCES,
+        command: Command::SetRes,
+    },
+    DeviceSetting {
+        id: "mode",
+        key: "cmd-mode",
+        options: &MODES,
+        command: Command::SetMode,
+    },
+    DeviceSetting {
+        id: "voltage",
+        key: "cmd-voltage",
+        options: &VOLTAGES,
+        command: Command::SetVolt,
+    },
+];
+
+impl DeviceSetting {
+    /// Retrouver un réglage par son identifiant
+    pub fn find(id: &str) -> Option<&'static DeviceSetting> {
+        DEVICE_SETTINGS.iter().find(|setting| setting.id == id)
+    }
+
+    /// Commande correspondant au libellé d'une option
+    pub fn command_for(&self, label: &str) -> Option<Command> {
+        self.options
+            .iter()
+            .find(|(option, _)| *option == label)
+            .map(|&(_, index)| (self.command)(index))
+    }
+
+    /// Libellé de l'option désignée par `value`, saisie en ligne de commande :
+    /// libellé exact sans tenir compte de la casse, ou sans son unité
+    /// (`5` pour 5V, `2k` pour 2kHz)
+    pub fn find_option(&self, value: &str) -> Option<&'static str> {
+        let value = value.trim().to_lowercase();
+        let labels = self.options.iter().map(|&(label, _)| label);
+        labels.clone().find(|label| label.to_lowercase() == value).or_else(|| {
+            labels.into_iter().find(|label| {
+                let label = label.to_lowercase();
+                label.strip_prefix(value.as_str()).is_some_and(|unit| {
+                    !value.is_empty() && unit.chars().all(|c| c.is_ascii_alphabetic())
+                })
+            })
+        })
+    }
+}
+
 /// Backend HID pour envoyer des commandes
 pub struct HidBackend {
     device: Arc<Mutex<HidDevice>>,
@@ -29,12 +153,12 @@ impl HidBackend {
     pub fn new() -> Result<Self, String> {
         let api = HidApi::new().map_err(|e| format!("Erreur HidApi: {}", e))?;
         
-        println!("Recherche du CT220S...");
+        info!("Recherche du CT220S...");
         let device = api
             .open(VID, PID)
             .map_err(|e| format!("Impossible d'ouvrir le périphérique: {}", e))?;
         
-        println!("Périphérique ouvert pour les commandes.");
+        info!("Périphérique ouvert pour les commandes.");
         
         Ok(Self {
             device: Arc::new(Mutex::new(device)),
@@ -50,54 +174,207 @@ impl HidBackend {
             Command::SetVolt(i) => (0xFDu8, i),
         };
 
-        let mut buf = [0u8; READ_SIZE];
-        buf[1] = prefix;
-        buf[2] = index;
+        self.send_report(&[prefix, index])?;
 
-        let device = self.device.lock().unwrap();
-        device.write(&buf).map_err(|e| e.to_string())?;
-        
-        println!(
+        debug!(
             "Cmd HID envoyée: prefix=0x{:02X}, index={}",
             prefix, index
         );
         Ok(())
     }
 
+    /// Envoyer un rapport brut : `payload` suit l'identifiant de rapport (0)
+    /// et est complété par des zéros
+    pub fn send_report(&self, payload: &[u8]) -> Result<(), String> {
+        if payload.len() > REPORT_DATA_SIZE {
+            return Err(format!(
+                "Rapport trop long: {} octets ({} au plus)",
+                payload.len(),
+                REPORT_DATA_SIZE
+            ));
+        }
+        let mut buf = [0u8; READ_SIZE];
+        buf[1..=payload.len()].copy_from_slice(payload);
+
+        let device = self.device.lock().unwrap();
+        device.write(&buf).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
     /// Clone le device pour le reader thread
     pub fn clone_device(&self) -> Arc<Mutex<HidDevice>> {
         Arc::clone(&self.device)
     }
 }
 
+/// Boîtier CT220S connecté
+pub struct DeviceInfo {
+    /// Chemin HID (ex. /dev/hidraw3)
+    pub path: String,
+    pub serial: String,
+    pub manufacturer: String,
+    pub product: String,
+    /// Numéro de version du boîtier (bcdDevice)
+    pub release: u16,
+}
+
+impl DeviceInfo {
+    /// Version du boîtier en texte (0x0102 → 1.02)
+    pub fn release_text(&self) -> String {
+        format!("{:x}.{:02x}", self.release >> 8, self.release & 0xff)
+    }
+}
+
+/// Boîtiers CT220S connectés, triés par chemin HID
+pub fn list_devices() -> Result<Vec<DeviceInfo>, String> {
+    let api = HidApi::new().map_err(|e| format!("Erreur HidApi: {}", e))?;
+    let text = |value: Option<&str>| value.unwrap_or_default().to_string();
+    let mut devices: Vec<DeviceInfo> = api
+        .device_list()
+        .filter(|info| info.vendor_id() == VID && info.product_id() == PID)
+        .map(|info| DeviceInfo {
+            path: info.path().to_string_lossy().into_owned(),
+            serial: text(info.serial_number()),
+            manufacturer: text(info.manufacturer_string()),
+            product: text(info.product_string()),
+            release: info.release_number(),
+        })
+        .collect();
+    devices.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(devices)
+}
+
+/// Ouvrir le boîtier pour une lecture directe (sans interface)
+pub fn open_device() -> Result<HidDevice, String> {
+    let api = HidApi::new().map_err(|e| format!("Erreur HidApi: {}", e))?;
+    api.open(VID, PID)
+        .map_err(|e| format!("Impossible d'ouvrir le périphérique: {}", e))
+}
+
+/// Lire les rapports HID bruts du boîtier pendant `duration` (sans limite si
+/// `None`), sans les interpréter ; `on_report` reçoit chaque rapport lu et
+/// renvoie `false` pour arrêter. Renvoie le nombre de rapports reçus
+pub fn read_raw_reports(
+    device: &HidDevice,
+    duration: Option<Duration>,
+    mut on_report: impl FnMut(&[u8]) -> Result<bool, String>,
+) -> Result<usize, String> {
+    let deadline = duration.map(|d| Instant::now() + d);
+    let mut count = 0;
+    while deadline.is_none_or(|deadline| Instant::now() < deadline) {
+        let mut buf = [0u8; READ_SIZE];
+        let n = device
+            .read_timeout(&mut buf, READ_TIMEOUT_MS)
+            .map_err(|e| format!("Erreur de lecture: {}", e))?;
+        if n > 0 {
+            count += 1;
+            if !on_report(&buf[..n])? {
+                break;
+            }
+        }
+    }
+    Ok(count)
+}
+
+/// Assemblage des courbes au fil des rapports reçus, comme la lecture en
+/// continu : en-tête, puis `REPORTS_PER_CURVE` rapports de données
+#[derive(Default)]
+pub struct CurveAssembler {
+    /// Canal, données et nombre de rapports de la courbe en cours
+    pending: Option<(u8, Vec<u8>, usize)>,
+}
+
+impl CurveAssembler {
+    /// Ajouter un rapport ; renvoie la courbe qu'il termine. Un rapport
+    /// invalide abandonne la courbe en cours
+    pub fn push(&mut self, report: &[u8]) -> Option<CurveData> {
+        let Some(payload) = extract_payload(report) else {
+            self.pending = None;
+            return None;
+        };
+        let Some((_, data, reports)) = self.pending.as_mut() else {
+            if is_header(&payload) {
+                let data = Vec::with_capacity(REPORTS_PER_CURVE * REPORT_DATA_SIZE);
+                self.pending = Some((payload[2], data, 0));
+            }
+            return None;
+        };
+        data.extend_from_slice(&payload);
+        *reports += 1;
+        let (channel, data, _) = self.pending.take_if(|p| p.2 == REPORTS_PER_CURVE)?;
+        parse_curve(&data, channel).ok()
+    }
+}
+
+/// Rapport d'en-tête d'une courbe : signature puis numéro de canal
+fn is_header(payload: &[u8]) -> bool {
+    payload.len() >= 3 && payload[..2] == HEADER_MAGIC
+}
+
+/// Acquérir le prochain balayage de chaque canal émis par le boîtier ; au
+/// bout de `timeout`, se contenter des canaux reçus (mode Simple)
+pub fn acquire_curves(device: &HidDevice, timeout: Duration) -> Result<Vec<CurveData>, String> {
+    let deadline = Instant::now() + timeout;
+    let mut curves: Vec<CurveData> = Vec::new();
+    while Instant::now() < deadline && curves.len() < 2 {
+        let keep_waiting = || Instant::now() < deadline;
+        if let Some(curve) = read_one_curve(device, keep_waiting, |_, _, _| {})? {
+            if !curves.iter().any(|c| c.channel == curve.channel) {
+                curves.push(curve);
+            }
+        }
+    }
+    if curves.is_empty() {
+        return Err("Aucune courbe reçue du boîtier".to_string());
+    }
+    curves.sort_by_key(|c| c.channel);
+    Ok(curves)
+}
+
+/// Ligne hex d'un rapport, au format des fichiers de capture
+pub fn hex_line(report: &[u8]) -> String {
+    report.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 /// Lecture HID en continu (mode réel)
 pub fn run_hid_reader(
     device: Arc<Mutex<HidDevice>>,
     curve_data: Arc<Mutex<DualCurveData>>,
-    error_message: Arc<Mutex<Option<String>>>,
+    history: Arc<Mutex<CurveHistory>>,
+    status: Arc<Mutex<AcquisitionStatus>>,
     running: Arc<Mutex<bool>>,
+    discard: Arc<Mutex<bool>>,
 ) -> Result<(), String> {
-    *error_message.lock().unwrap() = Some("Lecture en cours...".to_string());
+    status.lock().unwrap().notify(Level::Info, tr("status-reading"));
 
     while *running.lock().unwrap() {
         let curve = {
             let dev = device.lock().unwrap();
-            read_one_curve(&*dev)
+            let keep_waiting = || *running.lock().unwrap();
+            read_one_curve(&*dev, keep_waiting, |channel, reports, data_bytes| {
+                publish_partial_curve(channel, data_bytes, &curve_data, &discard);
+                let mut status = status.lock().unwrap();
+                status.assembling = Some(CurveProgress { channel, reports });
+                status.request_repaint();
+            })
         };
+        status.lock().unwrap().assembling = None;
 
         match curve {
-            Ok(curve) => {
-                let mut data = curve_data.lock().unwrap();
-                if curve.channel == 0 {
-                    data.channel0 = Some(curve);
-                } else {
-                    data.channel1 = Some(curve);
-                }
-                *error_message.lock().unwrap() = None;
+            // Pas de données avant le délai : on revérifie `running`
+            Ok(None) => {}
+            Ok(Some(curve)) => {
+                debug!(channel = curve.channel, points = curve.voltage.len(), "Courbe reçue");
+                publish_curve(&curve, &curve_data, &history, &discard);
+                let mut status = status.lock().unwrap();
+                status.connection = Connection::Usb;
+                status.record_curve();
             }
             Err(e) => {
-                eprintln!("Erreur de lecture: {}", e);
-                *error_message.lock().unwrap() = Some(format!("Erreur: {}", e));
+                warn!("Erreur de lecture: {}", e);
+                let mut status = status.lock().unwrap();
+                status.connection = Connection::Disconnected;
+                status.set_error(tr_args("status-error", &[("error", e.into())]));
             }
         }
         thread::sleep(Duration::from_millis(10));
@@ -110,9 +387,111 @@ pub fn run_hid_reader(
 pub fn run_file_reader(
     file_path: &str,
     curve_data: Arc<Mutex<DualCurveData>>,
-    error_message: Arc<Mutex<Option<String>>>,
+    history: Arc<Mutex<CurveHistory>>,
+    status: Arc<Mutex<AcquisitionStatus>>,
     running: Arc<Mutex<bool>>,
+    discard: Arc<Mutex<bool>>,
+    playback: Arc<Mutex<Playback>>,
 ) -> Result<(), String> {
+    let reports = read_capture_reports(file_path)?;
+    info!("Chargé {} rapports du fichier", reports.len());
+
+    // Découper la capture en courbes une fois pour toutes, pour pouvoir reculer
+    let curves = split_curves(&reports)?;
+
+    {
+        let mut status = status.lock().unwrap();
+        status.connection = Connection::File;
+        status.notify(Level::Info, tr_args(
+            "status-file-loaded",
+            &[("count", reports.len().into())],
+        ));
+    }
+    let start = {
+        let mut playback = playback.lock().unwrap();
+        playback.position = playback.start.min(curves.len() - 1);
+        playback.total = curves.len();
+        playback.step = 0;
+        playback.position
+    };
+    publish_curve(&curves[start], &curve_data, &history, &discard);
+    status.lock().unwrap().record_curve();
+
+    while *running.lock().unwrap() {
+        let (next, speed) = {
+            let mut playback = playback.lock().unwrap();
+            let step = if playback.step != 0 {
+                std::mem::take(&mut playback.step)
+            } else if playback.playing {
+                1
+            } else {
+                0
+            };
+
+            let target = playback.position as i64 + step as i64;
+            let total = curves.len() as i64;
+            let next = if step == 0 {
+                None
+            } else if (0..total).contains(&target) {
+                Some(target as usize)
+            } else if playback.looping {
+                Some(target.rem_euclid(total) as usize)
+            } else {
+                // Fin (ou début) de fichier sans boucle : on s'arrête
+                playback.playing = false;
+                None
+            };
+            if let Some(next) = next {
+                playback.position = next;
+            }
+            (next, playback.speed)
+        };
+
+        if let Some(next) = next {
+            publish_curve(&curves[next], &curve_data, &history, &discard);
+            status.lock().unwrap().record_curve();
+        }
+
+        let speed = speed.clamp(*Playback::SPEED_RANGE.start(), *Playback::SPEED_RANGE.end());
+        thread::sleep(Duration::from_millis((PLAYBACK_INTERVAL_MS / speed) as u64));
+    }
+
+    Ok(())
+}
+
+/// Rendre une courbe reçue visible par l'interface et l'ajouter à l'historique
+fn publish_curve(
+    curve: &CurveData,
+    curve_data: &Arc<Mutex<DualCurveData>>,
+    history: &Arc<Mutex<CurveHistory>>,
+    discard: &Arc<Mutex<bool>>,
+) {
+    // En pause sans enregistrement, la courbe est ignorée
+    if *discard.lock().unwrap() {
+        return;
+    }
+    curve_data.lock().unwrap().store(curve.clone());
+    history.lock().unwrap().push(curve.clone());
+}
+
+/// Afficher une courbe en cours d'assemblage, sans l'ajouter à l'historique ;
+/// elle est remplacée par la courbe complète à la fin du balayage
+fn publish_partial_curve(
+    channel: u8,
+    data_bytes: &[u8],
+    curve_data: &Arc<Mutex<DualCurveData>>,
+    discard: &Arc<Mutex<bool>>,
+) {
+    if data_bytes.is_empty() || *discard.lock().unwrap() {
+        return;
+    }
+    if let Ok(curve) = parse_curve(data_bytes, channel) {
+        curve_data.lock().unwrap().store(curve);
+    }
+}
+
+/// Lire les rapports HID d'un fichier de capture (une ligne hex par rapport)
+pub fn read_capture_reports(file_path: &str) -> Result<Vec<Vec<u8>>, String> {
     let file = File::open(file_path)
         .map_err(|e| format!("Impossible d'ouvrir {}: {}", file_path, e))?;
     let reader = BufReader::new(file);
@@ -136,36 +515,196 @@ pub fn run_file_reader(
     if reports.is_empty() {
         return Err("Aucune donnée trouvée dans le fichier".to_string());
     }
+    Ok(reports)
+}
 
-    println!("Chargé {} rapports du fichier", reports.len());
-    *error_message.lock().unwrap() = Some(format!("Fichier chargé: {} rapports", reports.len()));
+/// Problème relevé dans un fichier de capture
+pub struct CaptureIssue {
+    /// Numéro de ligne (à partir de 1)
+    pub line: usize,
+    pub message: String,
+}
 
-    let mut report_idx = 0;
-    while *running.lock().unwrap() {
-        match read_one_curve_from_reports(&reports, &mut report_idx) {
-            Ok(curve) => {
-                let mut data = curve_data.lock().unwrap();
-                if curve.channel == 0 {
-                    data.channel0 = Some(curve);
-                } else {
-                    data.channel1 = Some(curve);
+/// Résultat de la vérification d'un fichier de capture
+#[derive(Default)]
+pub struct CaptureCheck {
+    pub reports: usize,
+    /// Courbes complètes de chaque canal
+    pub curves: [usize; 2],
+    pub issues: Vec<CaptureIssue>,
+}
+
+/// Courbe en cours d'assemblage pendant la vérification
+struct PendingCurve {
+    line: usize,
+    channel: u8,
+    data: Vec<u8>,
+    reports: usize,
+}
+
+/// Vérifier un fichier de capture ligne par ligne : caractères et nombre de
+/// chiffres hex, taille des rapports, alignement des en-têtes et courbes
+/// complètes de `REPORTS_PER_CURVE` rapports. Chaque problème est rattaché à
+/// sa ligne ; seule l'ouverture du fichier peut échouer
+pub fn check_capture(file_path: &str) -> Result<CaptureCheck, String> {
+    let file = File::open(file_path)
+        .map_err(|e| format!("Impossible d'ouvrir {}: {}", file_path, e))?;
+    let mut check = CaptureCheck::default();
+    let issue = |line: usize, message: String| CaptureIssue { line, message };
+    let mut issues = Vec::new();
+    let mut pending: Option<PendingCurve> = None;
+    // Rapports de données hors courbe : première et dernière ligne, nombre
+    let mut orphans: Option<(usize, usize, usize)> = None;
+    let flush_orphans = |orphans: &mut Option<(usize, usize, usize)>, issues: &mut Vec<_>| {
+        if let Some((first, last, count)) = orphans.take() {
+            let message = tr_args(
+                "capture-orphans",
+                &[("count", count.into()), ("first", first.into()), ("last", last.into())],
+            );
+            issues.push(CaptureIssue {
+                line: first,
+                message,
+            });
+        }
+    };
+
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let number = index + 1;
+        let Ok(line) = line else {
+            issues.push(issue(number, tr("capture-unreadable")));
+            continue;
+        };
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // Séparateurs tolérés entre les octets ; tout autre caractère décale
+        // la lecture (ex. préfixe 0x)
+        if let Some(c) = line
+            .chars()
+            .find(|c| !c.is_ascii_hexdigit() && !c.is_whitespace() && !":,;-".contains(*c))
+        {
+            let message = tr_args("capture-not-hex", &[("char", c.to_string().into())]);
+            issues.push(issue(number, message));
+        }
+        let digits = line.chars().filter(|c| c.is_ascii_hexdigit()).count();
+        if digits % 2 == 1 {
+            let message = tr_args("capture-odd-digits", &[("digits", digits.into())]);
+            issues.push(issue(number, message));
+        }
+        let bytes = parse_hex_line(line)?;
+        if bytes.is_empty() {
+            continue;
+        }
+        check.reports += 1;
+        let Some(payload) = extract_payload(&bytes) else {
+            // Dans une courbe, le chargement de la capture s'arrête à ce
+            // rapport ; ailleurs il est ignoré
+            let consequence = match pending.take() {
+                Some(curve) => tr_args("capture-curve-lost", &[("line", curve.line.into())]),
+                None => tr("capture-report-ignored"),
+            };
+            let message = tr_args(
+                "capture-report-size",
+                &[
+                    ("size", bytes.len().into()),
+                    ("data", REPORT_DATA_SIZE.into()),
+                    ("read", READ_SIZE.into()),
+                    ("consequence", consequence.into()),
+                ],
+            );
+            issues.push(issue(number, message));
+            continue;
+        };
+
+        if is_header(&payload) {
+            flush_orphans(&mut orphans, &mut issues);
+            if let Some(curve) = pending.take() {
+                let message = tr_args(
+                    "capture-header-mid-curve",
+                    &[
+                        ("line", curve.line.into()),
+                        ("reports", curve.reports.into()),
+                        ("total", REPORTS_PER_CURVE.into()),
+                    ],
+                );
+                issues.push(issue(number, message));
+            }
+            let channel = payload[2];
+            if channel > 1 {
+                let message = tr_args("capture-unknown-channel", &[("channel", channel.into())]);
+                issues.push(issue(number, message));
+            }
+            pending = Some(PendingCurve {
+                line: number,
+                channel,
+                data: Vec::with_capacity(REPORTS_PER_CURVE * REPORT_DATA_SIZE),
+                reports: 0,
+            });
+            continue;
+        }
+
+        let Some(curve) = pending.as_mut() else {
+            let (first, _, count) = orphans.unwrap_or((number, number, 0));
+            orphans = Some((first, number, count + 1));
+            continue;
+        };
+        curve.data.extend_from_slice(&payload);
+        curve.reports += 1;
+        if let Some(curve) = pending.take_if(|curve| curve.reports == REPORTS_PER_CURVE) {
+            match parse_curve(&curve.data, curve.channel) {
+                Ok(_) => check.curves[curve.channel.min(1) as usize] += 1,
+                Err(e) => {
+                    let message = tr_args("capture-invalid-curve", &[("error", e.into())]);
+                    issues.push(issue(curve.line, message));
                 }
-                *error_message.lock().unwrap() = None;
             }
+        }
+    }
+
+    flush_orphans(&mut orphans, &mut issues);
+    if let Some(curve) = pending {
+        let message = tr_args(
+            "capture-incomplete",
+            &[("reports", curve.reports.into()), ("total", REPORTS_PER_CURVE.into())],
+        );
+        issues.push(issue(curve.line, message));
+    }
+    if check.reports == 0 {
+        issues.push(issue(0, tr("capture-empty")));
+    }
+    issues.sort_by_key(|i| i.line);
+    check.issues = issues;
+    Ok(check)
+}
+
+/// Découper des rapports en courbes complètes ; une fin tronquée est ignorée
+pub fn split_curves(reports: &[Vec<u8>]) -> Result<Vec<CurveData>, String> {
+    let mut curves = Vec::new();
+    let mut report_idx = 0;
+    while report_idx < reports.len() {
+        match read_one_curve_from_reports(reports, &mut report_idx) {
+            Ok(curve) => curves.push(curve),
             Err(e) => {
-                eprintln!("Erreur lecture courbe: {}", e);
-                *error_message.lock().unwrap() = Some(format!("Erreur: {}", e));
-                report_idx = 0;
+                warn!("Fin de capture: {}", e);
+                break;
             }
         }
-        thread::sleep(Duration::from_millis(50));
     }
+    if curves.is_empty() {
+        return Err("Aucune courbe complète dans le fichier".to_string());
+    }
+    Ok(curves)
+}
 
-    Ok(())
+/// Charger toutes les courbes d'un fichier de capture
+pub fn load_capture_curves(file_path: &str) -> Result<Vec<CurveData>, String> {
+    split_curves(&read_capture_reports(file_path)?)
 }
 
 /// Parsing d'une ligne hex (capture fichier)
-fn parse_hex_line(line: &str) -> Result<Vec<u8>, String> {
+pub fn parse_hex_line(line: &str) -> Result<Vec<u8>, String> {
     let mut bytes = Vec::new();
 
     let clean: String = line.chars().filter(|c| c.is_ascii_hexdigit()).collect();
@@ -236,24 +775,40 @@ fn read_one_curve_from_reports(
     }
     *start_idx += REPORTS_PER_CURVE;
 
-    let (v_norm, i_norm) = parse_and_normalize_curve_data(&data_bytes)?;
+    parse_curve(&data_bytes, channel_id)
+}
 
-    Ok(CurveData {
-        voltage: v_norm,
-        current: i_norm,
-        channel: channel_id,
-    })
+/// Source de rapports HID : le boîtier, ou des rapports préparés (tests)
+trait ReportSource {
+    /// Lire un rapport dans `buf` ; 0 si rien n'arrive avant `timeout_ms`
+    fn read_report(&self, buf: &mut [u8], timeout_ms: i32) -> Result<usize, String>;
 }
 
-fn read_one_curve(device: &HidDevice) -> Result<CurveData, String> {
-    let mut channel_id = 1u8;
+impl ReportSource for HidDevice {
+    fn read_report(&self, buf: &mut [u8], timeout_ms: i32) -> Result<usize, String> {
+        self.read_timeout(buf, timeout_ms)
+            .map_err(|e| format!("Erreur de lecture: {}", e))
+    }
+}
+
+/// Lire une courbe complète ; `None` si aucun header n'arrive avant le délai,
+/// ou si les données tardent et que `keep_waiting` renvoie `false`.
+/// `on_report` reçoit le canal, le nombre de rapports reçus et les données
+/// accumulées, après le header puis après chaque rapport.
+fn read_one_curve(
+    device: &impl ReportSource,
+    keep_waiting: impl Fn() -> bool,
+    mut on_report: impl FnMut(u8, usize, &[u8]),
+) -> Result<Option<CurveData>, String> {
+    let channel_id;
 
     // Attendre le header
     loop {
         let mut buf = [0u8; READ_SIZE];
-        let n = device
-            .read(&mut buf)
-            .map_err(|e| format!("Erreur de lecture: {}", e))?;
+        let n = device.read_report(&mut buf, READ_TIMEOUT_MS)?;
+        if n == 0 {
+            return Ok(None);
+        }
 
         if let Some(payload) = extract_payload(&buf[..n]) {
             if payload.len() >= 3
@@ -268,24 +823,117 @@ fn read_one_curve(device: &HidDevice) -> Result<CurveData, String> {
 
     // Lire les données
     let mut data_bytes = Vec::with_capacity(REPORTS_PER_CURVE * REPORT_DATA_SIZE);
-    for _ in 0..REPORTS_PER_CURVE {
+    on_report(channel_id, 0, &data_bytes);
+    for received in 1..=REPORTS_PER_CURVE {
         let mut buf = [0u8; READ_SIZE];
-        let n = device
-            .read(&mut buf)
-            .map_err(|e| format!("Erreur de lecture: {}", e))?;
+        // Délai dépassé en cours de courbe : boîtier lent, pas déconnecté
+        let n = loop {
+            let n = device.read_report(&mut buf, READ_TIMEOUT_MS)?;
+            if n > 0 {
+                break n;
+            }
+            if !keep_waiting() {
+                return Ok(None);
+            }
+        };
 
         if let Some(payload) = extract_payload(&buf[..n]) {
             data_bytes.extend_from_slice(&payload);
         } else {
             return Err("Payload invalide".to_string());
         }
+        on_report(channel_id, received, &data_bytes);
     }
 
-    let (v_norm, i_norm) = parse_and_normalize_curve_data(&data_bytes)?;
+    parse_curve(&data_bytes, channel_id).map(Some)
+}
 
-    Ok(CurveData {
-        voltage: v_norm,
-        current: i_norm,
-        channel: channel_id,
-    })
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::collections::VecDeque;
+
+    /// Rapports servis dans l'ordre ; un rapport vide simule un délai dépassé
+    struct FakeDevice(RefCell<VecDeque<Vec<u8>>>);
+
+    impl ReportSource for FakeDevice {
+        fn read_report(&self, buf: &mut [u8], _: i32) -> Result<usize, String> {
+            let report = self.0.borrow_mut().pop_front().unwrap_or_default();
+            buf[..report.len()].copy_from_slice(&report);
+            Ok(report.len())
+        }
+    }
+
+    fn header(channel: u8) -> Vec<u8> {
+        let mut report = vec![0u8; READ_SIZE];
+        report[1..3].copy_from_slice(&HEADER_MAGIC);
+        report[3] = channel;
+        report
+    }
+
+    /// Rapports de données d'une rampe : courant et tension croissants
+    fn data_reports() -> Vec<Vec<u8>> {
+        let bytes: Vec<u8> = (0..REPORTS_PER_CURVE * REPORT_DATA_SIZE / 4)
+            .flat_map(|i| {
+                let value = (i as u16).to_le_bytes();
+                [value[0], value[1], value[0], value[1]]
+            })
+            .collect();
+        bytes
+            .chunks(REPORT_DATA_SIZE)
+            .map(|chunk| [&[0u8][..], chunk].concat())
+            .collect()
+    }
+
+    fn device(reports: Vec<Vec<u8>>) -> FakeDevice {
+        FakeDevice(RefCell::new(reports.into()))
+    }
+
+    #[test]
+    fn reads_a_complete_curve() {
+        let mut reports = vec![header(1)];
+        reports.extend(data_reports());
+        let curve = read_one_curve(&device(reports), || true, |_, _, _| {}).unwrap();
+        assert_eq!(curve.map(|c| c.channel), Some(1));
+    }
+
+    #[test]
+    fn no_header_before_timeout_is_not_an_error() {
+        let curve = read_one_curve(&device(vec![Vec::new()]), || true, |_, _, _| {});
+        assert!(matches!(curve, Ok(None)));
+    }
+
+    #[test]
+    fn timeout_mid_curve_keeps_waiting() {
+        let mut reports = vec![header(0)];
+        let mut data = data_reports();
+        let rest = data.split_off(10);
+        reports.extend(data);
+        reports.extend([Vec::new(), Vec::new()]);
+        reports.extend(rest);
+        let curve = read_one_curve(&device(reports), || true, |_, _, _| {}).unwrap();
+        assert_eq!(curve.map(|c| c.channel), Some(0));
+    }
+
+    #[test]
+    fn timeout_mid_curve_gives_up_when_stopped() {
+        let mut reports = vec![header(0)];
+        reports.extend(data_reports().into_iter().take(5));
+        reports.push(Vec::new());
+        let checks = Cell::new(0);
+        let keep_waiting = || {
+            checks.set(checks.get() + 1);
+            false
+        };
+        let curve = read_one_curve(&device(reports), keep_waiting, |_, _, _| {});
+        assert!(matches!(curve, Ok(None)));
+        assert_eq!(checks.get(), 1);
+    }
+
+    #[test]
+    fn malformed_report_is_an_error() {
+        let reports = vec![header(0), vec![0u8; 3]];
+        assert!(read_one_curve(&device(reports), || true, |_, _, _| {}).is_err());
+    }
 }
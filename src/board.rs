@@ -0,0 +1,123 @@
+// src/board.rs
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Rayon de sélection d'un point de test, relatif à la largeur de l'image
+pub const PICK_RADIUS: f32 = 0.02;
+
+/// Point de test placé sur la photo de la carte
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestPoint {
+    pub name: String,
+    /// Position relative dans l'image (0 à 1 sur chaque axe)
+    pub position: [f32; 2],
+    /// Nom de l'entrée de bibliothèque servant de signature de référence
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+/// Plan de carte : photo et points de test, enregistré en TOML
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BoardMap {
+    /// Chemin de la photo de la carte
+    pub image: String,
+    #[serde(default)]
+    pub points: Vec<TestPoint>,
+}
+
+impl BoardMap {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Impossible de lire {}: {}", path.display(), e))?;
+        toml::from_str(&content).map_err(|e| format!("Plan de carte invalide: {}", e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content =
+            toml::to_string(self).map_err(|e| format!("Erreur sérialisation: {}", e))?;
+        fs::write(path, content)
+            .map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))
+    }
+
+    /// Point de test le plus proche d'une position relative, dans le rayon de sélection
+    pub fn point_at(&self, pos: [f32; 2]) -> Option<usize> {
+        let distance = |p: &TestPoint| {
+            ((p.position[0] - pos[0]).powi(2) + (p.position[1] - pos[1]).powi(2)).sqrt()
+        };
+        self.points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i, distance(p)))
+            .filter(|(_, d)| *d <= PICK_RADIUS)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+}
+
+/// Plan de carte ouvert et son état d'affichage
+#[derive(Default)]
+pub struct BoardView {
+    pub map: BoardMap,
+    /// Fichier du plan, une fois enregistré ou ouvert
+    pub path: Option<PathBuf>,
+    /// Ajout de points par clic sur la photo
+    pub editing: bool,
+    /// Nom du prochain point ajouté
+    pub new_point: String,
+    /// Point de test sélectionné
+    pub active: Option<usize>,
+    texture: Option<egui::TextureHandle>,
+    /// Image dont le chargement a échoué, pour ne pas réessayer à chaque image
+    failed_image: Option<String>,
+}
+
+impl BoardView {
+    /// Ouvrir un plan enregistré
+    pub fn open(path: PathBuf) -> Result<Self, String> {
+        let map = BoardMap::load(&path)?;
+        Ok(Self {
+            map,
+            path: Some(path),
+            ..Self::default()
+        })
+    }
+
+    /// Nouveau plan vide à partir d'une photo
+    pub fn set_image(&mut self, image: String) {
+        self.map.image = image;
+        self.texture = None;
+        self.failed_image = None;
+    }
+
+    /// Texture de la photo, chargée au premier affichage
+    pub fn texture(&mut self, ctx: &egui::Context) -> Result<Option<egui::TextureHandle>, String> {
+        if self.map.image.is_empty() || self.failed_image.as_ref() == Some(&self.map.image) {
+            return Ok(None);
+        }
+        if self.texture.is_none() {
+            match load_image(&self.map.image) {
+                Ok(image) => {
+                    self.texture =
+                        Some(ctx.load_texture("board", image, egui::TextureOptions::LINEAR));
+                }
+                Err(e) => {
+                    self.failed_image = Some(self.map.image.clone());
+                    return Err(e);
+                }
+            }
+        }
+        Ok(self.texture.clone())
+    }
+}
+
+/// Charger une image (PNG, JPEG…) pour l'afficher dans egui
+fn load_image(path: &str) -> Result<egui::ColorImage, String> {
+    let image = image::open(path)
+        .map_err(|e| format!("Impossible d'ouvrir l'image {}: {}", path, e))?
+        .to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(egui::ColorImage::from_rgba_unmultiplied(size, image.as_raw()))
+}
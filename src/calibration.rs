@@ -0,0 +1,60 @@
+// src/calibration.rs
+
+use crate::settings::GridStyle;
+use serde::{Deserialize, Serialize};
+
+/// Conversion des courbes normalisées (±1) en unités physiques.
+/// Les valeurs de pleine échelle correspondent au réglage du boîtier
+/// (tension de test et résistance série).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Calibration {
+    pub enabled: bool,
+    /// Tension correspondant à ±1 (V)
+    pub volts_full_scale: f32,
+    /// Courant correspondant à ±1 (mA)
+    pub milliamps_full_scale: f32,
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            volts_full_scale: 5.0,
+            milliamps_full_scale: 20.0,
+        }
+    }
+}
+
+impl Calibration {
+    /// Graduations de l'axe tension : (position dans [-1, 1], libellé)
+    pub fn voltage_ticks(&self, grid: &GridStyle) -> Vec<(f32, String)> {
+        ticks(grid, self.volts_full_scale, "V")
+    }
+
+    /// Graduations de l'axe courant : (position dans [-1, 1], libellé)
+    pub fn current_ticks(&self, grid: &GridStyle) -> Vec<(f32, String)> {
+        ticks(grid, self.milliamps_full_scale, "mA")
+    }
+}
+
+/// Une graduation sur environ cinq divisions principales, hors origine
+fn ticks(grid: &GridStyle, full_scale: f32, unit: &str) -> Vec<(f32, String)> {
+    let n = grid.divisions.max(1) as i32;
+    let step = ((n + 4) / 5).max(1);
+
+    (-n..=n)
+        .filter(|i| *i != 0 && i % step == 0)
+        .map(|i| {
+            let pos = i as f32 / n as f32;
+            (pos, format_value(pos * full_scale, unit))
+        })
+        .collect()
+}
+
+/// Formater une valeur signée sans décimales inutiles (ex: "+2.5 V", "-20 mA")
+pub fn format_value(value: f32, unit: &str) -> String {
+    let text = format!("{:+.2}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    format!("{} {}", text, unit)
+}
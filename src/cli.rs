@@ -0,0 +1,1336 @@
+// src/cli.rs
+
+use crate::analysis::{loop_area, loop_area_ratio, Analysis};
+use crate::backend::{
+    acquire_curves, check_capture, hex_line, list_devices, load_capture_curves, open_device,
+    read_raw_reports, split_curves, Command, CurveAssembler, DeviceSetting, HidBackend, Playback,
+};
+use crate::comparison_report::{
+    overall_line, save_comparison_report, verdict_text, ChannelDeviation, ReportFormat,
+};
+use crate::csv_export::{save_curves_as_csv, save_sweeps_as_csv};
+use crate::curve::{range, CurveData, DualCurveData};
+use crate::html_export::{save_batch_index, IndexRow};
+use crate::i18n::{self, tr, tr_args};
+use crate::image_export::{
+    save_curve, save_dual_curves, save_overlay_curves, ImageFormat, ImageText,
+};
+use crate::json_export::pretty;
+use crate::json_import::load_curves_from_json;
+use crate::junit::{save_junit, TestCase};
+use crate::library::{match_score, Library, LibraryEntry};
+use crate::report::{self, image_caption, png_metadata, save_report};
+use crate::results_db::{ResultsDb, TestRecord};
+use crate::session::Session;
+use crate::settings::{DisplayMode, Override, Settings};
+use clap::{Subcommand, ValueEnum};
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+/// Contenu listé par la sous-commande `list`
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum ListKind {
+    /// Boîtiers CT220S connectés
+    Devices,
+    /// Entrées de la bibliothèque de signatures
+    Signatures,
+    /// Styles d'export enregistrés
+    Styles,
+}
+
+/// Actions de la sous-commande `config`
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Afficher la configuration effective, avec l'origine des valeurs
+    /// remplacées
+    Show,
+}
+
+/// Tracés produits par `convert`, à la place du mode d'affichage enregistré
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum ConvertMode {
+    /// CH0 seul
+    Ch0,
+    /// CH1 seul
+    Ch1,
+    /// Deux canaux superposés
+    Overlay,
+    /// Deux canaux côte à côte
+    SideBySide,
+}
+
+impl ConvertMode {
+    fn display_mode(self) -> DisplayMode {
+        match self {
+            ConvertMode::Ch0 => DisplayMode::SingleCh0,
+            ConvertMode::Ch1 => DisplayMode::SingleCh1,
+            ConvertMode::Overlay => DisplayMode::DualOverlay,
+            ConvertMode::SideBySide => DisplayMode::DualSideBySide,
+        }
+    }
+}
+
+/// Canaux affichés par `--channel`
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum ChannelChoice {
+    #[value(name = "0")]
+    Ch0,
+    #[value(name = "1")]
+    Ch1,
+    Both,
+}
+
+/// Relecture et affichage imposés en ligne de commande, pour les démonstrations
+/// et les captures d'écran (interface graphique ou texte)
+#[derive(Debug, Clone, Default)]
+pub struct ViewOptions {
+    pub speed: Option<f32>,
+    /// Relecture arrêtée à la fin du fichier au lieu de reprendre au début
+    pub once: bool,
+    /// Balayage affiché au chargement, à partir de 1 ; relecture arrêtée dessus
+    pub start_at: Option<usize>,
+    pub channel: Option<ChannelChoice>,
+}
+
+impl ViewOptions {
+    /// Appliquer au mode d'affichage et à la relecture d'un onglet, avant le
+    /// lancement de son lecteur. Le mode imposé par `--channel` est retenu
+    /// comme un `--set` : l'enregistrement des préférences garde celui du
+    /// fichier
+    pub fn apply(&self, session: &Session, settings: &mut Settings) {
+        if let Some(channel) = self.channel {
+            settings.display_mode = match (channel, settings.display_mode) {
+                (ChannelChoice::Ch0, _) => DisplayMode::SingleCh0,
+                (ChannelChoice::Ch1, _) => DisplayMode::SingleCh1,
+                // Deux canaux : côte à côte si c'est le mode enregistré
+                (ChannelChoice::Both, DisplayMode::DualSideBySide) => DisplayMode::DualSideBySide,
+                (ChannelChoice::Both, _) => DisplayMode::DualOverlay,
+            };
+            settings.overrides.push(Override {
+                key: "display_mode".to_string(),
+                value: format!("{:?}", settings.display_mode),
+                source: "--channel".to_string(),
+            });
+        }
+        let mut playback = session.playback.lock().unwrap();
+        if let Some(speed) = self.speed {
+            playback.speed = speed;
+        }
+        playback.looping = !self.once;
+        if let Some(start_at) = self.start_at {
+            playback.start = start_at.saturating_sub(1);
+            playback.playing = false;
+        }
+    }
+}
+
+/// Vitesse de relecture, dans `Playback::SPEED_RANGE`
+pub fn parse_speed(text: &str) -> Result<f32, String> {
+    let speed: f32 = text
+        .trim()
+        .parse()
+        .map_err(|_| format!("Vitesse invalide: {}", text))?;
+    if !Playback::SPEED_RANGE.contains(&speed) {
+        let (min, max) = (Playback::SPEED_RANGE.start(), Playback::SPEED_RANGE.end());
+        return Err(format!("Vitesse hors de {} à {}: {}", min, max, text));
+    }
+    Ok(speed)
+}
+
+/// Capture chargée pour un export sans interface : une courbe de chaque
+/// canal, dans un onglet figé nommé d'après le fichier
+struct Capture {
+    data: DualCurveData,
+    channels: Vec<u8>,
+    session: Session,
+}
+
+impl Capture {
+    /// Acquérir un balayage de chaque canal sur le boîtier
+    fn acquire(settings: &Settings) -> Result<Self, String> {
+        let device = open_device()?;
+        // Sur la sortie d'erreur : la sortie standard peut être du JSON
+        eprintln!("{}", tr("acquire-running"));
+        let mut data = DualCurveData::new();
+        for curve in acquire_curves(&device, ACQUIRE_TIMEOUT)? {
+            data.store(curve);
+        }
+        Ok(Self::new(data, "CT220S".to_string(), settings))
+    }
+
+    /// Charger le balayage `sweep` (à partir de 1) de chaque canal, le
+    /// dernier si `None`
+    fn load(path: &str, settings: &Settings, sweep: Option<usize>) -> Result<Self, String> {
+        let mut data = DualCurveData::new();
+        let mut counts = [0usize; 2];
+        for curve in load_capture_curves(path)? {
+            let count = &mut counts[curve.channel.min(1) as usize];
+            *count += 1;
+            if sweep.is_none_or(|n| n == *count) {
+                data.store(curve);
+            }
+        }
+        let channels: Vec<u8> = [0, 1]
+            .into_iter()
+            .filter(|&c| data.channel(c).is_some())
+            .collect();
+        if channels.is_empty() {
+            return match sweep {
+                Some(n) => Err(format!("Pas de balayage {} dans {}", n, path)),
+                None => Err(format!("Aucune courbe dans {}", path)),
+            };
+        }
+        let name = Path::new(path)
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.to_string());
+        Ok(Self::new(data, name, settings))
+    }
+
+    fn new(data: DualCurveData, name: String, settings: &Settings) -> Self {
+        let channels = [0, 1]
+            .into_iter()
+            .filter(|&c| data.channel(c).is_some())
+            .collect();
+        let session = Session::snapshot(name, data.clone(), settings);
+        Self {
+            data,
+            channels,
+            session,
+        }
+    }
+}
+
+/// Référence d'un test : une courbe pour tous les canaux (signature de la
+/// bibliothèque, export JSON d'un seul canal) ou une par canal
+pub struct Reference {
+    pub name: String,
+    pub curves: Vec<CurveData>,
+}
+
+impl Reference {
+    pub fn for_channel(&self, channel: u8) -> Option<&CurveData> {
+        match &self.curves[..] {
+            [curve] => Some(curve),
+            curves => curves.iter().find(|curve| curve.channel == channel),
+        }
+    }
+
+    /// Entrée de bibliothèque équivalente, pour le verdict du rapport PDF ;
+    /// `None` si la référence diffère selon le canal
+    fn entry(&self) -> Option<LibraryEntry> {
+        match &self.curves[..] {
+            [curve] => Some(LibraryEntry::new(&self.name, curve.clone())),
+            _ => None,
+        }
+    }
+}
+
+/// Écart toléré d'un test, en pourcentage (`5%` ou `5`)
+pub fn parse_tolerance(text: &str) -> Result<f32, String> {
+    let value: f32 = text
+        .trim()
+        .trim_end_matches('%')
+        .trim()
+        .parse()
+        .map_err(|_| format!("Tolérance invalide: {}", text))?;
+    if !(0.0..=100.0).contains(&value) {
+        return Err(format!("Tolérance hors de 0 à 100 %: {}", text));
+    }
+    Ok(value)
+}
+
+/// Options de la sous-commande `test`
+pub struct TestOptions<'a> {
+    /// Fichier de capture ; acquisition sur le boîtier si `None`
+    pub capture: Option<&'a str>,
+    /// Entrée de bibliothèque servant de référence
+    pub signature: Option<&'a str>,
+    /// Export JSON servant de référence, canal par canal
+    pub reference: Option<&'a Path>,
+    /// Écart toléré (%) : le seuil PASS/FAIL devient 100 - tolérance
+    pub tolerance: Option<f32>,
+    pub serial: &'a str,
+    pub junit: Option<&'a Path>,
+    pub report: Option<&'a Path>,
+    /// Verdicts en JSON
+    pub json: bool,
+}
+
+/// Délai d'acquisition d'un balayage de chaque canal sur le boîtier
+pub const ACQUIRE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Préférences enregistrées, langue appliquée
+pub fn load_settings() -> Settings {
+    let settings = Settings::load();
+    i18n::set_language(settings.language);
+    settings
+}
+
+/// Entrée de bibliothèque nommée `name`
+fn find_signature<'a>(library: &'a Library, name: &str) -> Result<&'a LibraryEntry, String> {
+    library
+        .entries
+        .iter()
+        .find(|entry| entry.name == name)
+        .ok_or_else(|| format!("Signature inconnue: {}", name))
+}
+
+/// Comparer chaque canal d'une capture, ou d'une acquisition sur le boîtier,
+/// à une signature de la bibliothèque ou à un export JSON : score affiché,
+/// résultats en JUnit XML (`junit`) et dans la base de résultats si elle est
+/// activée ; `report` reçoit le rapport PDF, avec le verdict s'il y a une
+/// référence. Verdicts en JSON si `options.json`. Renvoie `true` si tous les
+/// canaux passent
+pub fn test(options: &TestOptions) -> Result<bool, String> {
+    let mut settings = load_settings();
+    if let Some(tolerance) = options.tolerance {
+        settings.analysis.pass_threshold = 100.0 - tolerance;
+    }
+    let capture = match options.capture {
+        Some(path) => Capture::load(path, &settings, None)?,
+        None => Capture::acquire(&settings)?,
+    };
+    let reference = match (options.signature, options.reference) {
+        (Some(name), _) => {
+            let library = Library::load();
+            let entry = find_signature(&library, name)?;
+            Some(Reference {
+                name: entry.name.clone(),
+                curves: vec![entry.curve.clone()],
+            })
+        }
+        (None, Some(path)) => Some(Reference {
+            name: path
+                .file_stem()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            curves: load_curves_from_json(path)?,
+        }),
+        (None, None) => None,
+    };
+    if let Some(output) = options.report {
+        let (session, data) = (&capture.session, &capture.data);
+        let entry = reference.as_ref().and_then(Reference::entry);
+        save_report(session, data, &capture.channels, &settings, entry.as_ref(), output)?;
+    }
+    let Some(reference) = reference else {
+        if options.json {
+            println!("{}", pretty(&test_json(None, 0.0, &[])));
+        }
+        return Ok(true);
+    };
+
+    let threshold = settings.analysis.pass_threshold;
+    let classname = capture.session.snapshot_name.clone().unwrap_or_default();
+    // Canaux de la capture ayant une courbe de référence
+    let (curves, cases): (Vec<&CurveData>, Vec<TestCase>) = capture
+        .channels
+        .iter()
+        .filter_map(|&c| capture.data.channel(c))
+        .filter_map(|curve| {
+            let score = match_score(curve, reference.for_channel(curve.channel)?);
+            let case = TestCase {
+                name: format!("{} CH{}", reference.name, curve.channel),
+                classname: classname.clone(),
+                score,
+                pass: score >= threshold,
+                threshold,
+                time: 0.0,
+            };
+            Some((curve, case))
+        })
+        .unzip();
+    if cases.is_empty() {
+        return Err(format!("Aucun canal de la capture n'a de référence dans {}", reference.name));
+    }
+    if options.json {
+        let channels: Vec<u8> = curves.iter().map(|curve| curve.channel).collect();
+        let cases: Vec<(u8, &TestCase)> = channels.into_iter().zip(&cases).collect();
+        println!("{}", pretty(&test_json(Some(&reference.name), threshold, &cases)));
+    } else {
+        for case in &cases {
+            let args = [
+                ("name", case.name.as_str().into()),
+                ("result", verdict_text(case.pass).into()),
+                ("score", format!("{:.1}", case.score).into()),
+            ];
+            println!("{}", tr_args("test-verdict", &args));
+        }
+    }
+    if let Some(output) = options.junit {
+        save_junit(&classname, &cases, threshold, output)?;
+    }
+
+    if settings.log_results {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let device = TestRecord::settings_text(&settings.device, &settings);
+        let records: Vec<TestRecord> = curves
+            .iter()
+            .zip(&cases)
+            .map(|(curve, case)| TestRecord {
+                time,
+                serial: options.serial.to_string(),
+                test_point: reference.name.clone(),
+                channel: curve.channel,
+                settings: device.clone(),
+                score: case.score,
+                pass: case.pass,
+                curve: (*curve).clone(),
+            })
+            .collect();
+        // Le verdict est déjà affiché : un échec d'enregistrement ne le change pas
+        if let Err(e) = ResultsDb::open().and_then(|mut db| db.record(&records, &settings)) {
+            warn!("{}", e);
+        }
+    }
+    Ok(cases.iter().all(|case| case.pass))
+}
+
+/// Verdicts de `test --json`
+#[derive(Serialize)]
+struct TestJson<'a> {
+    reference: Option<&'a str>,
+    threshold: Option<f32>,
+    cases: Vec<CaseJson<'a>>,
+    pass: bool,
+}
+
+/// Verdict d'un canal
+#[derive(Serialize)]
+struct CaseJson<'a> {
+    name: &'a str,
+    channel: u8,
+    score: f32,
+    pass: bool,
+}
+
+/// Verdicts de `test --json` : un cas par canal comparé ; sans référence,
+/// aucun cas et un verdict positif
+fn test_json<'a>(
+    reference: Option<&'a str>,
+    threshold: f32,
+    cases: &[(u8, &'a TestCase)],
+) -> TestJson<'a> {
+    TestJson {
+        reference,
+        threshold: reference.map(|_| threshold),
+        cases: cases
+            .iter()
+            .map(|&(channel, case)| CaseJson {
+                name: &case.name,
+                channel,
+                score: case.score,
+                pass: case.pass,
+            })
+            .collect(),
+        pass: cases.iter().all(|(_, case)| case.pass),
+    }
+}
+
+/// Options de `convert` remplaçant les préférences enregistrées
+pub struct ConvertOptions<'a> {
+    pub mode: Option<ConvertMode>,
+    /// Balayage exporté (à partir de 1) ; le dernier si `None`
+    pub sweep: Option<usize>,
+    pub style: Option<&'a str>,
+    pub size: Option<u32>,
+    pub scale: Option<f32>,
+}
+
+/// Image d'une capture, sans interface ni affichage (serveurs, traitements
+/// par lots) ; le format suit l'extension du fichier. Le mode d'affichage
+/// enregistré et les réglages d'export sont ceux des préférences, sauf
+/// indication contraire dans `options`. Fichiers produits en JSON si `json`
+pub fn export(
+    capture: &str,
+    output: &Path,
+    options: &ConvertOptions,
+    json: bool,
+) -> Result<(), String> {
+    let settings = convert_settings(options)?;
+    let loaded = Capture::load(capture, &settings, options.sweep)?;
+    render(&loaded, &settings, output)?;
+    if json {
+        #[derive(Serialize)]
+        struct ExportJson<'a> {
+            capture: &'a str,
+            output: String,
+        }
+        let output = output.display().to_string();
+        println!("{}", pretty(&ExportJson { capture, output }));
+    }
+    Ok(())
+}
+
+/// Image (png, svg ou pdf) des dernières courbes de chaque canal de
+/// `curves`, selon le mode d'affichage enregistré
+pub fn export_curves(
+    curves: &[CurveData],
+    settings: &Settings,
+    output: &Path,
+) -> Result<(), String> {
+    let mut data = DualCurveData::new();
+    for curve in curves {
+        data.store(curve.clone());
+    }
+    let capture = Capture::new(data, "CT220S".to_string(), settings);
+    if capture.channels.is_empty() {
+        return Err("Aucune courbe à exporter".to_string());
+    }
+    render(&capture, settings, output)
+}
+
+/// Préférences de `convert`, précisées par `options`
+fn convert_settings(options: &ConvertOptions) -> Result<Settings, String> {
+    let mut settings = load_settings();
+    if let Some(mode) = options.mode {
+        settings.display_mode = mode.display_mode();
+    }
+    let (style, size, scale) = (options.style, options.size, options.scale);
+    // Style d'abord : --size et --scale le précisent
+    if let Some(style) = style {
+        settings.use_export_style(style)?;
+    }
+    if let Some(size) = size {
+        settings.export_size = size;
+    }
+    if let Some(scale) = scale {
+        settings.export_scale = scale;
+    }
+    Ok(settings)
+}
+
+/// Enregistrer l'image d'une capture chargée, selon le mode d'affichage
+fn render(capture: &Capture, settings: &Settings, output: &Path) -> Result<(), String> {
+    let format = ImageFormat::from_path(output)
+        .ok_or_else(|| format!("Format d'image non reconnu: {}", output.display()))?;
+    let (data, session) = (&capture.data, &capture.session);
+
+    let single = match settings.display_mode {
+        DisplayMode::SingleCh0 => data.channel(0),
+        DisplayMode::SingleCh1 => data.channel(1),
+        DisplayMode::DualOverlay | DisplayMode::DualSideBySide => None,
+    };
+    let channels = single.map_or(capture.channels.clone(), |curve| vec![curve.channel]);
+    let caption = if settings.export_measurements {
+        image_caption(session, data, &channels, settings, |_| None)
+    } else {
+        Vec::new()
+    };
+    let metadata = png_metadata(session, data, &channels, settings, |_| None);
+    let text = ImageText {
+        notes: &session.notes,
+        caption: &caption,
+        metadata: &metadata,
+    };
+    let annotations = &session.annotations;
+    match (settings.display_mode, single) {
+        (_, Some(curve)) => save_curve(curve, settings, annotations, &text, format, output),
+        (DisplayMode::DualOverlay, _) => {
+            save_overlay_curves(data, settings, annotations, &text, format, output)
+        }
+        _ => save_dual_curves(data, settings, annotations, &text, format, output),
+    }
+}
+
+/// Extensions des fichiers de capture convertis par lots
+const CAPTURE_EXTENSIONS: [&str; 3] = ["txt", "hex", "log"];
+
+/// Convertir toutes les captures de `dir` en image `image_format` (png par
+/// défaut) et en CSV dans `out_dir` (`dir` par défaut), sur `jobs` threads
+/// (un par cœur par défaut), puis écrire `index.html` : mesures, vignette et
+/// fichiers produits de chaque capture. Bilan en JSON si `json`
+pub fn batch(
+    dir: &Path,
+    out_dir: Option<&Path>,
+    image_format: Option<ImageFormat>,
+    jobs: Option<usize>,
+    options: &ConvertOptions,
+    json: bool,
+) -> Result<(), String> {
+    let settings = convert_settings(options)?;
+    let out_dir = out_dir.unwrap_or(dir);
+    fs::create_dir_all(out_dir)
+        .map_err(|e| format!("Impossible de créer {}: {}", out_dir.display(), e))?;
+    let mut captures: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| format!("Impossible de lire {}: {}", dir.display(), e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+            path.is_file() && CAPTURE_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+        })
+        .collect();
+    captures.sort();
+    if captures.is_empty() {
+        return Err(format!("Aucune capture dans {}", dir.display()));
+    }
+
+    let image_format = image_format.unwrap_or(ImageFormat::Png);
+    let jobs = jobs
+        .or_else(|| thread::available_parallelism().ok().map(|n| n.get()))
+        .unwrap_or(1)
+        .clamp(1, captures.len());
+    // Chaque thread prend la capture suivante ; une ligne d'index par capture
+    let next = AtomicUsize::new(0);
+    let rows: Mutex<Vec<Option<IndexRow>>> =
+        Mutex::new(captures.iter().map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = captures.get(i) else {
+                    break;
+                };
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let stem = path
+                    .file_stem()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let files =
+                    vec![format!("{}.{}", stem, image_format.extension()), format!("{}.csv", stem)];
+                let summary = convert_one(path, out_dir, &files, &settings, options.sweep);
+                if let Err(e) = &summary {
+                    let args = [("name", name.as_str().into()), ("error", e.as_str().into())];
+                    eprintln!("{}", tr_args("batch-capture-error", &args));
+                }
+                let files = if summary.is_ok() { files } else { Vec::new() };
+                rows.lock().unwrap()[i] = Some(IndexRow {
+                    name,
+                    summary,
+                    files,
+                });
+            });
+        }
+    });
+    let rows: Vec<IndexRow> = rows.into_inner().unwrap().into_iter().flatten().collect();
+
+    let title = tr_args("batch-title", &[("dir", dir.display().to_string().into())]);
+    let index = out_dir.join("index.html");
+    save_batch_index(&title, &rows, &index)?;
+    let failed = rows.iter().filter(|row| row.summary.is_err()).count();
+    if json {
+        println!("{}", pretty(&batch_json(&rows, failed, &index)));
+    } else {
+        let args = [
+            ("converted", (rows.len() - failed).into()),
+            ("failed", failed.into()),
+        ];
+        println!("{}", tr_args("batch-done", &args));
+    }
+    if failed > 0 {
+        return Err(format!("{} capture(s) non convertie(s)", failed));
+    }
+    Ok(())
+}
+
+/// Bilan de `convert --batch --json`
+#[derive(Serialize)]
+struct BatchJson<'a> {
+    converted: usize,
+    failed: usize,
+    index: String,
+    captures: Vec<BatchCaptureJson<'a>>,
+}
+
+/// Fichiers produits ou erreur d'une capture
+#[derive(Serialize)]
+struct BatchCaptureJson<'a> {
+    capture: &'a str,
+    files: &'a [String],
+    summary: Option<&'a str>,
+    error: Option<&'a str>,
+}
+
+/// Bilan de `convert --batch --json` : fichiers produits ou erreur de chaque
+/// capture
+fn batch_json<'a>(rows: &'a [IndexRow], failed: usize, index: &Path) -> BatchJson<'a> {
+    BatchJson {
+        converted: rows.len() - failed,
+        failed,
+        index: index.display().to_string(),
+        captures: rows
+            .iter()
+            .map(|row| BatchCaptureJson {
+                capture: &row.name,
+                files: &row.files,
+                summary: row.summary.as_deref().ok(),
+                error: row.summary.as_ref().err().map(String::as_str),
+            })
+            .collect(),
+    }
+}
+
+/// Écrire l'image et le CSV (`files`) d'une capture de la conversion par
+/// lots ; renvoie le résumé des mesures
+fn convert_one(
+    path: &Path,
+    out_dir: &Path,
+    files: &[String],
+    settings: &Settings,
+    sweep: Option<usize>,
+) -> Result<String, String> {
+    let capture = Capture::load(&path.to_string_lossy(), settings, sweep)?;
+    let (session, data, channels) = (&capture.session, &capture.data, &capture.channels);
+    render(&capture, settings, &out_dir.join(&files[0]))?;
+    let header = report::header(session, data, channels, settings);
+    save_curves_as_csv(data, channels, settings, &header, &out_dir.join(&files[1]))?;
+    Ok(report::fields(session, data, channels, settings, None)["summary"].clone())
+}
+
+/// Format d'image saisi en ligne de commande (png, svg ou pdf)
+pub fn parse_image_format(text: &str) -> Result<ImageFormat, String> {
+    ImageFormat::ALL
+        .into_iter()
+        .find(|format| format.extension().eq_ignore_ascii_case(text.trim()))
+        .ok_or_else(|| format!("Format d'image inconnu: {} (png, svg, pdf)", text))
+}
+
+/// Comparer deux captures canal par canal, `reference` servant de référence
+/// (balayage `sweep` de chacune, le dernier par défaut) : score, statistiques
+/// d'écart et verdict affichés ; `output` reçoit le rapport de comparaison
+/// (courbes superposées et écart point par point ; png, pdf ou html).
+/// Renvoie `true` si tous les canaux comparés passent le seuil PASS/FAIL.
+/// Résultats en JSON si `json`
+pub fn compare(
+    capture: &str,
+    reference: &str,
+    sweep: Option<usize>,
+    output: Option<&Path>,
+    json: bool,
+) -> Result<bool, String> {
+    let settings = load_settings();
+    let format = match output {
+        Some(path) => Some(
+            ReportFormat::from_path(path)
+                .ok_or_else(|| format!("Format de rapport non reconnu: {}", path.display()))?,
+        ),
+        None => None,
+    };
+    let live = Capture::load(capture, &settings, sweep)?;
+    let golden = Capture::load(reference, &settings, sweep)?;
+    let name = golden.session.snapshot_name.clone().unwrap_or_default();
+
+    let threshold = settings.analysis.pass_threshold;
+    let deviations: Vec<ChannelDeviation> = live
+        .channels
+        .iter()
+        .filter_map(|&c| Some((live.data.channel(c)?, golden.data.channel(c)?)))
+        .map(|(curve, reference)| ChannelDeviation::new(curve, reference, &name, threshold))
+        .collect();
+    if deviations.is_empty() {
+        return Err(format!("Aucun canal commun à {} et {}", capture, reference));
+    }
+    if json {
+        #[derive(Serialize)]
+        struct CompareJson<'a> {
+            capture: &'a str,
+            reference: &'a str,
+            threshold: f32,
+            channels: Vec<DeviationJson>,
+            pass: bool,
+        }
+        #[derive(Serialize)]
+        struct DeviationJson {
+            channel: u8,
+            score: f32,
+            pass: bool,
+            mean: f32,
+            rms: f32,
+            max: f32,
+            max_point: usize,
+        }
+        let channels = deviations
+            .iter()
+            .map(|deviation| {
+                let (max_point, max) = deviation.max();
+                DeviationJson {
+                    channel: deviation.live.channel,
+                    score: deviation.score,
+                    pass: deviation.pass,
+                    mean: deviation.mean(),
+                    rms: deviation.rms(),
+                    max,
+                    max_point,
+                }
+            })
+            .collect();
+        let result = CompareJson {
+            capture,
+            reference,
+            threshold,
+            channels,
+            pass: deviations.iter().all(|deviation| deviation.pass),
+        };
+        println!("{}", pretty(&result));
+    } else {
+        for deviation in &deviations {
+            println!("{}", deviation.verdict_line());
+            println!("  {}", deviation.stats_line());
+        }
+        println!("{}", overall_line(&deviations));
+    }
+
+    if let (Some(path), Some(format)) = (output, format) {
+        let (session, data) = (&live.session, &live.data);
+        let header = report::header(session, data, &live.channels, &settings);
+        let fields = report::fields(session, data, &live.channels, &settings, None);
+        save_comparison_report(&deviations, &header, fields, &settings, format, path)?;
+    }
+    Ok(deviations.iter().all(|deviation| deviation.pass))
+}
+
+/// Afficher les mesures de chaque courbe d'une capture (balayages numérotés
+/// par canal, à partir de 1) : nombre de points, plages de tension et de
+/// courant, aire de la boucle et classification ; en JSON si `json`
+pub fn stats(capture: &str, json: bool) -> Result<(), String> {
+    let settings = load_settings();
+    print_stats(&load_capture_curves(capture)?, &settings, json);
+    Ok(())
+}
+
+/// Afficher les mesures d'une suite de courbes (voir `stats`)
+pub fn print_stats(curves: &[CurveData], settings: &Settings, json: bool) {
+    let calibration = &settings.calibration;
+    let mut counts = [0usize; 2];
+    let mut rows = Vec::new();
+    for curve in curves {
+        let sweep = &mut counts[curve.channel.min(1) as usize];
+        *sweep += 1;
+        let analysis = Analysis::of(curve);
+        let (v_min, v_max) = range(&curve.voltage).unwrap_or_default();
+        let (i_min, i_max) = range(&curve.current).unwrap_or_default();
+        let component = format!("{:?}", analysis.component).to_lowercase();
+        if !json {
+            let mut line = tr_args(
+                "stats-sweep",
+                &[
+                    ("sweep", (*sweep).into()),
+                    ("channel", curve.channel.into()),
+                    ("points", curve.voltage.len().into()),
+                    ("vmin", format!("{:.3}", v_min).into()),
+                    ("vmax", format!("{:.3}", v_max).into()),
+                    ("imin", format!("{:.3}", i_min).into()),
+                    ("imax", format!("{:.3}", i_max).into()),
+                    ("area", format!("{:.4}", loop_area(curve)).into()),
+                    ("ratio", format!("{:.1}", loop_area_ratio(curve) * 100.0).into()),
+                    ("component", component.into()),
+                    ("resistance", analysis.format_resistance(calibration).into()),
+                ],
+            );
+            if let Some(vf) = analysis.forward_voltage {
+                let vf = tr_args("stats-forward-voltage", &[("vf", format!("{:.3}", vf).into())]);
+                line = format!("{}, {}", line, vf);
+            }
+            println!("{}", line);
+            continue;
+        }
+        rows.push(StatsJson {
+            sweep: *sweep,
+            channel: curve.channel,
+            points: curve.voltage.len(),
+            voltage_min: v_min,
+            voltage_max: v_max,
+            current_min: i_min,
+            current_max: i_max,
+            loop_area: loop_area(curve),
+            loop_area_ratio: loop_area_ratio(curve),
+            component,
+            slope: analysis.slope,
+            resistance: analysis.resistance(),
+            resistance_text: analysis.format_resistance(calibration),
+            forward_voltage: analysis.forward_voltage,
+        });
+    }
+    if json {
+        println!("{}", pretty(&rows));
+    }
+}
+
+/// Mesures d'une courbe de `stats --json` ; les valeurs non finies
+/// deviennent null
+#[derive(Serialize)]
+struct StatsJson {
+    sweep: usize,
+    channel: u8,
+    points: usize,
+    voltage_min: f32,
+    voltage_max: f32,
+    current_min: f32,
+    current_max: f32,
+    loop_area: f32,
+    loop_area_ratio: f32,
+    component: String,
+    slope: f32,
+    resistance: Option<f32>,
+    resistance_text: String,
+    forward_voltage: Option<f32>,
+}
+
+/// Vérifier un fichier de capture et afficher chaque ligne fautive, puis le
+/// nombre de courbes complètes, en JSON si `json`. Renvoie `true` si la
+/// capture est valide
+pub fn validate(capture: &str, json: bool) -> Result<bool, String> {
+    load_settings();
+    let check = check_capture(capture)?;
+    if json {
+        #[derive(Serialize)]
+        struct ValidateJson<'a> {
+            capture: &'a str,
+            reports: usize,
+            curves: [usize; 2],
+            issues: Vec<IssueJson<'a>>,
+            valid: bool,
+        }
+        #[derive(Serialize)]
+        struct IssueJson<'a> {
+            line: usize,
+            message: &'a str,
+        }
+        let result = ValidateJson {
+            capture,
+            reports: check.reports,
+            curves: check.curves,
+            issues: check
+                .issues
+                .iter()
+                .map(|issue| IssueJson {
+                    line: issue.line,
+                    message: &issue.message,
+                })
+                .collect(),
+            valid: check.issues.is_empty(),
+        };
+        println!("{}", pretty(&result));
+        return Ok(check.issues.is_empty());
+    }
+    for issue in &check.issues {
+        println!("{}:{}: {}", capture, issue.line, issue.message);
+    }
+    let summary = tr_args(
+        "validate-summary",
+        &[
+            ("reports", check.reports.into()),
+            ("curves", (check.curves[0] + check.curves[1]).into()),
+            ("ch0", check.curves[0].into()),
+            ("ch1", check.curves[1].into()),
+            ("issues", check.issues.len().into()),
+        ],
+    );
+    println!("{}", summary);
+    Ok(check.issues.is_empty())
+}
+
+/// Préférence imposée par `--set clé=valeur`
+pub fn parse_override(text: &str) -> Result<(String, String), String> {
+    let (key, value) = text
+        .split_once('=')
+        .ok_or_else(|| format!("clé=valeur attendu: {}", text))?;
+    Ok((key.trim().to_string(), value.to_string()))
+}
+
+/// Configuration effective : fichier, puis variables `CT220S_*`, puis `--set` ;
+/// en JSON si `json`
+pub fn config(command: &ConfigCommand, json: bool) -> Result<(), String> {
+    match command {
+        ConfigCommand::Show if json => {
+            #[derive(Serialize)]
+            struct ConfigJson<'a> {
+                file: Option<String>,
+                overrides: &'a [Override],
+                settings: &'a Settings,
+            }
+            let settings = load_settings();
+            let result = ConfigJson {
+                file: Settings::path().map(|path| path.display().to_string()),
+                overrides: &settings.overrides,
+                settings: &settings,
+            };
+            println!("{}", pretty(&result));
+        }
+        ConfigCommand::Show => {
+            let settings = load_settings();
+            // En commentaires TOML : la sortie reste un fichier de configuration
+            let file = match Settings::path() {
+                Some(path) => {
+                    let key = match path.exists() {
+                        true => "config-file",
+                        false => "config-file-missing",
+                    };
+                    tr_args(key, &[("path", path.display().to_string().into())])
+                }
+                None => tr("config-no-dir"),
+            };
+            println!("# {}", file);
+            for replaced in &settings.overrides {
+                println!("# {} = {} ({})", replaced.key, replaced.value, replaced.source);
+            }
+            print!("{}", settings.to_toml()?);
+        }
+    }
+    Ok(())
+}
+
+/// Afficher une liste, une ligne par élément (séparateur : tabulation), ou
+/// en tableau JSON d'objets si `json`
+pub fn list(kind: ListKind, json: bool) -> Result<(), String> {
+    #[derive(Serialize)]
+    struct DeviceRow {
+        path: String,
+        serial: String,
+        manufacturer: String,
+        product: String,
+        release: String,
+    }
+    #[derive(Serialize)]
+    struct SignatureRow<'a> {
+        name: &'a str,
+        description: &'a str,
+    }
+    #[derive(Serialize)]
+    struct StyleRow<'a> {
+        name: &'a str,
+        pixels: u32,
+    }
+
+    let rows: Vec<serde_json::Value> = match kind {
+        ListKind::Devices => list_devices()?
+            .into_iter()
+            .map(|device| {
+                serde_json::json!(DeviceRow {
+                    release: device.release_text(),
+                    path: device.path,
+                    serial: device.serial,
+                    manufacturer: device.manufacturer,
+                    product: device.product,
+                })
+            })
+            .collect(),
+        ListKind::Signatures => Library::load()
+            .entries
+            .iter()
+            .map(|entry| {
+                serde_json::json!(SignatureRow {
+                    name: &entry.name,
+                    description: &entry.description,
+                })
+            })
+            .collect(),
+        ListKind::Styles => load_settings()
+            .export_styles
+            .iter()
+            .map(|style| {
+                let pixels = (style.size as f32 * style.scale).round() as u32;
+                serde_json::json!(StyleRow {
+                    name: &style.name,
+                    pixels,
+                })
+            })
+            .collect(),
+    };
+
+    if json {
+        println!("{}", pretty(&rows));
+        return Ok(());
+    }
+    for row in rows {
+        // Colonnes dans l'ordre des champs (`preserve_order`)
+        let values = row.as_object().into_iter().flat_map(|fields| fields.values());
+        let fields: Vec<String> = values
+            .map(|value| match value {
+                serde_json::Value::String(s) if s.is_empty() => "-".to_string(),
+                serde_json::Value::String(s) => s.clone(),
+                value => value.to_string(),
+            })
+            .collect();
+        println!("{}", fields.join("\t"));
+    }
+    Ok(())
+}
+
+/// Réglage envoyé par la sous-commande `cmd`
+#[derive(Subcommand, Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum DeviceCommand {
+    /// Fréquence du signal de test : 10Hz, 100Hz, 500Hz ou 2kHz
+    #[command(alias = "frequency")]
+    SetFreq { value: String },
+    /// Résistance de mesure : 47R, 1K, 10K ou offset
+    #[command(alias = "resistance")]
+    SetRes { value: String },
+    /// Mode de balayage : Simple ou Dual
+    #[command(alias = "mode")]
+    SetMode { value: String },
+    /// Tension de test : 2.5V, 5V, 10V ou 20V
+    #[command(alias = "voltage")]
+    SetVolt { value: String },
+}
+
+impl DeviceCommand {
+    /// Identifiant du réglage dans `DEVICE_SETTINGS` et valeur saisie
+    fn setting(&self) -> (&'static str, &str) {
+        match self {
+            DeviceCommand::SetFreq { value } => ("frequency", value),
+            DeviceCommand::SetRes { value } => ("resistance", value),
+            DeviceCommand::SetMode { value } => ("mode", value),
+            DeviceCommand::SetVolt { value } => ("voltage", value),
+        }
+    }
+}
+
+/// Envoyer un réglage au boîtier, puis confirmer l'option appliquée (en JSON
+/// si `json`)
+pub fn command(command: &DeviceCommand, json: bool) -> Result<(), String> {
+    load_settings();
+    let (cmd, setting, label) = resolve_setting(command)?;
+    HidBackend::new()?.send_cmd(cmd)?;
+    if json {
+        #[derive(Serialize)]
+        struct CommandJson<'a> {
+            setting: &'a str,
+            value: &'a str,
+        }
+        println!("{}", pretty(&CommandJson { setting: setting.id, value: label }));
+    } else {
+        println!("{}", setting_confirmation(setting, label));
+    }
+    Ok(())
+}
+
+/// Commande HID d'un réglage, réglage concerné et libellé de l'option
+pub fn resolve_setting(
+    command: &DeviceCommand,
+) -> Result<(Command, &'static DeviceSetting, &'static str), String> {
+    let (id, value) = command.setting();
+    resolve_option(id, value)
+}
+
+/// Commande HID, réglage et libellé de l'option `value` du réglage `id`
+/// (identifiant de `DEVICE_SETTINGS`)
+pub fn resolve_option(
+    id: &str,
+    value: &str,
+) -> Result<(Command, &'static DeviceSetting, &'static str), String> {
+    let setting = DeviceSetting::find(id).ok_or_else(|| format!("Réglage inconnu: {}", id))?;
+    let label = setting.find_option(value).ok_or_else(|| {
+        let options: Vec<&str> = setting.options.iter().map(|&(label, _)| label).collect();
+        format!("Option inconnue: {} ({})", value, options.join(", "))
+    })?;
+    let cmd = setting
+        .command_for(label)
+        .ok_or_else(|| format!("Option inconnue: {}", label))?;
+    Ok((cmd, setting, label))
+}
+
+/// Message confirmant l'option appliquée d'un réglage
+pub fn setting_confirmation(setting: &DeviceSetting, label: &str) -> String {
+    tr_args("cmd-applied", &[("setting", tr(setting.key).into()), ("value", label.into())])
+}
+
+/// Sortie de `capture --stream`, une ligne par valeur séparée d'espaces
+/// (gnuplot, awk)
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum StreamFormat {
+    /// Une ligne par point : balayage, canal, indice, tension, courant ; une
+    /// ligne vide après chaque courbe
+    Points,
+    /// Une ligne par balayage : mesures de la courbe
+    Metrics,
+}
+
+/// Options de la sous-commande `capture`
+pub struct CaptureOptions<'a> {
+    /// Durée de l'enregistrement ; jusqu'à l'interruption si `None`
+    pub seconds: Option<f32>,
+    /// Fichier de capture produit
+    pub output: Option<&'a Path>,
+    /// CSV des balayages complets
+    pub curves: Option<&'a Path>,
+    /// Courbes affichées sur la sortie standard au fil de la capture
+    pub stream: Option<StreamFormat>,
+    /// Bilan en JSON, messages sur la sortie d'erreur
+    pub json: bool,
+}
+
+/// Enregistrer les rapports bruts du boîtier dans `options.output`, au format
+/// des fichiers de capture (relisible par `--file`, `convert` et `test`).
+/// Chaque rapport est écrit dès sa réception ; `curves` reçoit en plus les
+/// balayages complets en CSV. Avec `stream`, chaque courbe reçue est écrite
+/// sur la sortie standard et les messages passent sur la sortie d'erreur, de
+/// même qu'avec `json` (bilan en JSON à la fin)
+pub fn capture(options: &CaptureOptions) -> Result<(), String> {
+    if let Some(seconds) = options.seconds.filter(|s| !(s.is_finite() && *s > 0.0)) {
+        return Err(format!("Durée invalide: {}", seconds));
+    }
+    if options.json && options.stream.is_some() {
+        return Err("--stream écrit du texte : incompatible avec --json".to_string());
+    }
+    let settings = load_settings();
+    let device = open_device()?;
+    let quiet = options.stream.is_some() || options.json;
+    let status = |message: String| {
+        if quiet {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message);
+        }
+    };
+    let write_error = |path: &Path, e: std::io::Error| {
+        format!("Impossible d'écrire {}: {}", path.display(), e)
+    };
+    let mut file = match options.output {
+        Some(path) => {
+            let mut file = File::create(path).map_err(|e| write_error(path, e))?;
+            let duration = options.seconds.map_or("-".to_string(), |s| format!("{} s", s));
+            writeln!(file, "# Capture CT220S, {}", duration).map_err(|e| write_error(path, e))?;
+            Some((path, file))
+        }
+        None => None,
+    };
+
+    status(match options.seconds {
+        Some(seconds) => tr_args("capture-running-for", &[("seconds", seconds.into())]),
+        None => tr("capture-running"),
+    });
+    let mut stdout = std::io::stdout();
+    if let Some(format) = options.stream {
+        let _ = writeln!(stdout, "{}", stream_header(format));
+    }
+    let mut reports = Vec::new();
+    let mut assembler = CurveAssembler::default();
+    let mut counts = [0usize; 2];
+    let duration = options.seconds.map(Duration::from_secs_f32);
+    let count = read_raw_reports(&device, duration, |report| {
+        if let Some((path, file)) = &mut file {
+            writeln!(file, "{}", hex_line(report)).map_err(|e| write_error(path, e))?;
+        }
+        if options.curves.is_some() {
+            reports.push(report.to_vec());
+        }
+        let Some(format) = options.stream else {
+            return Ok(true);
+        };
+        let Some(curve) = assembler.push(report) else {
+            return Ok(true);
+        };
+        let sweep = &mut counts[curve.channel.min(1) as usize];
+        *sweep += 1;
+        // Sortie fermée (ex. `| head`) : fin de la capture
+        Ok(write_stream(&mut stdout, format, *sweep, &curve).is_ok())
+    })?;
+    status(match options.output {
+        Some(path) => {
+            let args = [
+                ("path", path.display().to_string().into()),
+                ("reports", count.into()),
+            ];
+            tr_args("capture-saved", &args)
+        }
+        None => tr_args("capture-received", &[("reports", count.into())]),
+    });
+
+    if let Some(path) = options.curves {
+        let sweeps: Vec<(usize, CurveData)> =
+            split_curves(&reports)?.into_iter().enumerate().map(|(i, c)| (i + 1, c)).collect();
+        let source = options.output.map_or("CT220S".into(), |p| p.display().to_string());
+        let header = [format!("Capture: {}", source)];
+        save_sweeps_as_csv(&sweeps, &settings, &header, path)?;
+    }
+    if options.json {
+        #[derive(Serialize)]
+        struct CaptureJson {
+            output: Option<String>,
+            curves: Option<String>,
+            reports: usize,
+        }
+        let path = |path: Option<&Path>| path.map(|p| p.display().to_string());
+        let result = CaptureJson {
+            output: path(options.output),
+            curves: path(options.curves),
+            reports: count,
+        };
+        println!("{}", pretty(&result));
+    }
+    Ok(())
+}
+
+/// Ligne de commentaire nommant les colonnes de `capture --stream`
+fn stream_header(format: StreamFormat) -> &'static str {
+    match format {
+        StreamFormat::Points => "# sweep channel index voltage current",
+        StreamFormat::Metrics => {
+            "# sweep channel component slope resistance forward_voltage voltage_span \
+             current_span loop_area"
+        }
+    }
+}
+
+/// Écrire une courbe reçue sur la sortie `capture --stream` (valeurs
+/// normalisées, `nan` si absentes)
+fn write_stream(
+    out: &mut impl Write,
+    format: StreamFormat,
+    sweep: usize,
+    curve: &CurveData,
+) -> std::io::Result<()> {
+    let channel = curve.channel;
+    match format {
+        StreamFormat::Points => {
+            for (index, (v, i)) in curve.voltage.iter().zip(&curve.current).enumerate() {
+                writeln!(out, "{} {} {} {:.6} {:.6}", sweep, channel, index, v, i)?;
+            }
+            writeln!(out)?;
+        }
+        StreamFormat::Metrics => {
+            let analysis = Analysis::of(curve);
+            let metrics = curve.metrics();
+            let optional =
+                |value: Option<f32>| value.map_or("nan".to_string(), |v| format!("{:.6}", v));
+            writeln!(
+                out,
+                "{} {} {} {:.6} {} {} {:.6} {:.6} {:.6}",
+                sweep,
+                channel,
+                format!("{:?}", analysis.component).to_lowercase(),
+                analysis.slope,
+                optional(analysis.resistance()),
+                optional(analysis.forward_voltage),
+                metrics.voltage_span,
+                metrics.current_span,
+                loop_area(curve)
+            )?;
+        }
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_are_split_at_the_first_equal_sign() {
+        assert_eq!(
+            parse_override("plot.connect_points=false").unwrap(),
+            ("plot.connect_points".to_string(), "false".to_string())
+        );
+        assert_eq!(
+            parse_override(" html_template =a=b.html").unwrap(),
+            ("html_template".to_string(), "a=b.html".to_string())
+        );
+        assert!(parse_override("plot.connect_points").is_err());
+    }
+}
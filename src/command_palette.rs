@@ -0,0 +1,122 @@
+// src/command_palette.rs
+
+use crate::i18n::tr;
+use eframe::egui;
+
+/// Palette de commandes (Ctrl+P) avec recherche approximative
+#[derive(Default)]
+pub struct CommandPalette {
+    pub open: bool,
+    query: String,
+    selected: usize,
+}
+
+impl CommandPalette {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    /// Afficher la palette ; renvoie l'index de l'entrée choisie
+    pub fn show(&mut self, ctx: &egui::Context, labels: &[String]) -> Option<usize> {
+        if !self.open {
+            return None;
+        }
+
+        let mut matches: Vec<(i32, usize)> = labels
+            .iter()
+            .enumerate()
+            .filter_map(|(i, label)| fuzzy_score(&self.query, label).map(|score| (score, i)))
+            .collect();
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        let (up, down, enter, escape) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+            )
+        });
+        if down {
+            self.selected += 1;
+        }
+        if up {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        self.selected = self.selected.min(matches.len().saturating_sub(1));
+
+        let mut chosen = if enter {
+            matches.get(self.selected).map(|&(_, i)| i)
+        } else {
+            None
+        };
+
+        egui::Window::new(tr("palette-title"))
+            .collapsible(false)
+            .resizable(false)
+            .title_bar(false)
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 40.0))
+            .fixed_size(egui::vec2(400.0, 0.0))
+            .show(ctx, |ui| {
+                let edit = ui.add(
+                    egui::TextEdit::singleline(&mut self.query)
+                        .hint_text(tr("palette-hint"))
+                        .desired_width(f32::INFINITY),
+                );
+                edit.request_focus();
+                if edit.changed() {
+                    self.selected = 0;
+                }
+
+                ui.separator();
+                egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for (row, &(_, i)) in matches.iter().enumerate() {
+                        let label = ui.selectable_label(row == self.selected, &labels[i]);
+                        if row == self.selected && (up || down) {
+                            label.scroll_to_me(None);
+                        }
+                        if label.clicked() {
+                            chosen = Some(i);
+                        }
+                    }
+                });
+            });
+
+        if chosen.is_some() || escape {
+            self.open = false;
+        }
+        chosen
+    }
+}
+
+/// Score de correspondance approximative : les caractères de la requête doivent
+/// apparaître dans l'ordre ; les suites consécutives et les débuts de mot comptent plus.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let query: Vec<char> = query.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut qi = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for (ti, &c) in text.iter().enumerate() {
+        if qi < query.len() && c == query[qi] {
+            score += 1;
+            if previous_match == Some(ti.wrapping_sub(1)) {
+                score += 5;
+            }
+            if ti == 0 || !text[ti - 1].is_alphanumeric() {
+                score += 3;
+            }
+            previous_match = Some(ti);
+            qi += 1;
+        }
+    }
+
+    (qi == query.len()).then_some(score)
+}
@@ -0,0 +1,72 @@
+// src/compare.rs
+
+use crate::backend::load_capture_curves;
+use crate::curve::CurveData;
+use std::time::{Duration, Instant};
+
+/// Intervalle entre deux positions en lecture synchronisée
+pub const STEP_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Capture chargée en mémoire pour la comparaison
+pub struct CaptureFile {
+    pub path: String,
+    pub curves: Vec<CurveData>,
+}
+
+impl CaptureFile {
+    pub fn load(path: &str) -> Result<Self, String> {
+        Ok(Self {
+            path: path.to_string(),
+            curves: load_capture_curves(path)?,
+        })
+    }
+
+    /// Courbe à la position donnée (la dernière si la capture est plus courte)
+    pub fn curve_at(&self, position: usize) -> Option<&CurveData> {
+        self.curves.get(position.min(self.curves.len().saturating_sub(1)))
+    }
+}
+
+/// Comparaison A/B de deux fichiers de capture, avec une position de lecture commune
+#[derive(Default)]
+pub struct Comparison {
+    pub open: bool,
+    pub a: Option<CaptureFile>,
+    pub b: Option<CaptureFile>,
+    pub position: usize,
+    pub playing: bool,
+    /// Superposer A et B dans un même tracé plutôt que côte à côte
+    pub overlay: bool,
+    last_step: Option<Instant>,
+}
+
+impl Comparison {
+    /// Nombre de positions : celui de la plus longue des deux captures
+    pub fn len(&self) -> usize {
+        [&self.a, &self.b]
+            .iter()
+            .filter_map(|c| c.as_ref().map(|c| c.curves.len()))
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn step(&mut self, delta: i64) {
+        let len = self.len() as i64;
+        if len > 0 {
+            self.position = (self.position as i64 + delta).rem_euclid(len) as usize;
+        }
+    }
+
+    /// Avancer la lecture synchronisée si l'intervalle est écoulé
+    pub fn tick(&mut self) {
+        if !self.playing {
+            self.last_step = None;
+            return;
+        }
+        let now = Instant::now();
+        if self.last_step.is_none_or(|t| now.duration_since(t) >= STEP_INTERVAL) {
+            self.last_step = Some(now);
+            self.step(1);
+        }
+    }
+}
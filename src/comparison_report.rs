@@ -0,0 +1,154 @@
+// src/comparison_report.rs
+
+use crate::curve::CurveData;
+use crate::html_export::save_comparison_as_html;
+use crate::i18n::{tr, tr_args};
+use crate::image_export::{save_comparison, ImageFormat};
+use crate::library::{match_score, nearest_distances};
+use crate::settings::Settings;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Format du rapport de comparaison
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Png,
+    Pdf,
+    Html,
+}
+
+impl ReportFormat {
+    pub const ALL: [ReportFormat; 3] = [ReportFormat::Png, ReportFormat::Pdf, ReportFormat::Html];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Png => "png",
+            ReportFormat::Pdf => "pdf",
+            ReportFormat::Html => "html",
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        ReportFormat::ALL
+            .into_iter()
+            .find(|format| format.extension() == extension)
+    }
+}
+
+/// Comparaison d'un canal à sa référence (entrée de bibliothèque ou
+/// référence figée) : écart point par point, score et verdict
+pub struct ChannelDeviation {
+    pub live: CurveData,
+    pub reference: CurveData,
+    /// Nom de la référence (entrée de bibliothèque)
+    pub reference_name: String,
+    /// Distance de chaque point de la courbe au point le plus proche de la
+    /// référence (unités normalisées)
+    pub distances: Vec<f32>,
+    /// Score de correspondance (%)
+    pub score: f32,
+    pub pass: bool,
+}
+
+impl ChannelDeviation {
+    pub fn new(
+        live: &CurveData,
+        reference: &CurveData,
+        reference_name: &str,
+        pass_threshold: f32,
+    ) -> Self {
+        let score = match_score(live, reference);
+        Self {
+            live: live.clone(),
+            reference: reference.clone(),
+            reference_name: reference_name.to_string(),
+            distances: nearest_distances(live, reference),
+            score,
+            pass: score >= pass_threshold,
+        }
+    }
+
+    pub fn mean(&self) -> f32 {
+        self.distances.iter().sum::<f32>() / self.distances.len().max(1) as f32
+    }
+
+    /// Écart quadratique moyen
+    pub fn rms(&self) -> f32 {
+        let squares: f32 = self.distances.iter().map(|d| d * d).sum();
+        (squares / self.distances.len().max(1) as f32).sqrt()
+    }
+
+    /// Écart maximal et indice du point concerné
+    pub fn max(&self) -> (usize, f32) {
+        self.distances
+            .iter()
+            .copied()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((0, 0.0))
+    }
+
+    /// Verdict du canal, une ligne
+    pub fn verdict_line(&self) -> String {
+        tr_args(
+            "comparison-verdict",
+            &[
+                ("channel", format!("CH{}", self.live.channel).into()),
+                ("result", verdict_text(self.pass).into()),
+                ("score", format!("{:.0}", self.score).into()),
+                ("reference", self.reference_name.clone().into()),
+            ],
+        )
+    }
+
+    /// Statistiques d'écart, une ligne
+    pub fn stats_line(&self) -> String {
+        let (point, max) = self.max();
+        tr_args(
+            "comparison-stats",
+            &[
+                ("mean", format!("{:.4}", self.mean()).into()),
+                ("rms", format!("{:.4}", self.rms()).into()),
+                ("max", format!("{:.4}", max).into()),
+                ("point", point.into()),
+            ],
+        )
+    }
+}
+
+/// Verdict traduit : PASS ou FAIL
+pub fn verdict_text(pass: bool) -> String {
+    tr(if pass { "operator-pass" } else { "operator-fail" })
+}
+
+/// Verdict global : PASS si tous les canaux comparés passent
+pub fn overall_line(deviations: &[ChannelDeviation]) -> String {
+    let pass = deviations.iter().all(|d| d.pass);
+    tr_args("comparison-result", &[("result", verdict_text(pass).into())])
+}
+
+/// Enregistrer le rapport de comparaison : courbes superposées à leur
+/// référence, tracé de l'écart, statistiques et verdict. `header` reprend
+/// l'en-tête des exports, `fields` les champs du modèle HTML
+pub fn save_comparison_report(
+    deviations: &[ChannelDeviation],
+    header: &[String],
+    fields: BTreeMap<&'static str, String>,
+    settings: &Settings,
+    format: ReportFormat,
+    path: &Path,
+) -> Result<(), String> {
+    if deviations.is_empty() {
+        return Err(tr("comparison-none"));
+    }
+    let mut header = header.to_vec();
+    // Le verdict global suit les réglages, comme dans le rapport PDF
+    header.insert(header.len().min(4), overall_line(deviations));
+
+    match format {
+        ReportFormat::Png => save_comparison(deviations, &header, settings, ImageFormat::Png, path),
+        ReportFormat::Pdf => save_comparison(deviations, &header, settings, ImageFormat::Pdf, path),
+        ReportFormat::Html => save_comparison_as_html(deviations, &header, fields, settings, path),
+    }
+}
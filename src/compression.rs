@@ -0,0 +1,105 @@
+// src/compression.rs
+
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use std::io::{Read as _, Write as _};
+use std::path::Path;
+
+/// Compression d'un fichier enregistré (sessions) : gzip ou zstd
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Compression {
+    None,
+    #[default]
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Suffixe ajouté au nom du fichier
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Compression::None => "",
+            Compression::Gzip => ".gz",
+            Compression::Zstd => ".zst",
+        }
+    }
+
+    /// Compression choisie par l'extension du fichier de sortie
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(e) if e.eq_ignore_ascii_case("gz") => Compression::Gzip,
+            Some(e) if e.eq_ignore_ascii_case("zst") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+
+    /// Compression reconnue à la signature du contenu
+    fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            Compression::Gzip
+        } else if bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+}
+
+/// Compresser `bytes` ; inchangés sans compression
+pub fn compress(bytes: Vec<u8>, compression: Compression) -> Result<Vec<u8>, String> {
+    // Niveau par défaut de chaque format, rapide
+    match compression {
+        Compression::None => Ok(bytes),
+        Compression::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder
+                .write_all(&bytes)
+                .and_then(|_| encoder.finish())
+                .map_err(|e| format!("Erreur de compression gzip: {}", e))
+        }
+        Compression::Zstd => zstd::encode_all(bytes.as_slice(), 0)
+            .map_err(|e| format!("Erreur de compression zstd: {}", e)),
+    }
+}
+
+/// Décompresser un contenu gzip ou zstd reconnu à sa signature ; les
+/// contenus non compressés sont renvoyés tels quels
+pub fn decompress(bytes: Vec<u8>) -> Result<Vec<u8>, String> {
+    match Compression::detect(&bytes) {
+        Compression::None => Ok(bytes),
+        Compression::Gzip => {
+            let mut output = Vec::new();
+            MultiGzDecoder::new(bytes.as_slice())
+                .read_to_end(&mut output)
+                .map_err(|e| format!("Erreur de décompression gzip: {}", e))?;
+            Ok(output)
+        }
+        Compression::Zstd => zstd::decode_all(bytes.as_slice())
+            .map_err(|e| format!("Erreur de décompression zstd: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let content = b"version = 1\nnotes = \"carte 42\"\n".repeat(50);
+        for compression in [Compression::None, Compression::Gzip, Compression::Zstd] {
+            let compressed = compress(content.clone(), compression).unwrap();
+            assert_eq!(Compression::detect(&compressed), compression);
+            assert_eq!(decompress(compressed).unwrap(), content);
+        }
+    }
+
+    #[test]
+    fn truncated_content_is_an_error() {
+        for compression in [Compression::Gzip, Compression::Zstd] {
+            let mut compressed = compress(b"version = 1\n".repeat(50), compression).unwrap();
+            compressed.truncate(compressed.len() / 2);
+            assert!(decompress(compressed).is_err());
+        }
+    }
+}
@@ -6,3 +6,4 @@ pub const READ_SIZE: usize = 65;
 pub const POINTS_PER_CURVE: usize = 512;
 pub const REPORTS_PER_CURVE: usize = 32;
 pub const HEADER_MAGIC: [u8; 2] = [0xf0, 0xff];
+pub const READ_TIMEOUT_MS: i32 = 500;
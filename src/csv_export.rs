@@ -0,0 +1,204 @@
+// src/csv_export.rs
+
+use crate::analysis::{estimate_capacitance, Analysis, Component};
+use crate::calibration::Calibration;
+use crate::curve::{CurveData, DualCurveData};
+use crate::settings::Settings;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use tracing::info;
+
+/// Colonnes d'un point
+const COLUMNS: [&str; 6] =
+    ["channel", "index", "voltage_norm", "current_norm", "voltage_v", "current_ma"];
+
+/// Exporter les points des canaux en CSV : en-tête de métadonnées en
+/// commentaires (`#`), puis une ligne par point avec les valeurs normalisées
+/// et, si la calibration est active, converties en unités physiques
+pub fn save_curves_as_csv(
+    data: &DualCurveData,
+    channels: &[u8],
+    settings: &Settings,
+    header: &[String],
+    path: &Path,
+) -> Result<(), String> {
+    let mut content = comments(header);
+    let _ = writeln!(content, "{}", COLUMNS.join(","));
+    for curve in channels.iter().filter_map(|&c| data.channel(c)) {
+        write_points(&mut content, "", curve, &settings.calibration, ',');
+    }
+
+    fs::write(path, content)
+        .map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+
+    info!("CSV sauvegardé : {}", path.display());
+    Ok(())
+}
+
+/// Exporter plusieurs balayages (historique) dans un seul CSV : même format,
+/// précédé du numéro de balayage de chaque courbe
+pub fn save_sweeps_as_csv(
+    sweeps: &[(usize, CurveData)],
+    settings: &Settings,
+    header: &[String],
+    path: &Path,
+) -> Result<(), String> {
+    let mut content = comments(header);
+    let _ = writeln!(content, "sweep,{}", COLUMNS.join(","));
+    for (sweep, curve) in sweeps {
+        let prefix = format!("{},", sweep);
+        write_points(&mut content, &prefix, curve, &settings.calibration, ',');
+    }
+
+    fs::write(path, content)
+        .map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+
+    info!("CSV sauvegardé : {}", path.display());
+    Ok(())
+}
+
+/// Colonnes du tableau de mesures, après la colonne de libellé
+const METRIC_COLUMNS: [&str; 12] = [
+    "channel",
+    "component",
+    "slope",
+    "resistance_norm",
+    "resistance_ohm",
+    "forward_voltage_norm",
+    "forward_voltage_v",
+    "voltage_span",
+    "current_span",
+    "capacitance_f",
+    "match_score",
+    "pass",
+];
+
+/// Courbe d'une série (balayage de l'historique, point de test validé) et son
+/// score contre la référence éventuelle
+pub struct MetricsRow {
+    /// Numéro de balayage ou nom du point de test
+    pub label: String,
+    pub curve: CurveData,
+    pub score: Option<f32>,
+    pub pass: Option<bool>,
+}
+
+/// Exporter les mesures d'une série de courbes en CSV, une ligne par courbe,
+/// pour la maîtrise statistique des procédés : classification, pente et
+/// grandeurs dérivées, score de correspondance et verdict. Les colonnes en
+/// unités physiques restent vides sans calibration ; `frequency` (Hz) permet
+/// l'estimation de la capacité
+pub fn save_metrics_as_csv(
+    rows: &[MetricsRow],
+    label_column: &str,
+    settings: &Settings,
+    frequency: Option<f32>,
+    header: &[String],
+    path: &Path,
+) -> Result<(), String> {
+    let calibration = &settings.calibration;
+    let physical = |value: Option<f32>, factor: f32| match value {
+        Some(value) if calibration.enabled => format!("{:.6}", value * factor),
+        _ => String::new(),
+    };
+    let normalized = |value: Option<f32>| value.map(|v| format!("{:.6}", v)).unwrap_or_default();
+    let ohms_per_ratio = calibration.volts_full_scale / (calibration.milliamps_full_scale / 1000.0);
+
+    let mut content = comments(header);
+    let _ = writeln!(content, "{},{}", label_column, METRIC_COLUMNS.join(","));
+    for row in rows {
+        let curve = &row.curve;
+        let analysis = Analysis::of(curve);
+        let metrics = curve.metrics();
+        let capacitance = match analysis.component {
+            Component::Capacitor => frequency
+                .and_then(|f| estimate_capacitance(curve, calibration, f))
+                .map(|farads| format!("{:e}", farads))
+                .unwrap_or_default(),
+            _ => String::new(),
+        };
+        let fields = [
+            csv_field(&row.label),
+            curve.channel.to_string(),
+            format!("{:?}", analysis.component).to_lowercase(),
+            format!("{:.6}", analysis.slope),
+            normalized(analysis.resistance()),
+            physical(analysis.resistance(), ohms_per_ratio),
+            normalized(analysis.forward_voltage),
+            physical(analysis.forward_voltage, calibration.volts_full_scale),
+            format!("{:.6}", metrics.voltage_span),
+            format!("{:.6}", metrics.current_span),
+            capacitance,
+            row.score.map(|s| format!("{:.2}", s)).unwrap_or_default(),
+            row.pass.map(|p| (p as u8).to_string()).unwrap_or_default(),
+        ];
+        let _ = writeln!(content, "{}", fields.join(","));
+    }
+
+    fs::write(path, content)
+        .map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+
+    info!("Mesures CSV sauvegardées : {}", path.display());
+    Ok(())
+}
+
+/// Champ CSV entre guillemets s'il contient un séparateur, un guillemet ou
+/// un saut de ligne
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Points des canaux séparés par des tabulations, pour le presse-papiers :
+/// collés dans un tableur, ils remplissent directement les colonnes
+pub fn curves_as_tsv(data: &DualCurveData, channels: &[u8], settings: &Settings) -> String {
+    let mut content = String::new();
+    let _ = writeln!(content, "{}", COLUMNS.join("\t"));
+    for curve in channels.iter().filter_map(|&c| data.channel(c)) {
+        write_points(&mut content, "", curve, &settings.calibration, '\t');
+    }
+    content
+}
+
+/// Lignes d'en-tête en commentaires (`#`)
+fn comments(header: &[String]) -> String {
+    let mut content = String::new();
+    for line in header {
+        for part in line.lines() {
+            let _ = writeln!(content, "# {}", part);
+        }
+    }
+    content
+}
+
+/// Une ligne par point d'une courbe, chacune commençant par `prefix`, les
+/// valeurs séparées par `sep`
+fn write_points(
+    content: &mut String,
+    prefix: &str,
+    curve: &CurveData,
+    calibration: &Calibration,
+    sep: char,
+) {
+    for (i, (v, c)) in curve.voltage.iter().zip(&curve.current).enumerate() {
+        let physical = if calibration.enabled {
+            format!(
+                "{:.6}{}{:.6}",
+                v * calibration.volts_full_scale,
+                sep,
+                c * calibration.milliamps_full_scale
+            )
+        } else {
+            sep.to_string()
+        };
+        let _ = writeln!(
+            content,
+            "{}{}{sep}{}{sep}{:.6}{sep}{:.6}{sep}{}",
+            prefix, curve.channel, i, v, c, physical
+        );
+    }
+}
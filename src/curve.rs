@@ -2,14 +2,56 @@
 
 use crate::config::POINTS_PER_CURVE;
 use byteorder::{ByteOrder, LittleEndian};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CurveData {
     pub voltage: Vec<f32>,
     pub current: Vec<f32>,
     pub channel: u8,
+    /// Valeurs brutes de l'ADC avant normalisation (vides pour les courbes
+    /// enregistrées sans elles)
+    #[serde(default)]
+    pub raw_voltage: Vec<u16>,
+    #[serde(default)]
+    pub raw_current: Vec<u16>,
 }
 
+/// Mesures simples d'une courbe (valeurs normalisées)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CurveMetrics {
+    /// Excursion crête à crête de la tension
+    pub voltage_span: f32,
+    /// Excursion crête à crête du courant
+    pub current_span: f32,
+}
+
+impl CurveData {
+    pub fn metrics(&self) -> CurveMetrics {
+        CurveMetrics {
+            voltage_span: span(&self.voltage),
+            current_span: span(&self.current),
+        }
+    }
+}
+
+/// Écart entre la plus grande et la plus petite valeur
+fn span(values: &[f32]) -> f32 {
+    range(values).map_or(0.0, |(min, max)| max - min)
+}
+
+/// Plus petite et plus grande valeur ; `None` sans valeur
+pub fn range(values: &[f32]) -> Option<(f32, f32)> {
+    if values.is_empty() {
+        return None;
+    }
+    let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    Some((min, max))
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct DualCurveData {
     pub channel0: Option<CurveData>,
     pub channel1: Option<CurveData>,
@@ -22,23 +64,97 @@ impl DualCurveData {
             channel1: None,
         }
     }
+
+    /// Remplacer la dernière courbe du canal correspondant
+    pub fn store(&mut self, curve: CurveData) {
+        if curve.channel == 0 {
+            self.channel0 = Some(curve);
+        } else {
+            self.channel1 = Some(curve);
+        }
+    }
+
+    pub fn channel(&self, channel: u8) -> Option<&CurveData> {
+        if channel == 0 {
+            self.channel0.as_ref()
+        } else {
+            self.channel1.as_ref()
+        }
+    }
+}
+
+/// Historique glissant des dernières courbes reçues, par canal
+pub struct CurveHistory {
+    pub capacity: usize,
+    channel0: VecDeque<CurveData>,
+    channel1: VecDeque<CurveData>,
+}
+
+impl CurveHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            channel0: VecDeque::new(),
+            channel1: VecDeque::new(),
+        }
+    }
+
+    pub fn push(&mut self, curve: CurveData) {
+        let capacity = self.capacity;
+        let list = if curve.channel == 0 {
+            &mut self.channel0
+        } else {
+            &mut self.channel1
+        };
+        list.push_back(curve);
+        while list.len() > capacity {
+            list.pop_front();
+        }
+    }
+
+    /// Courbes d'un canal, de la plus ancienne à la plus récente
+    pub fn channel(&self, channel: u8) -> &VecDeque<CurveData> {
+        if channel == 0 {
+            &self.channel0
+        } else {
+            &self.channel1
+        }
+    }
 }
 
 /// Parse les bytes bruts d'une courbe + normalisation comme dans ton Python.
-/// Retourne (V_norm, I_norm).
-pub fn parse_and_normalize_curve_data(data_bytes: &[u8]) -> Result<(Vec<f32>, Vec<f32>), String> {
+/// La courbe garde aussi les valeurs brutes de l'ADC.
+pub fn parse_curve(data_bytes: &[u8], channel: u8) -> Result<CurveData, String> {
     let raw_len = (POINTS_PER_CURVE * 4).min(data_bytes.len());
     let raw = &data_bytes[..raw_len];
 
-    let mut pairs = Vec::with_capacity(POINTS_PER_CURVE);
-
+    let mut raw_current = Vec::with_capacity(POINTS_PER_CURVE);
+    let mut raw_voltage = Vec::with_capacity(POINTS_PER_CURVE);
     for i in 0..(raw.len() / 4).min(POINTS_PER_CURVE) {
         let offset = i * 4;
-        let current_raw = LittleEndian::read_u16(&raw[offset..offset + 2]) as f32;
-        let voltage_raw = LittleEndian::read_u16(&raw[offset + 2..offset + 4]) as f32;
-        pairs.push((current_raw, voltage_raw));
+        raw_current.push(LittleEndian::read_u16(&raw[offset..offset + 2]));
+        raw_voltage.push(LittleEndian::read_u16(&raw[offset + 2..offset + 4]));
     }
 
+    let (voltage, current) = normalize(&raw_voltage, &raw_current)?;
+    Ok(CurveData {
+        voltage,
+        current,
+        channel,
+        raw_voltage,
+        raw_current,
+    })
+}
+
+/// Centrer les valeurs brutes sur leur médiane puis les ramener dans [-1, 1].
+/// Retourne (V_norm, I_norm).
+fn normalize(raw_voltage: &[u16], raw_current: &[u16]) -> Result<(Vec<f32>, Vec<f32>), String> {
+    let pairs: Vec<(f32, f32)> = raw_current
+        .iter()
+        .zip(raw_voltage)
+        .map(|(&c, &v)| (c as f32, v as f32))
+        .collect();
+
     if pairs.is_empty() {
         return Err("Aucune paire de données extraite".to_string());
     }
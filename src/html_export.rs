@@ -0,0 +1,332 @@
+// src/html_export.rs
+
+use crate::comparison_report::ChannelDeviation;
+use crate::curve::{CurveData, DualCurveData};
+use crate::i18n::{tr, tr_args};
+use crate::settings::Settings;
+use crate::template;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use tracing::info;
+
+/// Modèle de page intégré, remplacé par le modèle HTML des préférences
+const PAGE: &str = r##"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{{ app }} — {{ source }}</title>
+<style>
+body { font-family: sans-serif; margin: 20px; }
+</style>
+</head>
+<body>
+{{ header }}
+{{ plot }}
+</body>
+</html>
+"##;
+
+/// Tracé interactif (champ `plot`) : les points sont insérés à la place de
+/// `/*CURVES*/`, le facteur d'échelle à la place de `/*SCALE*/` et l'aide à la
+/// place de `<!--HINT-->`
+const PLOT: &str = r##"<style>
+#plot { border: 1px solid #888; cursor: grab; }
+#pos { font-family: monospace; height: 1.2em; }
+</style>
+<canvas id="plot" width="800" height="800"></canvas>
+<div id="pos"></div>
+<p><!--HINT--></p>
+<script>
+const CURVES = [/*CURVES*/];
+const SCALE = /*SCALE*/;
+const canvas = document.getElementById("plot");
+const ctx = canvas.getContext("2d");
+const HOME = { x0: -1.1, x1: 1.1, y0: -1.1, y1: 1.1 };
+let view = Object.assign({}, HOME);
+let drag = null;
+
+const toX = v => (v - view.x0) / (view.x1 - view.x0) * canvas.width;
+const toY = i => (view.y1 - i) / (view.y1 - view.y0) * canvas.height;
+const fromX = x => view.x0 + x / canvas.width * (view.x1 - view.x0);
+const fromY = y => view.y1 - y / canvas.height * (view.y1 - view.y0);
+
+function step(span) {
+  const raw = span / 10;
+  const pow = Math.pow(10, Math.floor(Math.log10(raw)));
+  return [1, 2, 5, 10].map(m => m * pow).find(s => s >= raw);
+}
+
+function draw() {
+  ctx.fillStyle = "#fff";
+  ctx.fillRect(0, 0, canvas.width, canvas.height);
+  ctx.font = "12px sans-serif";
+  ctx.lineWidth = 1;
+  const sx = step(view.x1 - view.x0), sy = step(view.y1 - view.y0);
+  for (let v = Math.ceil(view.x0 / sx) * sx; v <= view.x1; v += sx) {
+    ctx.strokeStyle = Math.abs(v) < sx / 2 ? "#000" : "#ddd";
+    ctx.beginPath(); ctx.moveTo(toX(v), 0); ctx.lineTo(toX(v), canvas.height); ctx.stroke();
+    ctx.fillStyle = "#555";
+    ctx.fillText(label(v, SCALE && SCALE.v, "V"), toX(v) + 2, canvas.height - 4);
+  }
+  for (let i = Math.ceil(view.y0 / sy) * sy; i <= view.y1; i += sy) {
+    ctx.strokeStyle = Math.abs(i) < sy / 2 ? "#000" : "#ddd";
+    ctx.beginPath(); ctx.moveTo(0, toY(i)); ctx.lineTo(canvas.width, toY(i)); ctx.stroke();
+    ctx.fillStyle = "#555";
+    ctx.fillText(label(i, SCALE && SCALE.i, "mA"), 4, toY(i) - 2);
+  }
+  ctx.lineWidth = 2;
+  CURVES.forEach((curve, n) => {
+    ctx.strokeStyle = curve.color;
+    ctx.beginPath();
+    curve.v.forEach((v, k) => {
+      if (k === 0) ctx.moveTo(toX(v), toY(curve.i[k])); else ctx.lineTo(toX(v), toY(curve.i[k]));
+    });
+    ctx.stroke();
+    ctx.fillStyle = curve.color;
+    ctx.fillText(curve.name, canvas.width - 60, 20 + 16 * n);
+  });
+}
+
+function label(value, scale, unit) {
+  const shown = Math.abs(value) < 1e-9 ? 0 : value;
+  return scale ? (shown * scale).toPrecision(3) + " " + unit : shown.toPrecision(3);
+}
+
+canvas.addEventListener("wheel", e => {
+  e.preventDefault();
+  const factor = e.deltaY < 0 ? 0.8 : 1.25;
+  const v = fromX(e.offsetX), i = fromY(e.offsetY);
+  view = {
+    x0: v + (view.x0 - v) * factor, x1: v + (view.x1 - v) * factor,
+    y0: i + (view.y0 - i) * factor, y1: i + (view.y1 - i) * factor,
+  };
+  draw();
+});
+canvas.addEventListener("mousedown", e => { drag = { x: e.offsetX, y: e.offsetY }; });
+window.addEventListener("mouseup", () => { drag = null; });
+canvas.addEventListener("mousemove", e => {
+  const v = fromX(e.offsetX), i = fromY(e.offsetY);
+  document.getElementById("pos").textContent =
+    "V " + label(v, SCALE && SCALE.v, "V") + "   I " + label(i, SCALE && SCALE.i, "mA");
+  if (!drag) return;
+  const dv = fromX(drag.x) - v, di = fromY(drag.y) - i;
+  view = { x0: view.x0 + dv, x1: view.x1 + dv, y0: view.y0 + di, y1: view.y1 + di };
+  drag = { x: e.offsetX, y: e.offsetY };
+  draw();
+});
+canvas.addEventListener("dblclick", () => { view = Object.assign({}, HOME); draw(); });
+draw();
+</script>
+"##;
+
+/// Exporter une page HTML autonome : points des canaux intégrés et tracé
+/// interactif (zoom, déplacement) lisible sans l'application. `fields` sont
+/// les champs du modèle (voir `report::fields`), échappés ici
+pub fn save_curves_as_html(
+    data: &DualCurveData,
+    channels: &[u8],
+    settings: &Settings,
+    header: &[String],
+    fields: BTreeMap<&'static str, String>,
+    path: &Path,
+) -> Result<(), String> {
+    let mut curves = String::new();
+    for curve in channels.iter().filter_map(|&c| data.channel(c)) {
+        let name = format!("CH{}", curve.channel);
+        curves.push_str(&curve_js(&name, settings.plot.channel_rgb(curve.channel), curve));
+    }
+    save_page(&curves, "", settings, header, fields, path)?;
+
+    info!("HTML sauvegardé : {}", path.display());
+    Ok(())
+}
+
+/// Rapport de comparaison en HTML : courbes et références dans le tracé
+/// interactif, puis verdict, statistiques et écart point par point (SVG) de
+/// chaque canal
+pub fn save_comparison_as_html(
+    deviations: &[ChannelDeviation],
+    header: &[String],
+    fields: BTreeMap<&'static str, String>,
+    settings: &Settings,
+    path: &Path,
+) -> Result<(), String> {
+    let mut curves = String::new();
+    let mut details = String::new();
+    for deviation in deviations {
+        let live = &deviation.live;
+        let name = format!("CH{}", live.channel);
+        curves.push_str(&curve_js(&name, settings.plot.channel_rgb(live.channel), live));
+        let reference = format!("{} ({})", deviation.reference_name, name);
+        curves.push_str(&curve_js(&reference, REFERENCE_RGB, &deviation.reference));
+
+        let _ = writeln!(details, "<h3>{}</h3>", name);
+        let _ = writeln!(details, "<p><b>{}</b></p>", escape_html(&deviation.verdict_line()));
+        let _ = writeln!(details, "<p>{}</p>", escape_html(&deviation.stats_line()));
+        let _ = writeln!(details, "<p>{}</p>", escape_html(&tr("comparison-deviation")));
+        details.push_str(&deviation_svg(&deviation.distances));
+    }
+    save_page(&curves, &details, settings, header, fields, path)?;
+
+    info!("Rapport de comparaison sauvegardé : {}", path.display());
+    Ok(())
+}
+
+/// Couleur des références, celle de la référence à l'écran
+const REFERENCE_RGB: [u8; 3] = [0, 170, 90];
+
+/// Entrée du tableau `CURVES` du tracé interactif
+fn curve_js(name: &str, [r, g, b]: [u8; 3], curve: &CurveData) -> String {
+    let join = |values: &[f32]| {
+        values
+            .iter()
+            .map(|x| format!("{:.5}", x))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    // Nom en chaîne JavaScript, sans fermeture possible du <script>
+    format!(
+        "{{ name: \"{}\", color: \"#{:02x}{:02x}{:02x}\", v: [{}], i: [{}] }},\n",
+        name.replace('\\', "\\\\").replace('"', "\\\"").replace('<', "\\u003c"),
+        r,
+        g,
+        b,
+        join(&curve.voltage),
+        join(&curve.current)
+    )
+}
+
+/// Écart point par point en SVG intégré : polyligne et échelle verticale
+fn deviation_svg(distances: &[f32]) -> String {
+    const WIDTH: f32 = 800.0;
+    const HEIGHT: f32 = 180.0;
+    let top = distances.iter().copied().fold(0.0, f32::max).max(1e-3);
+    let last = distances.len().saturating_sub(1).max(1) as f32;
+    let points: Vec<String> = distances
+        .iter()
+        .enumerate()
+        .map(|(k, d)| format!("{:.1},{:.1}", k as f32 / last * WIDTH, HEIGHT - d / top * HEIGHT))
+        .collect();
+    format!(
+        "<svg width=\"{w}\" height=\"{h}\" style=\"border: 1px solid #888\">\n\
+         <polyline fill=\"none\" stroke=\"#000\" stroke-width=\"1.5\" points=\"{}\"/>\n\
+         <text x=\"{x}\" y=\"14\" text-anchor=\"end\" font-size=\"12\">{:.3}</text>\n\
+         </svg>\n",
+        points.join(" "),
+        top,
+        w = WIDTH,
+        h = HEIGHT,
+        x = WIDTH - 4.0
+    )
+}
+
+/// Page complète : tracé interactif des `curves`, suivi de `details` (HTML),
+/// dans le modèle des préférences ou le modèle intégré
+fn save_page(
+    curves: &str,
+    details: &str,
+    settings: &Settings,
+    header: &[String],
+    fields: BTreeMap<&'static str, String>,
+    path: &Path,
+) -> Result<(), String> {
+    let calibration = &settings.calibration;
+    let scale = if calibration.enabled {
+        format!(
+            "{{ v: {}, i: {} }}",
+            calibration.volts_full_scale, calibration.milliamps_full_scale
+        )
+    } else {
+        "null".to_string()
+    };
+
+    let mut header_html = String::new();
+    for line in header {
+        let _ = writeln!(header_html, "<p>{}</p>", escape_html(line));
+    }
+
+    let plot = PLOT
+        .replace("<!--HINT-->", &escape_html(&tr("html-hint")))
+        .replace("/*CURVES*/", curves)
+        .replace("/*SCALE*/", &scale)
+        + details;
+
+    let mut fields: BTreeMap<&str, String> = fields
+        .into_iter()
+        .map(|(name, value)| (name, escape_html(&value)))
+        .collect();
+    fields.insert("header", header_html);
+    fields.insert("plot", plot);
+    let page = template::load(settings.html_template.as_deref())?;
+    let content = template::render(page.as_deref().unwrap_or(PAGE), &fields)?;
+    fs::write(path, content).map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Ligne de l'index d'une conversion par lots
+pub struct IndexRow {
+    /// Nom du fichier de capture
+    pub name: String,
+    /// Résumé des mesures, ou erreur de conversion
+    pub summary: Result<String, String>,
+    /// Fichiers produits, relatifs au répertoire de l'index
+    pub files: Vec<String>,
+}
+
+/// Enregistrer l'index d'une conversion par lots : une ligne par capture,
+/// avec une vignette de l'image produite et les liens vers les fichiers
+pub fn save_batch_index(title: &str, rows: &[IndexRow], path: &Path) -> Result<(), String> {
+    let mut table = String::new();
+    for row in rows {
+        let summary = match &row.summary {
+            Ok(summary) => escape_html(summary),
+            Err(e) => format!(
+                "<b>{}</b>",
+                escape_html(&tr_args("batch-error", &[("error", e.clone().into())]))
+            ),
+        };
+        let image = row.files.iter().find(|f| f.ends_with(".png") || f.ends_with(".svg"));
+        let thumbnail = image.map_or(String::new(), |f| {
+            format!("<a href=\"{0}\"><img src=\"{0}\" height=\"120\"></a>", escape_html(f))
+        });
+        let links: Vec<String> = row
+            .files
+            .iter()
+            .map(|f| format!("<a href=\"{0}\">{0}</a>", escape_html(f)))
+            .collect();
+        let _ = writeln!(
+            table,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape_html(&row.name),
+            thumbnail,
+            summary,
+            links.join("<br>")
+        );
+    }
+
+    let content = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+         <style>\nbody {{ font-family: sans-serif; margin: 20px; }}\n\
+         td, th {{ border-bottom: 1px solid #ccc; padding: 6px; text-align: left; \
+         vertical-align: top; }}\n</style>\n</head>\n<body>\n<h1>{title}</h1>\n<table>\n\
+         <tr><th>{}</th><th></th><th>{}</th><th>{}</th></tr>\n{}</table>\n</body>\n</html>\n",
+        escape_html(&tr("batch-capture")),
+        escape_html(&tr("batch-summary")),
+        escape_html(&tr("batch-files")),
+        table,
+        title = escape_html(title),
+    );
+    fs::write(path, content)
+        .map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+
+    info!("Index sauvegardé : {}", path.display());
+    Ok(())
+}
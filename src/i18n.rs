@@ -0,0 +1,104 @@
+// src/i18n.rs
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU8, Ordering};
+use unic_langid::LanguageIdentifier;
+
+/// Langues disponibles pour l'interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    French,
+    English,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::French, Language::English];
+
+    /// Nom de la langue dans cette langue (pour le sélecteur)
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Language::French => "Français",
+            Language::English => "English",
+        }
+    }
+
+    fn id(self) -> &'static str {
+        match self {
+            Language::French => "fr",
+            Language::English => "en",
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Language::French => include_str!("../locales/fr.ftl"),
+            Language::English => include_str!("../locales/en.ftl"),
+        }
+    }
+}
+
+/// Langue courante, partagée par tous les threads
+static CURRENT: AtomicU8 = AtomicU8::new(Language::French as u8);
+
+thread_local! {
+    // Les bundles Fluent ne sont pas Sync : un par thread, reconstruit au changement de langue
+    static BUNDLE: RefCell<Option<(Language, FluentBundle<FluentResource>)>> =
+        const { RefCell::new(None) };
+}
+
+pub fn set_language(language: Language) {
+    CURRENT.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Language::English,
+        _ => Language::French,
+    }
+}
+
+fn build_bundle(language: Language) -> FluentBundle<FluentResource> {
+    let langid: LanguageIdentifier = language.id().parse().expect("Identifiant de langue invalide");
+    let resource =
+        FluentResource::try_new(language.source().to_string()).expect("Fichier FTL invalide");
+
+    let mut bundle = FluentBundle::new(vec![langid]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(resource).expect("Message FTL en double");
+    bundle
+}
+
+/// Traduire un message sans argument
+pub fn tr(key: &str) -> String {
+    tr_args(key, &[])
+}
+
+/// Traduire un message avec arguments ; renvoie la clé si le message est absent
+pub fn tr_args(key: &str, args: &[(&str, FluentValue)]) -> String {
+    let language = language();
+
+    BUNDLE.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        if cell.as_ref().map(|(lang, _)| *lang) != Some(language) {
+            *cell = Some((language, build_bundle(language)));
+        }
+        let (_, bundle) = cell.as_ref().unwrap();
+
+        let Some(pattern) = bundle.get_message(key).and_then(|msg| msg.value()) else {
+            return key.to_string();
+        };
+
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+
+        let mut errors = Vec::new();
+        bundle
+            .format_pattern(pattern, Some(&fluent_args), &mut errors)
+            .into_owned()
+    })
+}
@@ -1,222 +1,954 @@
 // src/image_export.rs
 
+use crate::annotation::Annotation;
+use crate::comparison_report::ChannelDeviation;
 use crate::curve::{CurveData, DualCurveData};
-use image::{ImageBuffer, Rgba};
+use crate::i18n::tr;
+use crate::plot_renderer::{self, PlotFrame, PlotStyle, PlotTarget, REFERENCE_COLOR};
+use crate::settings::Settings;
+use ab_glyph::{Font, FontArc, PxScale, ScaleFont};
+use eframe::egui::{self, Align2, Color32, Pos2, Rect, Stroke};
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, ImageBuffer, Rgba};
+use pdf_writer::types::LineCapStyle;
+use pdf_writer::{Content, Finish, Name, Rect as PdfRect, Ref, Str};
+use std::fmt::Write as _;
+use std::fs;
+use std::io::BufWriter;
+use std::path::Path;
+use tracing::{info, warn};
+
+/// Taille du texte des annotations (pixels)
+const ANNOTATION_TEXT_SIZE: f32 = 20.0;
+
+/// Grille et axes des exports : traits fins gris, axes noirs de trois pixels
+const STYLE: PlotStyle = PlotStyle {
+    grid: Color32::from_rgb(200, 200, 200),
+    minor_grid: Color32::from_rgb(235, 235, 235),
+    axis: BLACK,
+    grid_width: 1.0,
+    axis_width: 3.0,
+    tick_size: 14.0,
+    name_size: ANNOTATION_TEXT_SIZE,
+};
+
+/// Page imprimable : A4 à 150 ppp
+const PAGE_WIDTH: u32 = 1240;
+const PAGE_HEIGHT: u32 = 1754;
+const PAGE_MARGIN: u32 = 80;
+const PAGE_LINE_HEIGHT: u32 = 30;
+
+/// Taille d'un pixel en points PDF (page A4 à 150 ppp)
+const PDF_POINTS_PER_PIXEL: f32 = 0.48;
+
+/// Nom de la police dans les ressources de la page PDF
+const PDF_FONT: Name = Name(b"F1");
+
+const BLACK: Color32 = Color32::BLACK;
+const WHITE: Color32 = Color32::WHITE;
+const TRANSPARENT: Rgba<u8> = Rgba([255, 255, 255, 0]);
+
+/// Format des images exportées
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    /// Vectoriel, pour les rapports et publications
+    Svg,
+    /// Document PDF d'une page (rapports)
+    Pdf,
+}
 
-pub fn save_curve_as_png(curve: &CurveData, filename: &str) -> Result<(), String> {
-    let width = 800;
-    let height = 800;
+impl ImageFormat {
+    pub const ALL: [ImageFormat; 3] = [ImageFormat::Png, ImageFormat::Svg, ImageFormat::Pdf];
 
-    let mut img = ImageBuffer::from_fn(width, height, |_, _| {
-        Rgba([255u8, 255u8, 255u8, 255u8])
-    });
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Svg => "svg",
+            ImageFormat::Pdf => "pdf",
+        }
+    }
+
+    /// Format d'après l'extension d'un fichier de sortie
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        ImageFormat::ALL
+            .into_iter()
+            .find(|format| format.extension() == extension)
+    }
+}
 
-    let center_x = width as f32 / 2.0;
-    let center_y = height as f32 / 2.0;
-    let scale = (width.min(height) as f32) * 0.45;
+/// Zone carrée d'un tracé dans l'image (pixels)
+fn plot_frame(x: f32, y: f32, size: f32) -> PlotFrame {
+    PlotFrame::new(Rect::from_min_size(egui::pos2(x, y), egui::Vec2::splat(size)))
+}
 
-    let grid_color = Rgba([200u8, 200u8, 200u8, 255u8]);
-    for i in -10..=10 {
-        let offset = (i as f32) * scale / 10.0;
+/// Cible de rendu des exports : le tracé passe par `PlotTarget`, comme à
+/// l'écran ; s'y ajoutent le fond et les légendes de la mise en page
+trait Canvas: PlotTarget {
+    fn height(&self) -> u32;
+    /// Rectangle plein (fond, cadre des légendes)
+    fn fill_rect(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color32);
+}
 
-        let x = (center_x + offset) as i32;
-        if x >= 0 && x < width as i32 {
-            for y in 0..height {
-                if let Some(pixel) = img.get_pixel_mut_checked(x as u32, y) {
-                    *pixel = grid_color;
-                }
+/// Image matricielle, enregistrée en PNG ; les coordonnées de dessin sont
+/// multipliées par `factor` (résolution d'export)
+struct Raster {
+    img: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    factor: f32,
+    /// Police chargée au premier texte (`Some(None)` si indisponible)
+    font: Option<Option<FontArc>>,
+}
+
+impl Raster {
+    fn new(width: u32, height: u32, factor: f32) -> Self {
+        let (w, h) = (scaled(width, factor), scaled(height, factor));
+        Self {
+            img: ImageBuffer::from_pixel(w, h, TRANSPARENT),
+            factor,
+            font: None,
+        }
+    }
+
+    /// Enregistrer en PNG avec les textes `metadata` (mot-clé, valeur) :
+    /// chunks tEXt pour l'ASCII, iTXt (UTF-8) sinon
+    fn save(&self, path: &Path, metadata: &[(&str, String)]) -> Result<(), String> {
+        let png_error = |e: png::EncodingError| format!("Erreur sauvegarde PNG: {}", e);
+        let file = fs::File::create(path)
+            .map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+        let (width, height) = self.img.dimensions();
+        let mut encoder = png::Encoder::new(BufWriter::new(file), width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        for (keyword, text) in metadata {
+            let (keyword, text) = (keyword.to_string(), text.clone());
+            if text.is_ascii() {
+                encoder.add_text_chunk(keyword, text)
+            } else {
+                encoder.add_itxt_chunk(keyword, text)
             }
+            .map_err(png_error)?;
         }
+        let mut writer = encoder.write_header().map_err(png_error)?;
+        writer
+            .write_image_data(self.img.as_raw())
+            .map_err(png_error)?;
+        writer.finish().map_err(png_error)
+    }
+}
 
-        let y = (center_y + offset) as i32;
-        if y >= 0 && y < height as i32 {
-            for x in 0..width {
-                if let Some(pixel) = img.get_pixel_mut_checked(x, y as u32) {
-                    *pixel = grid_color;
-                }
+impl Canvas for Raster {
+    fn height(&self) -> u32 {
+        (self.img.height() as f32 / self.factor).round() as u32
+    }
+
+    fn fill_rect(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color32) {
+        let color = rgba(color);
+        let f = self.factor;
+        let (x, y, w, h) = (x * f, y * f, w * f, h * f);
+        let (x0, y0) = (x.max(0.0) as u32, y.max(0.0) as u32);
+        let x1 = ((x + w).max(0.0) as u32).min(self.img.width());
+        let y1 = ((y + h).max(0.0) as u32).min(self.img.height());
+        for py in y0..y1 {
+            for px in x0..x1 {
+                self.img.put_pixel(px, py, color);
             }
         }
     }
+}
 
-    let axis_color = Rgba([0u8, 0u8, 0u8, 255u8]);
+impl PlotTarget for Raster {
+    /// Segment anti-crénelé (algorithme de Wu étendu à un trait épais) : le
+    /// long de l'axe principal, chaque pixel est couvert selon sa part de trait
+    fn line(&mut self, from: Pos2, to: Pos2, stroke: Stroke) {
+        let color = rgba(stroke.color);
+        let f = self.factor;
+        let (mut x0, mut y0, mut x1, mut y1) = (from.x * f, from.y * f, to.x * f, to.y * f);
+        // Épaisseur à l'échelle 1, augmentée avec la résolution
+        let width = stroke.width * f;
+        // Lignes de grille et axes centrés sur des pixels entiers, sans flou
+        if x0 == x1 {
+            x0 = x0.floor() + 0.5;
+            x1 = x0;
+        } else if y0 == y1 {
+            y0 = y0.floor() + 0.5;
+            y1 = y0;
+        }
 
-    let cy = center_y as i32;
-    for x in 0..width {
-        for dy in -1..=1 {
-            let y = cy + dy;
-            if y >= 0 && y < height as i32 {
-                if let Some(pixel) = img.get_pixel_mut_checked(x, y as u32) {
-                    *pixel = axis_color;
+        let steep = (y1 - y0).abs() > (x1 - x0).abs();
+        if steep {
+            std::mem::swap(&mut x0, &mut y0);
+            std::mem::swap(&mut x1, &mut y1);
+        }
+        if x0 > x1 {
+            std::mem::swap(&mut x0, &mut x1);
+            std::mem::swap(&mut y0, &mut y1);
+        }
+        let gradient = if x1 - x0 > f32::EPSILON {
+            (y1 - y0) / (x1 - x0)
+        } else {
+            0.0
+        };
+        // Épaisseur mesurée le long de l'axe secondaire
+        let half = width * (1.0 + gradient * gradient).sqrt() / 2.0;
+
+        for x in x0.round() as i32..=x1.round() as i32 {
+            let center = y0 + gradient * (x as f32 + 0.5 - x0);
+            let (top, bottom) = (center - half, center + half);
+            for y in top.floor() as i32..=bottom.floor() as i32 {
+                let coverage = bottom.min(y as f32 + 1.0) - top.max(y as f32);
+                let (px, py) = if steep { (y, x) } else { (x, y) };
+                if px >= 0 && py >= 0 && coverage > 0.0 {
+                    blend_pixel(&mut self.img, px as u32, py as u32, color, coverage);
                 }
             }
         }
     }
 
-    let cx = center_x as i32;
-    for y in 0..height {
-        for dx in -1..=1 {
-            let x = cx + dx;
-            if x >= 0 && x < width as i32 {
-                if let Some(pixel) = img.get_pixel_mut_checked(x as u32, y) {
-                    *pixel = axis_color;
-                }
+    fn text(&mut self, pos: Pos2, anchor: Align2, text: &str, size: f32, color: Color32) {
+        let font = self.font.get_or_insert_with(|| {
+            let font = ui_font();
+            if font.is_none() {
+                warn!("Police indisponible : textes exportés omis");
             }
+            font
+        });
+        if let Some(font) = font {
+            let origin = text_origin(pos, anchor, text, size) * self.factor;
+            let size = size * self.factor;
+            draw_text(&mut self.img, font, size, text, origin.x, origin.y, rgba(color));
         }
     }
+}
 
-    let curve_color = Rgba([0u8, 100u8, 255u8, 255u8]);
+/// Image vectorielle : éléments SVG accumulés dans l'ordre de dessin ; la
+/// taille affichée est multipliée par `factor`, pas le repère de dessin
+struct Svg {
+    width: u32,
+    height: u32,
+    factor: f32,
+    body: String,
+}
 
-    for i in 0..curve.voltage.len() {
-        let v = curve.voltage[i];
-        let c = curve.current[i];
+impl Svg {
+    fn new(width: u32, height: u32, factor: f32) -> Self {
+        Self {
+            width,
+            height,
+            factor,
+            body: String::new(),
+        }
+    }
 
-        let x = (center_x + v * scale) as i32;
-        let y = (center_y - c * scale) as i32;
+    fn save(&self, path: &Path) -> Result<(), String> {
+        let content = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             viewBox=\"0 0 {w} {h}\">\n{}</svg>\n",
+            scaled(self.width, self.factor),
+            scaled(self.height, self.factor),
+            self.body,
+            w = self.width,
+            h = self.height
+        );
+        fs::write(path, content).map_err(|e| format!("Erreur sauvegarde SVG: {}", e))
+    }
+}
 
-        for dy in -1..=1 {
-            for dx in -1..=1 {
-                let px = x + dx;
-                let py = y + dy;
+impl Canvas for Svg {
+    fn height(&self) -> u32 {
+        self.height
+    }
 
-                if px >= 0 && px < width as i32 && py >= 0 && py < height as i32 {
-                    if let Some(pixel) = img.get_pixel_mut_checked(px as u32, py as u32) {
-                        *pixel = curve_color;
-                    }
-                }
-            }
+    fn fill_rect(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color32) {
+        let _ = writeln!(
+            self.body,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+            x,
+            y,
+            w,
+            h,
+            svg_color(color)
+        );
+    }
+}
+
+impl PlotTarget for Svg {
+    fn line(&mut self, from: Pos2, to: Pos2, stroke: Stroke) {
+        let _ = writeln!(
+            self.body,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"{}\" \
+             stroke-linecap=\"round\"/>",
+            from.x,
+            from.y,
+            to.x,
+            to.y,
+            svg_color(stroke.color),
+            stroke.width
+        );
+    }
+
+    fn circle(&mut self, center: Pos2, radius: f32, stroke: Stroke) {
+        let _ = writeln!(
+            self.body,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" stroke=\"{}\" \
+             stroke-width=\"{}\"/>",
+            center.x,
+            center.y,
+            radius,
+            svg_color(stroke.color),
+            stroke.width
+        );
+    }
+
+    fn text(&mut self, pos: Pos2, anchor: Align2, text: &str, size: f32, color: Color32) {
+        let origin = text_origin(pos, anchor, text, size);
+        let escaped = text
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+        let _ = writeln!(
+            self.body,
+            "<text x=\"{}\" y=\"{}\" font-family=\"Ubuntu, sans-serif\" font-size=\"{}\" \
+             dominant-baseline=\"hanging\" fill=\"{}\">{}</text>",
+            origin.x,
+            origin.y,
+            size,
+            svg_color(color),
+            escaped
+        );
+    }
+}
+
+fn svg_color(color: Color32) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+/// Document PDF d'une page écrit avec pdf-writer : repère en pixels, origine
+/// en haut à gauche comme les autres cibles, police standard Helvetica
+struct Pdf {
+    width: u32,
+    height: u32,
+    content: Content,
+}
+
+impl Pdf {
+    fn new(width: u32, height: u32) -> Self {
+        let mut content = Content::new();
+        content
+            .save_state()
+            .transform([
+                PDF_POINTS_PER_PIXEL,
+                0.0,
+                0.0,
+                -PDF_POINTS_PER_PIXEL,
+                0.0,
+                height as f32 * PDF_POINTS_PER_PIXEL,
+            ])
+            .set_line_cap(LineCapStyle::RoundCap);
+        Self {
+            width,
+            height,
+            content,
         }
     }
 
-    img.save(filename)
-        .map_err(|e| format!("Erreur sauvegarde PNG: {}", e))?;
+    fn save(mut self, path: &Path) -> Result<(), String> {
+        self.content.restore_state();
+        let catalog = Ref::new(1);
+        let pages = Ref::new(2);
+        let page = Ref::new(3);
+        let font = Ref::new(4);
+        let contents = Ref::new(5);
+
+        let mut pdf = pdf_writer::Pdf::new();
+        pdf.catalog(catalog).pages(pages);
+        pdf.pages(pages).kids([page]).count(1);
+        let mut writer = pdf.page(page);
+        writer
+            .parent(pages)
+            .media_box(PdfRect::new(
+                0.0,
+                0.0,
+                self.width as f32 * PDF_POINTS_PER_PIXEL,
+                self.height as f32 * PDF_POINTS_PER_PIXEL,
+            ))
+            .contents(contents);
+        writer.resources().fonts().pair(PDF_FONT, font);
+        writer.finish();
+        pdf.type1_font(font)
+            .base_font(Name(b"Helvetica"))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+        pdf.stream(contents, &self.content.finish());
+        fs::write(path, pdf.finish()).map_err(|e| format!("Erreur sauvegarde PDF: {}", e))
+    }
+}
 
-    println!("Image sauvegardée : {}", filename);
-    Ok(())
+impl Canvas for Pdf {
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn fill_rect(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color32) {
+        let [r, g, b] = pdf_color(color);
+        self.content.set_fill_rgb(r, g, b).rect(x, y, w, h).fill_nonzero();
+    }
 }
 
-pub fn save_dual_curves_as_png(data: &DualCurveData, filename: &str) -> Result<(), String> {
-    let width = 1600;
-    let height = 800;
+impl PlotTarget for Pdf {
+    fn line(&mut self, from: Pos2, to: Pos2, stroke: Stroke) {
+        let [r, g, b] = pdf_color(stroke.color);
+        self.content
+            .set_stroke_rgb(r, g, b)
+            .set_line_width(stroke.width)
+            .move_to(from.x, from.y)
+            .line_to(to.x, to.y)
+            .stroke();
+    }
 
-    let mut img = ImageBuffer::from_fn(width, height, |_, _| {
-        Rgba([255u8, 255u8, 255u8, 255u8])
-    });
+    fn text(&mut self, pos: Pos2, anchor: Align2, text: &str, size: f32, color: Color32) {
+        let origin = text_origin(pos, anchor, text, size);
+        let [r, g, b] = pdf_color(color);
+        // Le texte est redressé dans le repère retourné, à partir de sa ligne de base
+        self.content
+            .begin_text()
+            .set_font(PDF_FONT, size)
+            .set_fill_rgb(r, g, b)
+            .set_text_matrix([1.0, 0.0, 0.0, -1.0, origin.x, origin.y + 0.8 * size])
+            .show(Str(&win_ansi(text)))
+            .end_text();
+    }
+}
 
-    if let Some(ch0) = &data.channel0 {
-        draw_curve_to_image(
-            &mut img,
-            ch0,
-            0,
-            0,
-            800,
-            800,
-            Rgba([255u8, 100u8, 0u8, 255u8]),
-        );
+fn pdf_color(color: Color32) -> [f32; 3] {
+    let [r, g, b, _] = color.to_srgba_unmultiplied().map(|c| c as f32 / 255.0);
+    [r, g, b]
+}
+
+/// Texte encodé en WinAnsi pour la police Helvetica ; les caractères absents
+/// de la police (emoji…) sont omis
+fn win_ansi(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for c in text.chars() {
+        match c {
+            'Ω' => bytes.extend_from_slice(b"Ohm"),
+            '—' => bytes.push(0x97),
+            '…' => bytes.push(0x85),
+            ' '..='~' | '\u{a0}'..='\u{ff}' => bytes.push(c as u8),
+            _ => {}
+        }
     }
+    bytes
+}
 
-    if let Some(ch1) = &data.channel1 {
-        draw_curve_to_image(
-            &mut img,
-            ch1,
-            800,
-            0,
-            800,
-            800,
-            Rgba([0u8, 100u8, 255u8, 255u8]),
-        );
+/// Exporter la courbe d'un canal (800×800), dans la couleur de son canal ;
+/// la légende de `text` (mesures, réglages…) est inscrite en haut à gauche
+pub fn save_curve(
+    curve: &CurveData,
+    settings: &Settings,
+    annotations: &[Annotation],
+    text: &ImageText,
+    format: ImageFormat,
+    path: &Path,
+) -> Result<(), String> {
+    let render = |canvas: &mut dyn Canvas| {
+        let frame = plot_frame(0.0, 0.0, 800.0);
+        let color = channel_color(settings, curve.channel);
+        draw_plot(canvas, curve, settings, frame, color);
+        draw_annotations(canvas, annotations, frame);
+        draw_caption(canvas, text.caption);
+        draw_notes(canvas, text.notes);
+    };
+    save_rendered(800, 800, Output::of(settings, text), format, path, render)?;
+
+    info!("Image sauvegardée : {}", path.display());
+    Ok(())
+}
+
+/// Exporter des balayages successifs en GIF animé (800×800 par canal, côte à
+/// côte) : une image par balayage, affichée `delay_ms` ms, jouée en boucle
+pub fn save_animation(
+    sweeps: &[Vec<CurveData>],
+    settings: &Settings,
+    delay_ms: u32,
+    path: &Path,
+) -> Result<(), String> {
+    let gif_error = |e: image::ImageError| format!("Erreur sauvegarde GIF: {}", e);
+    let file = fs::File::create(path)
+        .map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+    let mut encoder = GifEncoder::new_with_speed(file, 10);
+    encoder.set_repeat(Repeat::Infinite).map_err(gif_error)?;
+
+    for (i, curves) in sweeps.iter().enumerate() {
+        let curves: Vec<Option<&CurveData>> = curves.iter().map(Some).collect();
+        let mut canvas = plot_columns(&curves, settings);
+        // Numéro du balayage, dans le coin libre en haut à gauche
+        let counter = format!("{} / {}", i + 1, sweeps.len());
+        let at = egui::pos2(10.0, 10.0);
+        canvas.text(at, Align2::LEFT_TOP, &counter, ANNOTATION_TEXT_SIZE, BLACK);
+
+        let delay = Delay::from_numer_denom_ms(delay_ms, 1);
+        encoder
+            .encode_frame(Frame::from_parts(canvas.img, 0, 0, delay))
+            .map_err(gif_error)?;
     }
 
-    img.save(filename)
-        .map_err(|e| format!("Erreur sauvegarde PNG: {}", e))?;
+    info!("Animation sauvegardée : {}", path.display());
+    Ok(())
+}
 
-    println!("Image dual sauvegardée : {}", filename);
+/// Image des tracés côte à côte (800×800 par colonne), pour la vidéo
+pub fn plot_image(
+    curves: &[Option<&CurveData>],
+    settings: &Settings,
+) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+    plot_columns(curves, settings).img
+}
+
+/// Tracés sur fond blanc, une colonne de 800×800 par entrée de `curves` ;
+/// `None` laisse la grille vide
+fn plot_columns(curves: &[Option<&CurveData>], settings: &Settings) -> Raster {
+    let width = 800 * curves.len().max(1) as u32;
+    let mut canvas = Raster::new(width, 800, 1.0);
+    canvas.fill_rect(0.0, 0.0, width as f32, 800.0, WHITE);
+    for (column, curve) in curves.iter().enumerate() {
+        let frame = plot_frame(column as f32 * 800.0, 0.0, 800.0);
+        match curve {
+            Some(curve) => {
+                let color = channel_color(settings, curve.channel);
+                draw_plot(&mut canvas, curve, settings, frame, color);
+            }
+            None => plot_renderer::draw_grid(&mut canvas, settings, frame, &STYLE),
+        }
+    }
+    canvas
+}
+
+/// Exporter les deux canaux côte à côte (1600×800)
+pub fn save_dual_curves(
+    data: &DualCurveData,
+    settings: &Settings,
+    annotations: &[Annotation],
+    text: &ImageText,
+    format: ImageFormat,
+    path: &Path,
+) -> Result<(), String> {
+    let render = |canvas: &mut dyn Canvas| {
+        let frames = [0.0, 800.0].map(|x| plot_frame(x, 0.0, 800.0));
+        for (frame, curve) in frames.iter().zip([&data.channel0, &data.channel1]) {
+            if let Some(curve) = curve {
+                let color = channel_color(settings, curve.channel);
+                draw_plot(canvas, curve, settings, *frame, color);
+            }
+        }
+        // Les annotations s'appliquent aux deux tracés, comme à l'écran
+        for frame in frames {
+            draw_annotations(canvas, annotations, frame);
+        }
+        draw_caption(canvas, text.caption);
+        draw_notes(canvas, text.notes);
+    };
+    save_rendered(1600, 800, Output::of(settings, text), format, path, render)?;
+
+    info!("Image dual sauvegardée : {}", path.display());
     Ok(())
 }
 
-fn draw_curve_to_image(
-    img: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
-    curve: &CurveData,
-    offset_x: u32,
-    offset_y: u32,
-    w: u32,
-    h: u32,
-    curve_color: Rgba<u8>,
-) {
-    let center_x = offset_x as f32 + w as f32 / 2.0;
-    let center_y = offset_y as f32 + h as f32 / 2.0;
-    let scale = (w.min(h) as f32) * 0.45;
-
-    let grid_color = Rgba([200u8, 200u8, 200u8, 255u8]);
-    for i in -10..=10 {
-        let off = (i as f32) * scale / 10.0;
-
-        let x = (center_x + off) as i32;
-        if x >= offset_x as i32 && x < (offset_x + w) as i32 {
-            for y in offset_y..(offset_y + h) {
-                if let Some(pixel) = img.get_pixel_mut_checked(x as u32, y) {
-                    *pixel = grid_color;
+/// Exporter les deux canaux superposés sur les mêmes axes (800×800), avec les
+/// couleurs de l'écran, comme le mode d'affichage superposé
+pub fn save_overlay_curves(
+    data: &DualCurveData,
+    settings: &Settings,
+    annotations: &[Annotation],
+    text: &ImageText,
+    format: ImageFormat,
+    path: &Path,
+) -> Result<(), String> {
+    let render = |canvas: &mut dyn Canvas| {
+        let frame = plot_frame(0.0, 0.0, 800.0);
+        plot_renderer::draw_grid(canvas, settings, frame, &STYLE);
+        let curves = [&data.channel0, &data.channel1];
+        for (row, curve) in curves.into_iter().flatten().enumerate() {
+            // Couleurs de l'écran, sauf si le style d'export en impose
+            let color = match settings.export_colors {
+                Some(_) => channel_color(settings, curve.channel),
+                None => {
+                    let [r, g, b] = settings.plot.channel_rgb(curve.channel);
+                    Color32::from_rgb(r, g, b)
                 }
+            };
+            let stroke = Stroke::new(settings.plot.trace_width(), color);
+            plot_renderer::draw_trace(canvas, curve, settings, frame, stroke);
+            // Légende des canaux en haut à droite, l'une sous l'autre
+            if settings.export_labels {
+                let size = STYLE.name_size;
+                plot_renderer::draw_channel_name(canvas, curve.channel, frame, row, size, color);
             }
         }
+        if settings.export_labels {
+            plot_renderer::draw_axis_names(canvas, &settings.axes, frame, &STYLE);
+        }
+        draw_annotations(canvas, annotations, frame);
+        draw_caption(canvas, text.caption);
+        draw_notes(canvas, text.notes);
+    };
+    save_rendered(800, 800, Output::of(settings, text), format, path, render)?;
 
-        let y = (center_y + off) as i32;
-        if y >= offset_y as i32 && y < (offset_y + h) as i32 {
-            for x in offset_x..(offset_x + w) {
-                if let Some(pixel) = img.get_pixel_mut_checked(x, y as u32) {
-                    *pixel = grid_color;
-                }
+    info!("Image superposée sauvegardée : {}", path.display());
+    Ok(())
+}
+
+/// Page A4 (impression, rapport) : lignes d'en-tête (réglages, date…) puis
+/// tracé de chaque canal
+pub fn save_page(
+    data: &DualCurveData,
+    channels: &[u8],
+    settings: &Settings,
+    annotations: &[Annotation],
+    header: &[String],
+    format: ImageFormat,
+    path: &Path,
+) -> Result<(), String> {
+    let render = |canvas: &mut dyn Canvas| {
+        for (i, line) in header.iter().enumerate() {
+            let y = PAGE_MARGIN + i as u32 * PAGE_LINE_HEIGHT;
+            let pos = egui::pos2(PAGE_MARGIN as f32, y as f32);
+            canvas.text(pos, Align2::LEFT_TOP, line, ANNOTATION_TEXT_SIZE, BLACK);
+        }
+
+        // Tracés côte à côte sous l'en-tête, comme l'export double
+        let top = 2 * PAGE_MARGIN + header.len() as u32 * PAGE_LINE_HEIGHT;
+        let curves: Vec<&CurveData> =
+            channels.iter().filter_map(|&c| data.channel(c)).collect();
+        let size = ((PAGE_WIDTH - 2 * PAGE_MARGIN) / curves.len().max(1) as u32) as f32;
+        for (i, curve) in curves.iter().enumerate() {
+            let frame = plot_frame(PAGE_MARGIN as f32 + i as f32 * size, top as f32, size);
+            let color = channel_color(settings, curve.channel);
+            draw_plot(canvas, curve, settings, frame, color);
+            draw_annotations(canvas, annotations, frame);
+        }
+    };
+    save_rendered(PAGE_WIDTH, PAGE_HEIGHT, Output::PAGE, format, path, render)?;
+
+    info!("Page sauvegardée : {}", path.display());
+    Ok(())
+}
+
+/// Rapport de comparaison : en-tête puis, pour chaque canal, la courbe
+/// superposée à sa référence (en tirets), l'écart point par point, le verdict
+/// et les statistiques d'écart
+pub fn save_comparison(
+    deviations: &[ChannelDeviation],
+    header: &[String],
+    settings: &Settings,
+    format: ImageFormat,
+    path: &Path,
+) -> Result<(), String> {
+    const MARGIN: f32 = 40.0;
+    const CHART_HEIGHT: f32 = 180.0;
+    let line_height = PAGE_LINE_HEIGHT as f32;
+    let width = 800 * deviations.len().max(1) as u32;
+    let plot_top = MARGIN + header.len() as f32 * line_height + MARGIN / 2.0;
+    let chart_top = plot_top + 800.0 + MARGIN;
+    let stats_top = chart_top + CHART_HEIGHT + MARGIN;
+    let height = (stats_top + 2.0 * line_height + MARGIN) as u32;
+
+    let render = |canvas: &mut dyn Canvas| {
+        for (i, line) in header.iter().enumerate() {
+            let pos = egui::pos2(MARGIN, MARGIN + i as f32 * line_height);
+            canvas.text(pos, Align2::LEFT_TOP, line, ANNOTATION_TEXT_SIZE, BLACK);
+        }
+        for (column, deviation) in deviations.iter().enumerate() {
+            let left = column as f32 * 800.0;
+            let frame = plot_frame(left, plot_top, 800.0);
+            let color = channel_color(settings, deviation.live.channel);
+            let stroke = Stroke::new(settings.plot.trace_width(), REFERENCE_COLOR);
+            draw_plot(canvas, &deviation.live, settings, frame, color);
+            plot_renderer::draw_curve(
+                canvas,
+                &deviation.reference,
+                &settings.axes,
+                frame,
+                stroke,
+                true,
+            );
+            // Nom de la référence sous celui du canal
+            let size = STYLE.name_size;
+            let pos = frame.rect.right_top() + egui::vec2(-10.0, 10.0 + size * 1.3);
+            let name = &deviation.reference_name;
+            canvas.text(pos, Align2::RIGHT_TOP, name, size, REFERENCE_COLOR);
+
+            let chart = Rect::from_min_size(
+                egui::pos2(left + MARGIN, chart_top),
+                egui::vec2(800.0 - 2.0 * MARGIN, CHART_HEIGHT),
+            );
+            draw_deviation_chart(canvas, &deviation.distances, chart, color);
+
+            let lines = [deviation.verdict_line(), deviation.stats_line()];
+            for (i, line) in lines.iter().enumerate() {
+                let pos = egui::pos2(left + MARGIN, stats_top + i as f32 * line_height);
+                canvas.text(pos, Align2::LEFT_TOP, line, ANNOTATION_TEXT_SIZE, BLACK);
             }
         }
+    };
+    save_rendered(width, height, Output::PAGE, format, path, render)?;
+
+    info!("Rapport de comparaison sauvegardé : {}", path.display());
+    Ok(())
+}
+
+/// Écart de chaque point à la référence dans `rect` : cadre, courbe de
+/// l'écart, moyenne en gris et échelle verticale
+fn draw_deviation_chart(canvas: &mut dyn Canvas, distances: &[f32], rect: Rect, color: Color32) {
+    let size = STYLE.tick_size;
+    let frame = Stroke::new(STYLE.grid_width, STYLE.grid);
+    let corners = [rect.left_top(), rect.right_top(), rect.right_bottom(), rect.left_bottom()];
+    for (i, &corner) in corners.iter().enumerate() {
+        canvas.line(corner, corners[(i + 1) % 4], frame);
+    }
+    let title = rect.left_top() - egui::vec2(0.0, 4.0);
+    canvas.text(title, Align2::LEFT_BOTTOM, &tr("comparison-deviation"), size, BLACK);
+    if distances.len() < 2 {
+        return;
     }
 
-    let axis_color = Rgba([0u8, 0u8, 0u8, 255u8]);
-    let cy = center_y as i32;
-    for x in offset_x..(offset_x + w) {
-        for dy in -1..=1 {
-            let y = cy + dy;
-            if y >= offset_y as i32 && y < (offset_y + h) as i32 {
-                if let Some(pixel) = img.get_pixel_mut_checked(x, y as u32) {
-                    *pixel = axis_color;
-                }
-            }
+    let top = distances.iter().copied().fold(0.0, f32::max).max(1e-3);
+    let last = (distances.len() - 1) as f32;
+    let point = |k: usize, d: f32| {
+        egui::pos2(
+            rect.left() + k as f32 / last * rect.width(),
+            rect.bottom() - d / top * rect.height(),
+        )
+    };
+    let mean = distances.iter().sum::<f32>() / distances.len() as f32;
+    let grey = Stroke::new(STYLE.grid_width, Color32::GRAY);
+    canvas.line(point(0, mean), point(distances.len() - 1, mean), grey);
+    let points: Vec<Pos2> = distances.iter().enumerate().map(|(k, &d)| point(k, d)).collect();
+    canvas.polyline(&points, Stroke::new(1.5, color));
+
+    let scale = rect.right_top() - egui::vec2(4.0, -4.0);
+    canvas.text(scale, Align2::RIGHT_TOP, &format!("{:.3}", top), size, BLACK);
+    let count = rect.right_bottom() + egui::vec2(0.0, 4.0);
+    canvas.text(count, Align2::RIGHT_TOP, &format!("{}", last), size, BLACK);
+    canvas.text(rect.left_bottom() + egui::vec2(0.0, 4.0), Align2::LEFT_TOP, "0", size, BLACK);
+}
+
+/// Textes d'une image exportée : notes et légende inscrites sur l'image,
+/// métadonnées (mot-clé, valeur) enregistrées dans les PNG
+#[derive(Default)]
+pub struct ImageText<'a> {
+    pub notes: &'a str,
+    pub caption: &'a [String],
+    pub metadata: &'a [(&'a str, String)],
+}
+
+/// Options de rendu d'un export
+#[derive(Debug, Clone, Copy)]
+struct Output<'a> {
+    /// Facteur entre la mise en page et l'image (PNG, SVG)
+    factor: f32,
+    /// Fond transparent : seuls la grille, les axes et les courbes sont dessinés
+    transparent: bool,
+    /// Textes des PNG
+    metadata: &'a [(&'a str, String)],
+}
+
+impl<'a> Output<'a> {
+    /// Page imprimable : 150 ppp sur fond blanc
+    const PAGE: Output<'static> = Output {
+        factor: 1.0,
+        transparent: false,
+        metadata: &[],
+    };
+
+    fn of(settings: &Settings, text: &ImageText<'a>) -> Self {
+        Self {
+            factor: settings.export_factor(),
+            transparent: settings.export_transparent,
+            metadata: text.metadata,
         }
     }
+}
 
-    let cx = center_x as i32;
-    for y in offset_y..(offset_y + h) {
-        for dx in -1..=1 {
-            let x = cx + dx;
-            if x >= offset_x as i32 && x < (offset_x + w) as i32 {
-                if let Some(pixel) = img.get_pixel_mut_checked(x as u32, y) {
-                    *pixel = axis_color;
-                }
-            }
+/// Dessiner sur la cible correspondant au format puis enregistrer ; `width` et
+/// `height` sont la mise en page
+fn save_rendered(
+    width: u32,
+    height: u32,
+    output: Output,
+    format: ImageFormat,
+    path: &Path,
+    render: impl FnOnce(&mut dyn Canvas),
+) -> Result<(), String> {
+    let draw = |canvas: &mut dyn Canvas| {
+        if !output.transparent {
+            canvas.fill_rect(0.0, 0.0, width as f32, height as f32, WHITE);
+        }
+        render(canvas);
+    };
+    match format {
+        ImageFormat::Png => {
+            let mut canvas = Raster::new(width, height, output.factor);
+            draw(&mut canvas);
+            canvas.save(path, output.metadata)
+        }
+        ImageFormat::Svg => {
+            let mut canvas = Svg::new(width, height, output.factor);
+            draw(&mut canvas);
+            canvas.save(path)
+        }
+        ImageFormat::Pdf => {
+            let mut canvas = Pdf::new(width, height);
+            draw(&mut canvas);
+            canvas.save(path)
         }
     }
+}
 
-    for i in 0..curve.voltage.len() {
-        let v = curve.voltage[i];
-        let c = curve.current[i];
+/// Grille, axes, courbe et nom du canal dans une zone de l'image, dessinés
+/// comme à l'écran
+fn draw_plot(
+    canvas: &mut dyn Canvas,
+    curve: &CurveData,
+    settings: &Settings,
+    frame: PlotFrame,
+    color: Color32,
+) {
+    plot_renderer::draw_grid(canvas, settings, frame, &STYLE);
+    let stroke = Stroke::new(settings.plot.trace_width(), color);
+    plot_renderer::draw_trace(canvas, curve, settings, frame, stroke);
+    if settings.export_labels {
+        plot_renderer::draw_axis_names(canvas, &settings.axes, frame, &STYLE);
+        plot_renderer::draw_channel_name(canvas, curve.channel, frame, 0, STYLE.name_size, color);
+    }
+}
 
-        let x = (center_x + v * scale) as i32;
-        let y = (center_y - c * scale) as i32;
+/// Couleur d'un canal dans les exports : celle du style d'export, orange
+/// (CH0) ou bleu (CH1) par défaut
+fn channel_color(settings: &Settings, channel: u8) -> Color32 {
+    let colors = settings.export_colors.unwrap_or(Settings::EXPORT_COLORS);
+    let [r, g, b] = colors[channel.min(1) as usize];
+    Color32::from_rgb(r, g, b)
+}
 
-        for dy in -1..=1 {
-            for dx in -1..=1 {
-                let px = x + dx;
-                let py = y + dy;
+/// Annotations en noir, comme à l'écran
+fn draw_annotations(canvas: &mut dyn Canvas, annotations: &[Annotation], frame: PlotFrame) {
+    let stroke = Stroke::new(2.0, BLACK);
+    plot_renderer::draw_annotations(canvas, annotations, frame, stroke, ANNOTATION_TEXT_SIZE);
+}
 
-                if px >= offset_x as i32
-                    && px < (offset_x + w) as i32
-                    && py >= offset_y as i32
-                    && py < (offset_y + h) as i32
-                {
-                    if let Some(pixel) = img.get_pixel_mut_checked(px as u32, py as u32) {
-                        *pixel = curve_color;
-                    }
-                }
-            }
+/// Largeur approximative d'un texte : pas de mesure commune aux trois cibles
+fn text_width(text: &str, size: f32) -> f32 {
+    text.chars().count() as f32 * size * 0.5
+}
+
+/// Coin haut-gauche d'un texte de hauteur `size` placé selon `anchor`
+fn text_origin(pos: Pos2, anchor: Align2, text: &str, size: f32) -> Pos2 {
+    let width = text_width(text, size);
+    pos - egui::vec2(anchor.x().to_factor() * width, anchor.y().to_factor() * size)
+}
+
+/// Légende de mesures en haut à gauche, sur fond blanc pour rester lisible
+/// par-dessus la grille
+fn draw_caption(canvas: &mut dyn Canvas, caption: &[String]) {
+    if caption.is_empty() {
+        return;
+    }
+    let size = ANNOTATION_TEXT_SIZE;
+    let line_height = size * 1.3;
+    let width = caption.iter().map(|l| text_width(l, size)).fold(0.0, f32::max) + 16.0;
+    let height = line_height * caption.len() as f32 + 8.0;
+    canvas.fill_rect(6.0, 6.0, width, height, WHITE);
+    for (i, line) in caption.iter().enumerate() {
+        let pos = egui::pos2(14.0, 10.0 + line_height * i as f32);
+        canvas.text(pos, Align2::LEFT_TOP, line, size, BLACK);
+    }
+}
+
+/// Notes de la session en bas à gauche de l'image, une ligne par ligne de texte
+fn draw_notes(canvas: &mut dyn Canvas, notes: &str) {
+    let lines: Vec<&str> = notes.lines().filter(|l| !l.trim().is_empty()).collect();
+    let line_height = ANNOTATION_TEXT_SIZE * 1.3;
+    let top = canvas.height() as f32 - 10.0 - line_height * lines.len() as f32;
+    for (i, line) in lines.iter().enumerate() {
+        let pos = egui::pos2(10.0, top + line_height * i as f32);
+        canvas.text(pos, Align2::LEFT_TOP, line, ANNOTATION_TEXT_SIZE, BLACK);
+    }
+}
+
+/// Police de l'interface egui, réutilisée pour le texte des exports
+fn ui_font() -> Option<FontArc> {
+    let fonts = egui::FontDefinitions::default();
+    let data = fonts.font_data.get("Ubuntu-Light")?;
+    FontArc::try_from_vec(data.font.to_vec()).ok()
+}
+
+/// Écrire du texte (coin haut-gauche en `x`, `y`) avec anticrénelage
+fn draw_text(
+    img: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+    font: &FontArc,
+    size: f32,
+    text: &str,
+    x: f32,
+    y: f32,
+    color: Rgba<u8>,
+) {
+    let scaled = font.as_scaled(PxScale::from(size));
+    let mut caret = ab_glyph::point(x, y + scaled.ascent());
+    let mut previous = None;
+
+    for c in text.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(prev) = previous {
+            caret.x += scaled.kern(prev, id);
         }
+        let glyph = id.with_scale_and_position(scaled.scale(), caret);
+        caret.x += scaled.h_advance(id);
+        previous = Some(id);
+
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outlined.px_bounds();
+        outlined.draw(|gx, gy, coverage| {
+            let px = bounds.min.x as i32 + gx as i32;
+            let py = bounds.min.y as i32 + gy as i32;
+            if px >= 0 && py >= 0 {
+                blend_pixel(img, px as u32, py as u32, color, coverage);
+            }
+        });
     }
 }
 
+/// Mélanger une couleur sur un pixel selon une couverture dans [0, 1]
+fn blend_pixel(
+    img: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+    x: u32,
+    y: u32,
+    color: Rgba<u8>,
+    coverage: f32,
+) {
+    let Some(pixel) = img.get_pixel_mut_checked(x, y) else {
+        return;
+    };
+    // Composition « par-dessus », correcte aussi sur un fond transparent
+    let alpha = coverage.clamp(0.0, 1.0) * color[3] as f32 / 255.0;
+    let below = pixel[3] as f32 / 255.0 * (1.0 - alpha);
+    let total = alpha + below;
+    if total <= 0.0 {
+        return;
+    }
+    for c in 0..3 {
+        pixel[c] = ((color[c] as f32 * alpha + pixel[c] as f32 * below) / total) as u8;
+    }
+    pixel[3] = (total * 255.0).round() as u8;
+}
+
+/// Dimension en pixels d'une longueur de mise en page
+fn scaled(length: u32, factor: f32) -> u32 {
+    (length as f32 * factor).round().max(1.0) as u32
+}
+
+/// Couleur egui (prémultipliée) en pixel RGBA
+fn rgba(color: Color32) -> Rgba<u8> {
+    Rgba(color.to_srgba_unmultiplied())
+}
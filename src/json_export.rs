@@ -0,0 +1,194 @@
+// src/json_export.rs
+
+use crate::analysis::Analysis;
+use crate::curve::{CurveData, DualCurveData};
+use crate::timestamp::rfc3339_timestamp;
+use crate::session::Session;
+use crate::settings::{DisplayMode, Settings};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::info;
+
+/// Texte JSON indenté d'une valeur sérialisable. Les types exportés n'ont
+/// que des clés textuelles : la sérialisation ne peut pas échouer
+pub fn pretty(value: &impl Serialize) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+/// Document d'un export JSON
+#[derive(Serialize)]
+struct Document<'a> {
+    application: &'static str,
+    version: &'static str,
+    exported_at: u64,
+    /// Même instant en RFC 3339 (UTC)
+    exported_at_utc: String,
+    source: String,
+    notes: &'a str,
+    settings: ExportSettings,
+    device: Vec<DeviceValue<'a>>,
+    calibration: CalibrationJson,
+    curves: Vec<CurveJson<'a>>,
+}
+
+/// Préférences d'affichage utiles à la relecture de l'export
+#[derive(Serialize)]
+struct ExportSettings {
+    /// Nom du mode dans le fichier de configuration
+    display_mode: DisplayMode,
+    log_current: bool,
+    swap_axes: bool,
+    grid_divisions: u32,
+}
+
+/// Réglage appliqué au boîtier
+#[derive(Serialize)]
+struct DeviceValue<'a> {
+    id: &'a str,
+    value: &'a str,
+}
+
+#[derive(Serialize)]
+struct CalibrationJson {
+    enabled: bool,
+    volts_full_scale: f32,
+    milliamps_full_scale: f32,
+}
+
+/// Courbe exportée ; les points sont omis des métadonnées
+#[derive(Serialize)]
+struct CurveJson<'a> {
+    channel: u8,
+    points: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    voltage: Option<&'a [f32]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current: Option<&'a [f32]>,
+    analysis: AnalysisJson,
+}
+
+/// Résultats d'analyse d'une courbe ; les valeurs non finies deviennent null
+#[derive(Serialize)]
+struct AnalysisJson {
+    component: String,
+    slope: f32,
+    resistance: Option<f32>,
+    resistance_text: String,
+    forward_voltage: Option<f32>,
+    voltage_span: f32,
+    current_span: f32,
+    match_score: Option<f32>,
+}
+
+/// Exporter les courbes affichées en JSON : points, réglages, calibration,
+/// horodatage, notes et résultats d'analyse. `match_scores` donne le score de
+/// correspondance (%) des canaux comparés à une signature.
+pub fn save_curves_as_json(
+    data: &DualCurveData,
+    channels: &[u8],
+    settings: &Settings,
+    session: &Session,
+    match_scores: &[(u8, f32)],
+    path: &Path,
+) -> Result<(), String> {
+    let mut content = pretty(&document(data, channels, settings, session, match_scores, true));
+    content.push('\n');
+    fs::write(path, content)
+        .map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+
+    info!("JSON sauvegardé : {}", path.display());
+    Ok(())
+}
+
+/// Métadonnées de l'export JSON sans les points des courbes, pour accompagner
+/// une image (réglages, horodatage, version, analyses)
+pub fn metadata(
+    data: &DualCurveData,
+    channels: &[u8],
+    settings: &Settings,
+    session: &Session,
+    match_scores: &[(u8, f32)],
+) -> String {
+    pretty(&document(data, channels, settings, session, match_scores, false))
+}
+
+/// Document JSON d'un export ; `points` inclut les points des courbes
+fn document<'a>(
+    data: &'a DualCurveData,
+    channels: &[u8],
+    settings: &Settings,
+    session: &'a Session,
+    match_scores: &[(u8, f32)],
+    points: bool,
+) -> Document<'a> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let calibration = &settings.calibration;
+
+    let curves = channels
+        .iter()
+        .filter_map(|&c| data.channel(c))
+        .map(|curve| {
+            let score = match_scores.iter().find(|(c, _)| *c == curve.channel);
+            curve_json(curve, settings, score.map(|(_, s)| *s), points)
+        })
+        .collect();
+    let device = session
+        .applied_device
+        .iter()
+        .map(|(id, label)| DeviceValue { id, value: label })
+        .collect();
+
+    Document {
+        application: env!("CARGO_PKG_NAME"),
+        version: env!("CARGO_PKG_VERSION"),
+        exported_at: now,
+        exported_at_utc: rfc3339_timestamp(now),
+        source: session.title(),
+        notes: &session.notes,
+        settings: ExportSettings {
+            display_mode: settings.display_mode,
+            log_current: settings.axes.log_current,
+            swap_axes: settings.axes.swap,
+            grid_divisions: settings.grid.divisions,
+        },
+        device,
+        calibration: CalibrationJson {
+            enabled: calibration.enabled,
+            volts_full_scale: calibration.volts_full_scale,
+            milliamps_full_scale: calibration.milliamps_full_scale,
+        },
+        curves,
+    }
+}
+
+/// Analyse d'une courbe, et ses points si `points`
+fn curve_json<'a>(
+    curve: &'a CurveData,
+    settings: &Settings,
+    match_score: Option<f32>,
+    points: bool,
+) -> CurveJson<'a> {
+    let analysis = Analysis::of(curve);
+    let metrics = curve.metrics();
+    CurveJson {
+        channel: curve.channel,
+        points: curve.voltage.len(),
+        voltage: points.then_some(&curve.voltage[..]),
+        current: points.then_some(&curve.current[..]),
+        analysis: AnalysisJson {
+            component: format!("{:?}", analysis.component).to_lowercase(),
+            slope: analysis.slope,
+            resistance: analysis.resistance(),
+            resistance_text: analysis.format_resistance(&settings.calibration),
+            forward_voltage: analysis.forward_voltage,
+            voltage_span: metrics.voltage_span,
+            current_span: metrics.current_span,
+            match_score,
+        },
+    }
+}
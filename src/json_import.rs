@@ -0,0 +1,115 @@
+// src/json_import.rs
+
+use crate::curve::CurveData;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Export JSON relu : seules les courbes servent, les autres champs (réglages,
+/// calibration, analyses) sont ignorés
+#[derive(Deserialize)]
+struct ExportFile {
+    curves: Vec<CurveFile>,
+}
+
+/// Courbe d'un export ; les points manquent dans un export de métadonnées.
+/// Une valeur null (point non fini à l'export) est refusée
+#[derive(Deserialize)]
+struct CurveFile {
+    channel: u8,
+    voltage: Option<Vec<f32>>,
+    current: Option<Vec<f32>>,
+}
+
+/// Lire les courbes d'un export JSON (points inclus) : une par canal exporté
+pub fn load_curves_from_json(path: &Path) -> Result<Vec<CurveData>, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Impossible de lire {}: {}", path.display(), e))?;
+    parse(&text).map_err(|e| format!("{} dans {}", e, path.display()))
+}
+
+fn parse(text: &str) -> Result<Vec<CurveData>, String> {
+    let file: ExportFile =
+        serde_json::from_str(text).map_err(|e| format!("JSON invalide ({})", e))?;
+    if file.curves.is_empty() {
+        return Err("Pas de courbes".to_string());
+    }
+    file.curves
+        .into_iter()
+        .map(|curve| {
+            let channel = curve.channel;
+            if channel > 1 {
+                return Err(format!("Canal CH{} inconnu", channel));
+            }
+            let (Some(voltage), Some(current)) = (curve.voltage, curve.current) else {
+                return Err(format!("Courbe CH{} sans points (export de métadonnées ?)", channel));
+            };
+            if voltage.is_empty() || voltage.len() != current.len() {
+                return Err(format!("Points incohérents pour CH{}", channel));
+            }
+            Ok(CurveData {
+                voltage,
+                current,
+                channel,
+                raw_voltage: Vec::new(),
+                raw_current: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_exported_curves() {
+        let text = r#"{
+            "app": "CT220S", "settings": { "mode": "Both" },
+            "curves": [
+                { "channel": 0, "points": 2, "voltage": [-1.0, 1.0], "current": [0, 0.5],
+                  "analysis": { "component": "diode" } },
+                { "channel": 1, "points": 1, "voltage": [0.25], "current": [-0.5] }
+            ]
+        }"#;
+        let curves = parse(text).unwrap();
+        assert_eq!(curves.len(), 2);
+        assert_eq!(curves[0].current, vec![0.0, 0.5]);
+        assert_eq!(curves[1].channel, 1);
+        assert_eq!(curves[1].voltage, vec![0.25]);
+    }
+
+    #[test]
+    fn escaped_strings_are_accepted() {
+        let text = r#"{ "source": "carte 🔌 é", "curves": [
+            { "channel": 0, "voltage": [1], "current": [2] } ] }"#;
+        assert_eq!(parse(text).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn null_points_are_rejected() {
+        let text = r#"{ "curves": [ { "channel": 0, "voltage": [1, null], "current": [1, 2] } ] }"#;
+        assert!(parse(text).is_err());
+    }
+
+    #[test]
+    fn missing_channel_is_rejected() {
+        let text = r#"{ "curves": [ { "voltage": [1], "current": [2] } ] }"#;
+        assert!(parse(text).err().unwrap().contains("channel"));
+    }
+
+    #[test]
+    fn metadata_export_is_rejected() {
+        let text = r#"{ "curves": [ { "channel": 1, "points": 512 } ] }"#;
+        assert!(parse(text).err().unwrap().contains("sans points"));
+    }
+
+    #[test]
+    fn inconsistent_points_are_rejected() {
+        let text = r#"{ "curves": [ { "channel": 0, "voltage": [1, 2], "current": [1] } ] }"#;
+        assert!(parse(text).is_err());
+        assert!(parse(r#"{ "curves": [] }"#).is_err());
+        assert!(parse(r#"{ "curves": [ { "channel": 2, "voltage": [1], "current": [1] } ] }"#)
+            .is_err());
+    }
+}
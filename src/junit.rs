@@ -0,0 +1,100 @@
+// src/junit.rs
+
+use crate::timestamp::rfc3339_timestamp;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::info;
+
+/// Résultat d'une comparaison PASS/FAIL, un `<testcase>` du rapport JUnit
+#[derive(Debug, Clone)]
+pub struct TestCase {
+    /// Point de test (entrée de bibliothèque) et canal
+    pub name: String,
+    /// Regroupement : capture ou carte testée
+    pub classname: String,
+    /// Score de correspondance (%)
+    pub score: f32,
+    pub pass: bool,
+    /// Seuil PASS/FAIL appliqué au cas (%)
+    pub threshold: f32,
+    /// Durée du test (secondes)
+    pub time: f32,
+}
+
+/// Écrire les résultats au format JUnit XML, lu par les systèmes
+/// d'intégration continue et les outils de gestion des tests : une suite
+/// `suite`, un échec par comparaison sous le seuil
+pub fn save_junit(
+    suite: &str,
+    cases: &[TestCase],
+    pass_threshold: f32,
+    path: &Path,
+) -> Result<(), String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let failures = cases.iter().filter(|case| !case.pass).count();
+    let time: f32 = cases.iter().map(|case| case.time).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+        env!("CARGO_PKG_NAME"),
+        cases.len(),
+        failures,
+        time
+    );
+    let _ = writeln!(
+        xml,
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\" \
+         time=\"{:.3}\" timestamp=\"{}\">",
+        escape_xml(suite),
+        cases.len(),
+        failures,
+        time,
+        rfc3339_timestamp(now)
+    );
+    let _ = writeln!(
+        xml,
+        "    <properties>\n      <property name=\"pass_threshold\" value=\"{}\"/>\n    \
+         </properties>",
+        pass_threshold
+    );
+    for case in cases {
+        let _ = write!(
+            xml,
+            "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+            escape_xml(&case.name),
+            escape_xml(&case.classname),
+            case.time
+        );
+        let score = format!("{:.1} %", case.score);
+        if case.pass {
+            let _ = writeln!(xml, ">\n      <system-out>{}</system-out>\n    </testcase>", score);
+        } else {
+            let _ = writeln!(
+                xml,
+                ">\n      <failure type=\"mismatch\" message=\"{} &lt; {} %\"/>\n    </testcase>",
+                score, case.threshold
+            );
+        }
+    }
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+
+    fs::write(path, xml).map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+
+    info!("Résultats JUnit sauvegardés : {}", path.display());
+    Ok(())
+}
+
+/// Échapper un texte pour un attribut ou un contenu XML
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,137 @@
+// src/layout.rs
+
+use egui_dock::{DockState, NodeIndex};
+use serde::{Deserialize, Serialize};
+
+/// Onglets de l'interface : déplaçables, regroupables et détachables en
+/// fenêtres
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Panel {
+    /// Tracé des courbes, toujours présent
+    Plot,
+    Acquisition,
+    Display,
+    Commands,
+    History,
+    Library,
+    /// Résultats d'analyse en gros caractères
+    Measurements,
+    /// Photo de la carte et points de test
+    Board,
+}
+
+impl Panel {
+    /// Onglets que l'on peut fermer et rouvrir depuis le menu Affichage
+    pub const ALL: [Panel; 7] = [
+        Panel::Acquisition,
+        Panel::Display,
+        Panel::Commands,
+        Panel::History,
+        Panel::Library,
+        Panel::Measurements,
+        Panel::Board,
+    ];
+
+    /// Clé de traduction du titre du panneau
+    pub fn key(self) -> &'static str {
+        match self {
+            Panel::Plot => "panel-plot",
+            Panel::Acquisition => "panel-acquisition",
+            Panel::Display => "panel-display",
+            Panel::Commands => "commands-header",
+            Panel::History => "history-header",
+            Panel::Library => "library-header",
+            Panel::Measurements => "panel-measurements",
+            Panel::Board => "panel-board",
+        }
+    }
+}
+
+/// Disposition des onglets, sauvegardée avec les préférences
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelLayout {
+    pub dock: DockState<Panel>,
+}
+
+impl Default for PanelLayout {
+    /// Tracé au centre, réglages à gauche, commandes et mesures à droite,
+    /// historique en bas ; le plan de carte s'ouvre depuis le menu Affichage
+    fn default() -> Self {
+        let mut dock = DockState::new(vec![Panel::Plot]);
+        let tree = dock.main_surface_mut();
+        let [plot, _] = tree.split_left(
+            NodeIndex::root(),
+            0.22,
+            vec![Panel::Acquisition, Panel::Display],
+        );
+        let [plot, _] = tree.split_right(
+            plot,
+            0.72,
+            vec![Panel::Commands, Panel::Library, Panel::Measurements],
+        );
+        tree.split_below(plot, 0.75, vec![Panel::History]);
+        Self { dock }
+    }
+}
+
+impl PanelLayout {
+    pub fn is_open(&self, panel: Panel) -> bool {
+        self.dock.find_tab(&panel).is_some()
+    }
+
+    /// Fermer un onglet, ou le rouvrir dans une fenêtre détachée
+    pub fn set_open(&mut self, panel: Panel, open: bool) {
+        match self.dock.find_tab(&panel) {
+            Some(index) if !open => {
+                self.dock.remove_tab(index);
+            }
+            None if open => {
+                self.dock.add_window(vec![panel]);
+            }
+            _ => {}
+        }
+    }
+
+    /// Remettre le tracé si la disposition enregistrée l'a perdu
+    pub fn ensure_plot(&mut self) {
+        if !self.is_open(Panel::Plot) {
+            self.dock.push_to_first_leaf(Panel::Plot);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_layout_has_every_panel_but_the_board() {
+        let layout = PanelLayout::default();
+        assert!(layout.is_open(Panel::Plot));
+        for panel in Panel::ALL {
+            assert_eq!(layout.is_open(panel), panel != Panel::Board, "{:?}", panel);
+        }
+    }
+
+    #[test]
+    fn closed_panels_reopen_in_a_window() {
+        let mut layout = PanelLayout::default();
+        layout.set_open(Panel::History, false);
+        assert!(!layout.is_open(Panel::History));
+        layout.set_open(Panel::History, true);
+        let (surface, _, _) = layout.dock.find_tab(&Panel::History).unwrap();
+        assert!(!surface.is_main());
+    }
+
+    #[test]
+    fn layout_round_trips_through_toml() {
+        let mut layout = PanelLayout::default();
+        layout.set_open(Panel::Board, true);
+        let text = toml::to_string(&layout).unwrap();
+        let read: PanelLayout = toml::from_str(&text).unwrap();
+        for panel in Panel::ALL {
+            assert_eq!(read.dock.find_tab(&panel), layout.dock.find_tab(&panel));
+        }
+    }
+}
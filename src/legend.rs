@@ -0,0 +1,69 @@
+// src/legend.rs
+
+use crate::i18n::tr;
+use eframe::egui;
+
+/// Entrée de la légende d'un tracé
+pub struct LegendEntry {
+    pub name: String,
+    pub color: egui::Color32,
+    /// Mesures de la courbe affichée ; vide sans données
+    pub metrics: String,
+    pub visible: bool,
+}
+
+/// Légende posée sur le coin haut-gauche du tracé ; renvoie l'entrée cliquée.
+/// Dessinée dans sa propre couche pour que ses clics n'atteignent pas le tracé.
+pub fn show(
+    ctx: &egui::Context,
+    id: egui::Id,
+    pos: egui::Pos2,
+    entries: &[LegendEntry],
+) -> Option<usize> {
+    let mut clicked = None;
+    egui::Area::new(id)
+        .fixed_pos(pos)
+        .constrain(false)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                for (i, entry) in entries.iter().enumerate() {
+                    let row = ui
+                        .horizontal(|ui| legend_row(ui, entry))
+                        .response
+                        .interact(egui::Sense::click())
+                        .on_hover_cursor(egui::CursorIcon::PointingHand)
+                        .on_hover_text(tr("legend-toggle"));
+                    row.widget_info(|| {
+                        egui::WidgetInfo::selected(
+                            egui::WidgetType::Checkbox,
+                            entry.visible,
+                            &entry.name,
+                        )
+                    });
+                    if row.clicked() {
+                        clicked = Some(i);
+                    }
+                }
+            });
+        });
+    clicked
+}
+
+/// Pastille de couleur, nom du canal et mesures ; grisés si le canal est masqué
+fn legend_row(ui: &mut egui::Ui, entry: &LegendEntry) {
+    let (swatch, _) = ui.allocate_exact_size(egui::vec2(14.0, 14.0), egui::Sense::hover());
+    if entry.visible {
+        ui.painter().rect_filled(swatch, 2.0, entry.color);
+    } else {
+        ui.painter()
+            .rect_stroke(swatch, 2.0, egui::Stroke::new(1.5, entry.color));
+    }
+
+    let name = egui::RichText::new(&entry.name).strong();
+    if entry.visible {
+        ui.label(name.color(entry.color));
+        ui.label(egui::RichText::new(&entry.metrics).small());
+    } else {
+        ui.label(name.weak().strikethrough());
+    }
+}
@@ -0,0 +1,468 @@
+// src/library.rs
+
+use crate::curve::CurveData;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
+use zip::result::ZipResult;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+/// Écart moyen (en unités normalisées) pour lequel le score tombe à 0 %
+const MATCH_TOLERANCE: f32 = 0.5;
+
+/// Manifeste d'un paquet de bibliothèque exporté
+const BUNDLE_MANIFEST: &str = "manifest.toml";
+
+/// Traitement d'une entrée importée dont le nom existe déjà avec un contenu
+/// différent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MergePolicy {
+    /// Garder l'entrée locale
+    #[default]
+    Skip,
+    /// Remplacer l'entrée locale
+    Overwrite,
+    /// Ajouter l'entrée importée sous un nom libre (« nom (2) »…)
+    Rename,
+}
+
+/// Bilan d'une fusion de bibliothèque
+#[derive(Debug, Default)]
+pub struct MergeSummary {
+    pub added: usize,
+    pub replaced: usize,
+    pub renamed: usize,
+    /// Conflits gardés en local
+    pub skipped: usize,
+    /// Entrées déjà présentes à l'identique
+    pub unchanged: usize,
+}
+
+/// Courbe de référence ("signature") d'un composant ou d'un point de test connu bon
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LibraryEntry {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Date de création (secondes depuis l'epoch Unix)
+    #[serde(default)]
+    pub created: u64,
+    pub curve: CurveData,
+}
+
+impl LibraryEntry {
+    pub fn new(name: &str, curve: CurveData) -> Self {
+        let created = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self {
+            name: name.to_string(),
+            description: String::new(),
+            created,
+            curve,
+        }
+    }
+}
+
+/// Bibliothèque de signatures, un fichier TOML par entrée
+/// (~/.local/share/ct220s_viewer/library/)
+#[derive(Default)]
+pub struct Library {
+    pub entries: Vec<LibraryEntry>,
+}
+
+impl Library {
+    pub fn dir() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("ct220s_viewer").join("library"))
+    }
+
+    /// Charger toutes les entrées, triées par nom ; les fichiers invalides sont ignorés
+    pub fn load() -> Self {
+        let Some(dir) = Self::dir() else {
+            return Self::default();
+        };
+        let Ok(read_dir) = fs::read_dir(&dir) else {
+            return Self::default();
+        };
+
+        let mut entries: Vec<LibraryEntry> = read_dir
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+            .filter_map(|path| {
+                let content = fs::read_to_string(&path).ok()?;
+                toml::from_str(&content)
+                    .map_err(|e| {
+                        warn!("Entrée de bibliothèque invalide {}: {}", path.display(), e)
+                    })
+                    .ok()
+            })
+            .collect();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Self { entries }
+    }
+
+    /// Ajouter (ou remplacer) une entrée et l'enregistrer sur disque
+    pub fn add(&mut self, entry: LibraryEntry) -> Result<PathBuf, String> {
+        let path = write_entry(&entry)?;
+        self.insert(entry);
+        Ok(path)
+    }
+
+    /// Ajouter une entrée en mémoire, à la place de celle enregistrée dans le
+    /// même fichier
+    fn insert(&mut self, entry: LibraryEntry) {
+        let stem = file_stem(&entry.name);
+        self.entries.retain(|e| file_stem(&e.name) != stem);
+        self.entries.push(entry);
+        self.entries.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    /// Exporter toute la bibliothèque en un seul paquet à partager : archive
+    /// ZIP d'un manifeste et d'un fichier TOML par entrée (courbe et
+    /// métadonnées), dans le format du répertoire de la bibliothèque
+    pub fn export_bundle(&self, path: &Path) -> Result<(), String> {
+        let write = || -> ZipResult<()> {
+            self.write_bundle(File::create(path)?)?;
+            Ok(())
+        };
+        write().map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+
+        info!("Bibliothèque exportée : {}", path.display());
+        Ok(())
+    }
+
+    /// Paquet de la bibliothèque : archive ZIP d'un manifeste et d'un fichier
+    /// TOML par entrée
+    fn write_bundle<W: Write + Seek>(&self, writer: W) -> ZipResult<W> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let manifest = format!(
+            "application = \"{}\"\nversion = \"{}\"\nexported = {}\nentries = {}\n",
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            now,
+            self.entries.len()
+        );
+        let mut zip = ZipWriter::new(writer);
+        zip.start_file(BUNDLE_MANIFEST, SimpleFileOptions::default())?;
+        zip.write_all(manifest.as_bytes())?;
+        for entry in &self.entries {
+            let content = entry_toml(entry).map_err(std::io::Error::other)?;
+            let name = format!("library/{}.toml", file_stem(&entry.name));
+            zip.start_file(name, SimpleFileOptions::default())?;
+            zip.write_all(content.as_bytes())?;
+        }
+        zip.finish()
+    }
+
+    /// Fusionner les entrées d'un paquet exporté dans la bibliothèque ; les
+    /// conflits de nom sont traités selon `policy`
+    pub fn import_bundle(
+        &mut self,
+        path: &Path,
+        policy: MergePolicy,
+    ) -> Result<MergeSummary, String> {
+        let file = File::open(path)
+            .map_err(|e| format!("Impossible d'ouvrir {}: {}", path.display(), e))?;
+        // Tout lire avant d'écrire : un paquet illisible ne modifie pas la
+        // bibliothèque (une erreur d'écriture peut en revanche interrompre la
+        // fusion après une partie des entrées)
+        let entries = read_bundle(file).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let mut merged = Library {
+            entries: self.entries.clone(),
+        };
+        let (changed, summary) = merged.merge(entries, policy)?;
+        for entry in &changed {
+            write_entry(entry)?;
+        }
+        self.entries = merged.entries;
+
+        info!("Bibliothèque importée : {} ({:?})", path.display(), summary);
+        Ok(summary)
+    }
+
+    /// Fusionner des entrées en mémoire selon `policy` : une entrée est en
+    /// conflit avec l'entrée locale du même fichier (même nom, ou nom qui ne
+    /// diffère que par des caractères remplacés). Renvoie les entrées à
+    /// enregistrer et le bilan
+    fn merge(
+        &mut self,
+        entries: Vec<LibraryEntry>,
+        policy: MergePolicy,
+    ) -> Result<(Vec<LibraryEntry>, MergeSummary), String> {
+        let mut summary = MergeSummary::default();
+        let mut changed = Vec::new();
+        for mut entry in entries {
+            let stem = file_stem(&entry.name);
+            match self.entries.iter().find(|e| file_stem(&e.name) == stem) {
+                None => summary.added += 1,
+                Some(local) if entry_toml(local)? == entry_toml(&entry)? => {
+                    summary.unchanged += 1;
+                    continue;
+                }
+                Some(_) => match policy {
+                    MergePolicy::Skip => {
+                        summary.skipped += 1;
+                        continue;
+                    }
+                    MergePolicy::Overwrite => summary.replaced += 1,
+                    MergePolicy::Rename => {
+                        entry.name = self.free_name(&entry.name);
+                        summary.renamed += 1;
+                    }
+                },
+            }
+            self.insert(entry.clone());
+            changed.push(entry);
+        }
+        Ok((changed, summary))
+    }
+
+    /// Premier nom « nom (2) », « nom (3) »… absent de la bibliothèque et
+    /// dont le fichier ne remplace pas celui d'une autre entrée
+    fn free_name(&self, name: &str) -> String {
+        (2..)
+            .map(|n| format!("{} ({})", name, n))
+            .find(|candidate| {
+                let stem = file_stem(candidate);
+                !self.entries.iter().any(|e| e.name == *candidate || file_stem(&e.name) == stem)
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Entrées d'un paquet de bibliothèque, compressé ou non ; le paquet peut
+/// avoir été décompressé puis recompressé dans un dossier
+fn read_bundle<R: Read + Seek>(reader: R) -> Result<Vec<LibraryEntry>, String> {
+    let mut archive =
+        ZipArchive::new(reader).map_err(|e| format!("Archive ZIP invalide: {}", e))?;
+    // Racine du paquet : celle du manifeste le moins profond
+    let root = archive
+        .file_names()
+        .filter_map(|name| name.strip_suffix(BUNDLE_MANIFEST))
+        .filter(|root| root.is_empty() || root.ends_with('/') && !root.starts_with("__MACOSX/"))
+        .min_by_key(|root| root.len())
+        .map(|root| format!("{}library/", root))
+        .ok_or("Ce n'est pas une bibliothèque exportée")?;
+
+    let mut entries = Vec::new();
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|e| format!("Archive ZIP invalide: {}", e))?;
+        if file.is_dir() || !file.name().starts_with(&root) {
+            continue;
+        }
+        let name = file.name().to_string();
+        let invalid = |e: String| format!("Entrée de bibliothèque invalide {}: {}", name, e);
+        // La somme de contrôle est vérifiée en fin de lecture
+        let mut content = String::new();
+        file.read_to_string(&mut content)
+            .map_err(|e| invalid(e.to_string()))?;
+        entries.push(toml::from_str(&content).map_err(|e| invalid(e.to_string()))?);
+    }
+    Ok(entries)
+}
+
+/// Enregistrer une entrée dans le répertoire de la bibliothèque, sous un nom
+/// de fichier tiré de son nom
+fn write_entry(entry: &LibraryEntry) -> Result<PathBuf, String> {
+    let dir = Library::dir().ok_or("Répertoire de données introuvable")?;
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Impossible de créer {}: {}", dir.display(), e))?;
+
+    let path = dir.join(format!("{}.toml", file_stem(&entry.name)));
+    let content = entry_toml(entry)?;
+    fs::write(&path, content)
+        .map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// Entrée sérialisée, telle qu'enregistrée dans la bibliothèque
+fn entry_toml(entry: &LibraryEntry) -> Result<String, String> {
+    toml::to_string(entry).map_err(|e| format!("Erreur sérialisation: {}", e))
+}
+
+/// Nom de fichier sûr à partir du nom de l'entrée
+fn file_stem(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' })
+        .collect()
+}
+
+/// Score de ressemblance entre deux courbes, en pourcentage.
+/// Distance moyenne symétrique au point le plus proche : insensible au
+/// déphasage du balayage.
+pub fn match_score(a: &CurveData, b: &CurveData) -> f32 {
+    let distance = (mean_nearest_distance(a, b) + mean_nearest_distance(b, a)) / 2.0;
+    (1.0 - distance / MATCH_TOLERANCE).clamp(0.0, 1.0) * 100.0
+}
+
+fn mean_nearest_distance(from: &CurveData, to: &CurveData) -> f32 {
+    if from.voltage.is_empty() || to.voltage.is_empty() {
+        return MATCH_TOLERANCE;
+    }
+    let total: f32 = nearest_distances(from, to).iter().sum();
+    total / from.voltage.len() as f32
+}
+
+/// Distance de chaque point de `from` au point le plus proche de `to`
+/// (unités normalisées) ; vide si `to` n'a aucun point
+pub fn nearest_distances(from: &CurveData, to: &CurveData) -> Vec<f32> {
+    if to.voltage.is_empty() {
+        return Vec::new();
+    }
+    from.voltage
+        .iter()
+        .zip(&from.current)
+        .map(|(&v, &i)| {
+            to.voltage
+                .iter()
+                .zip(&to.current)
+                .map(|(&tv, &ti)| (v - tv).powi(2) + (i - ti).powi(2))
+                .fold(f32::INFINITY, f32::min)
+                .sqrt()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use zip::CompressionMethod;
+
+    fn entry(name: &str, offset: f32) -> LibraryEntry {
+        let curve = CurveData {
+            voltage: vec![-1.0, 0.0, 1.0],
+            current: vec![offset, 0.5, 1.0],
+            channel: 0,
+            raw_voltage: Vec::new(),
+            raw_current: Vec::new(),
+        };
+        LibraryEntry::new(name, curve)
+    }
+
+    fn library(entries: Vec<LibraryEntry>) -> Library {
+        Library { entries }
+    }
+
+    #[test]
+    fn bundle_round_trips() {
+        let local = library(vec![entry("D1", 0.0), entry("R 10k", 0.2)]);
+        let bytes = local.write_bundle(Cursor::new(Vec::new())).unwrap();
+        let entries = read_bundle(bytes).unwrap();
+        assert_eq!(entries.len(), 2);
+        for (read, written) in entries.iter().zip(&local.entries) {
+            assert_eq!(entry_toml(read).unwrap(), entry_toml(written).unwrap());
+        }
+    }
+
+    #[test]
+    fn rezipped_deflated_bundle_is_read() {
+        // Paquet décompressé puis recompressé dans son dossier
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        zip.add_directory("bundle/", options).unwrap();
+        zip.start_file("bundle/manifest.toml", options).unwrap();
+        zip.write_all(b"entries = 1\n").unwrap();
+        let content = entry_toml(&entry("D1", 0.0)).unwrap();
+        zip.start_file("bundle/library/D1.toml", options).unwrap();
+        zip.write_all(content.as_bytes()).unwrap();
+        let resource_fork = "__MACOSX/bundle/library/._D1.toml";
+        zip.start_file(resource_fork, options).unwrap();
+        zip.write_all(b"\0\x05\x16\x07").unwrap();
+        let entries = read_bundle(zip.finish().unwrap()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "D1");
+    }
+
+    #[test]
+    fn archive_without_manifest_is_rejected() {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+        let content = entry_toml(&entry("D1", 0.0)).unwrap();
+        zip.start_file("library/D1.toml", options).unwrap();
+        zip.write_all(content.as_bytes()).unwrap();
+        assert!(read_bundle(zip.finish().unwrap()).is_err());
+        assert!(read_bundle(Cursor::new(b"not a zip".to_vec())).is_err());
+    }
+
+    /// Noms des entrées, dans l'ordre de la bibliothèque
+    fn names(library: &Library) -> Vec<&str> {
+        library.entries.iter().map(|e| e.name.as_str()).collect()
+    }
+
+    fn merged(policy: MergePolicy, imported: Vec<LibraryEntry>) -> (Library, MergeSummary) {
+        let mut local = library(vec![entry("D1", 0.0), entry("R_10k", 0.0)]);
+        let (changed, summary) = local.merge(imported, policy).unwrap();
+        let written = summary.added + summary.replaced + summary.renamed;
+        assert_eq!(changed.len(), written);
+        (local, summary)
+    }
+
+    #[test]
+    fn new_and_identical_entries() {
+        let (local, summary) = merged(MergePolicy::Skip, vec![entry("C1", 0.0), entry("D1", 0.0)]);
+        assert_eq!(names(&local), ["C1", "D1", "R_10k"]);
+        assert_eq!((summary.added, summary.unchanged), (1, 1));
+    }
+
+    #[test]
+    fn skip_keeps_local_entries() {
+        let (local, summary) = merged(MergePolicy::Skip, vec![entry("D1", 0.3)]);
+        assert_eq!(names(&local), ["D1", "R_10k"]);
+        assert_eq!(local.entries[0].curve.current[0], 0.0);
+        assert_eq!(summary.skipped, 1);
+    }
+
+    #[test]
+    fn overwrite_replaces_local_entries() {
+        let (local, summary) = merged(MergePolicy::Overwrite, vec![entry("D1", 0.3)]);
+        assert_eq!(names(&local), ["D1", "R_10k"]);
+        assert_eq!(local.entries[0].curve.current[0], 0.3);
+        assert_eq!(summary.replaced, 1);
+    }
+
+    #[test]
+    fn rename_adds_under_a_free_name() {
+        let imported = vec![entry("D1", 0.3), entry("D1", 0.4)];
+        let (local, summary) = merged(MergePolicy::Rename, imported);
+        assert_eq!(names(&local), ["D1", "D1 (2)", "D1 (3)", "R_10k"]);
+        assert_eq!(summary.renamed, 2);
+    }
+
+    #[test]
+    fn same_file_name_is_a_conflict() {
+        // « R 10k » serait enregistré dans R_10k.toml, le fichier de « R_10k »
+        let (local, summary) = merged(MergePolicy::Skip, vec![entry("R 10k", 0.3)]);
+        assert_eq!(names(&local), ["D1", "R_10k"]);
+        assert_eq!(summary.skipped, 1);
+
+        let (local, _) = merged(MergePolicy::Rename, vec![entry("R 10k", 0.3)]);
+        assert_eq!(names(&local), ["D1", "R 10k (2)", "R_10k"]);
+
+        let (local, summary) = merged(MergePolicy::Overwrite, vec![entry("R 10k", 0.3)]);
+        assert_eq!(names(&local), ["D1", "R 10k"]);
+        assert_eq!(summary.replaced, 1);
+    }
+
+    #[test]
+    fn free_name_avoids_names_and_files() {
+        let local = library(vec![
+            entry("D1", 0.0),
+            entry("D1 (2)", 0.0),
+            entry("D1__3_", 0.0),
+        ]);
+        assert_eq!(local.free_name("D1"), "D1 (4)");
+        assert_eq!(local.free_name("C1"), "C1 (2)");
+    }
+}
@@ -0,0 +1,75 @@
+// src/logging.rs
+
+use std::io::IsTerminal;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::level_filters::LevelFilter;
+use tracing_appender::rolling::{Builder, Rotation};
+use tracing_subscriber::fmt::writer::MakeWriterExt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+/// Variable d'environnement du filtre des journaux
+/// (`warn`, `ct220s_viewer::backend=debug`…)
+pub const FILTER_VAR: &str = "RUST_LOG";
+
+/// Anciens journaux conservés (un fichier par jour)
+const LOG_FILES_KEPT: usize = 3;
+
+/// Journaux écrits sur la sortie d'erreur ; coupés pendant que l'interface
+/// texte occupe le terminal
+static STDERR: AtomicBool = AtomicBool::new(true);
+
+/// Écrire ou non les journaux sur la sortie d'erreur (le fichier journal
+/// reste alimenté)
+pub fn set_stderr(enabled: bool) {
+    STDERR.store(enabled, Ordering::Relaxed);
+}
+
+/// Fichier journal du jour : `ct220s.log` devient `ct220s.2024-05-01.log`,
+/// les plus anciens au-delà de `LOG_FILES_KEPT` sont supprimés
+fn log_file(path: &Path) -> Result<tracing_appender::rolling::RollingFileAppender, String> {
+    let name = path
+        .file_stem()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| format!("Nom de journal invalide: {}", path.display()))?;
+    let mut builder = Builder::new()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(name)
+        .max_log_files(LOG_FILES_KEPT);
+    if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
+        builder = builder.filename_suffix(extension);
+    }
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    builder
+        .build(dir)
+        .map_err(|e| format!("Impossible d'ouvrir le journal {}: {}", path.display(), e))
+}
+
+/// Installer les journaux : niveaux selon `RUST_LOG` (`info` par défaut),
+/// copie horodatée dans `file` avec rotation quotidienne
+pub fn init(file: Option<&Path>) -> Result<(), String> {
+    let filter = EnvFilter::builder()
+        .with_default_directive(LevelFilter::INFO.into())
+        .with_env_var(FILTER_VAR)
+        .from_env_lossy();
+    let stderr = tracing_subscriber::fmt::layer()
+        .without_time()
+        .with_ansi(std::io::stderr().is_terminal())
+        .with_writer(std::io::stderr.with_filter(|_| STDERR.load(Ordering::Relaxed)));
+    let file = file.map(log_file).transpose()?.map(|file| {
+        tracing_subscriber::fmt::layer()
+            .with_ansi(false)
+            .with_writer(file)
+    });
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(stderr)
+        .with(file)
+        .try_init()
+        .map_err(|e| format!("Journaux déjà initialisés: {}", e))
+}
@@ -1,35 +1,481 @@
-// src/main.rs
-
-mod config;
-mod curve;
-mod backend;
-mod image_export;
-mod app;
-
-use app::CT220SApp;
-use clap::Parser;
-use eframe::egui;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Chemin vers un fichier de capture hexadécimal
-    #[arg(short, long)]
-    file: Option<String>,
-}
-
-fn main() -> Result<(), eframe::Error> {
-    let args = Args::parse();
-
-    let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([900.0, 700.0]),
-        ..Default::default()
-    };
-
-    eframe::run_native(
-        "CT220S V-I Curve Viewer",
-        options,
-        Box::new(move |cc| Box::new(CT220SApp::new(cc, args.file.clone()))),
-    )
-}
-
+// src/main.rs
+
+mod config;
+mod cli;
+mod csv_export;
+mod accessibility;
+mod analysis;
+mod annotation;
+mod axes;
+mod board;
+mod calibration;
+mod command_palette;
+mod compare;
+mod compression;
+mod comparison_report;
+mod curve;
+mod backend;
+mod html_export;
+mod i18n;
+mod image_export;
+mod json_export;
+mod json_import;
+mod junit;
+mod layout;
+mod legend;
+mod library;
+mod logging;
+mod mat_export;
+mod notifications;
+mod numpy_export;
+mod plan;
+mod plot_renderer;
+mod print;
+mod report;
+mod results_db;
+mod session;
+mod settings;
+mod shell;
+mod sigrok_export;
+mod status;
+mod template;
+mod timestamp;
+mod tui;
+mod video;
+mod app;
+
+use app::CT220SApp;
+use clap::{ArgGroup, Parser, Subcommand};
+use cli::{
+    parse_image_format, parse_override, parse_speed, parse_tolerance, CaptureOptions,
+    ChannelChoice, ConfigCommand, ConvertMode, ConvertOptions, DeviceCommand, ListKind,
+    StreamFormat, TestOptions, ViewOptions,
+};
+use image_export::ImageFormat;
+use plan::PlanOptions;
+use eframe::egui;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Sans sous-commande : options de l'interface (`gui`)
+    #[command(flatten)]
+    gui: GuiArgs,
+
+    /// Remplacer une préférence (ex. `--set plot.connect_points=false`), après
+    /// le fichier de configuration et les variables CT220S_*
+    #[arg(long = "set", value_name = "CLÉ=VALEUR", global = true, value_parser = parse_override)]
+    overrides: Vec<(String, String)>,
+
+    /// Copier les journaux (niveaux selon RUST_LOG, `info` par défaut) dans ce
+    /// fichier, horodatés ; un fichier par jour (`app.2024-05-01.log`), les
+    /// trois derniers conservés
+    #[arg(long, value_name = "FICHIER", global = true)]
+    log_file: Option<PathBuf>,
+
+    /// Sorties des sous-commandes en JSON (listes, mesures, verdicts, erreurs),
+    /// pour les scripts
+    #[arg(long, global = true)]
+    json: bool,
+}
+
+// Options de l'interface graphique (commentaire simple : une documentation
+// remplacerait la description du programme)
+#[derive(clap::Args, Debug)]
+struct GuiArgs {
+    /// Chemin vers un fichier de capture hexadécimal
+    #[arg(short, long)]
+    file: Option<String>,
+
+    /// Mode opérateur : tracé, verdict PASS/FAIL et point de test suivant uniquement
+    #[arg(long)]
+    operator: bool,
+
+    /// Interface en mode texte dans le terminal (SSH, postes sans écran)
+    #[arg(long, conflicts_with = "operator")]
+    tui: bool,
+
+    /// Vitesse de relecture du fichier, de 0.1 à 10 (1 = une courbe toutes les 50 ms)
+    #[arg(long, value_name = "FACTEUR", requires = "file", value_parser = parse_speed)]
+    speed: Option<f32>,
+
+    /// Relire le fichier en boucle (par défaut)
+    #[arg(long = "loop", requires = "file", overrides_with = "once")]
+    looping: bool,
+
+    /// Arrêter la relecture à la fin du fichier
+    #[arg(long, requires = "file", overrides_with = "looping")]
+    once: bool,
+
+    /// Canaux affichés, pour cette exécution seulement ; mode d'affichage
+    /// enregistré par défaut
+    #[arg(long, value_enum)]
+    channel: Option<ChannelChoice>,
+
+    /// Balayage affiché au chargement du fichier, à partir de 1 ; la relecture
+    /// reste arrêtée dessus
+    #[arg(
+        long,
+        value_name = "N",
+        requires = "file",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    start_at: Option<u64>,
+}
+
+impl GuiArgs {
+    fn view_options(&self) -> ViewOptions {
+        ViewOptions {
+            speed: self.speed,
+            once: self.once,
+            start_at: self.start_at.map(|n| n as usize),
+            channel: self.channel,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Ouvrir l'interface graphique (par défaut)
+    Gui(GuiArgs),
+
+    /// Exporter une image d'une capture sans interface ni affichage ; format
+    /// selon l'extension (png, svg, pdf). `--batch` convertit un répertoire
+    Convert {
+        /// Fichier de capture hexadécimal
+        #[arg(required_unless_present = "batch")]
+        capture: Option<String>,
+        /// Image produite
+        #[arg(required_unless_present = "batch")]
+        output: Option<PathBuf>,
+
+        /// Convertir toutes les captures (.txt, .hex, .log) d'un répertoire en
+        /// image et CSV, avec un index.html récapitulatif
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["capture", "output"])]
+        batch: Option<PathBuf>,
+
+        /// Répertoire des fichiers produits par --batch ; celui des captures par défaut
+        #[arg(long, value_name = "DIR", requires = "batch")]
+        out_dir: Option<PathBuf>,
+
+        /// Format des images produites par --batch : png, svg ou pdf
+        #[arg(long, value_name = "FORMAT", requires = "batch", value_parser = parse_image_format)]
+        format: Option<ImageFormat>,
+
+        /// Nombre de conversions simultanées ; un par cœur par défaut
+        #[arg(long, value_name = "N", requires = "batch")]
+        jobs: Option<usize>,
+
+        /// Canaux tracés ; mode d'affichage enregistré par défaut
+        #[arg(long, value_enum)]
+        mode: Option<ConvertMode>,
+
+        /// Balayage exporté, à partir de 1 ; le dernier par défaut
+        #[arg(long, value_name = "N")]
+        sweep: Option<usize>,
+
+        /// Style d'export enregistré à appliquer (taille, couleurs, textes, fond)
+        #[arg(long, value_name = "NOM")]
+        style: Option<String>,
+
+        /// Côté d'un tracé exporté en pixels (800, 2048, 3840…)
+        #[arg(long, value_name = "PIXELS")]
+        size: Option<u32>,
+
+        /// Facteur appliqué à la taille d'export
+        #[arg(long, value_name = "FACTEUR")]
+        scale: Option<f32>,
+    },
+
+    /// Comparer une capture, ou une acquisition sur le boîtier, à une
+    /// signature de la bibliothèque ou à un export JSON : score affiché,
+    /// résultats JUnit XML et rapport PDF ; code de sortie 0 si tous les
+    /// canaux passent, 1 sinon ou en cas d'erreur
+    #[command(group(ArgGroup::new("verdict").args(["signature", "reference"])))]
+    Test {
+        /// Fichier de capture hexadécimal ; acquisition sur le boîtier si absent
+        capture: Option<String>,
+
+        /// Entrée de bibliothèque servant de référence au verdict
+        #[arg(long, value_name = "NOM")]
+        signature: Option<String>,
+
+        /// Export JSON (avec points) servant de référence, canal par canal
+        #[arg(long, value_name = "JSON")]
+        reference: Option<PathBuf>,
+
+        /// Écart toléré (ex. 5%) ; seuil PASS/FAIL des préférences par défaut
+        #[arg(long, value_name = "POURCENT", requires = "verdict", value_parser = parse_tolerance)]
+        tolerance: Option<f32>,
+
+        /// Écrire les résultats en JUnit XML
+        #[arg(long, value_name = "XML", requires = "verdict")]
+        junit: Option<PathBuf>,
+
+        /// Numéro de série de la carte testée, enregistré avec les résultats
+        #[arg(long, value_name = "SERIE", requires = "verdict")]
+        serial: Option<String>,
+
+        /// Générer un rapport PDF de la capture
+        #[arg(long, value_name = "PDF")]
+        report: Option<PathBuf>,
+    },
+
+    /// Comparer deux captures canal par canal : score, statistiques d'écart
+    /// et verdict ; code de sortie 1 si un canal échoue
+    Compare {
+        /// Capture examinée
+        capture: String,
+        /// Capture de référence (enregistrement d'une carte bonne)
+        reference: String,
+
+        /// Balayage comparé dans chaque capture, à partir de 1 ; le dernier par défaut
+        #[arg(long, value_name = "N")]
+        sweep: Option<usize>,
+
+        /// Écrire le rapport de comparaison (courbes superposées et écart ;
+        /// png, pdf ou html)
+        #[arg(long, value_name = "FICHIER")]
+        image: Option<PathBuf>,
+    },
+
+    /// Afficher les mesures de chaque courbe d'une capture : points, plages,
+    /// aire de la boucle et classification
+    Stats {
+        /// Fichier de capture hexadécimal
+        capture: String,
+    },
+
+    /// Vérifier un fichier de capture : lignes mal formées, taille des
+    /// rapports, en-têtes et courbes incomplètes ; code de sortie 1 si la
+    /// capture présente un problème
+    Validate {
+        /// Fichier de capture hexadécimal
+        capture: String,
+    },
+
+    /// Shell interactif : réglages, rapports bruts, capture, mesures et export
+    /// sur le boîtier ouvert, sans écrire de script
+    Shell,
+
+    /// Enregistrer les rapports bruts du boîtier pendant une durée donnée, au
+    /// format des fichiers de capture (collecte de données scriptée)
+    Capture {
+        /// Durée de l'enregistrement ; jusqu'à Ctrl+C avec --stream
+        #[arg(long, value_name = "N", required_unless_present = "stream")]
+        seconds: Option<f32>,
+
+        /// Fichier de capture hexadécimal produit
+        #[arg(long, value_name = "FICHIER", required_unless_present = "stream")]
+        out: Option<PathBuf>,
+
+        /// Écrire aussi les balayages complets en CSV
+        #[arg(long, value_name = "CSV", requires = "seconds")]
+        curves: Option<PathBuf>,
+
+        /// Afficher chaque courbe reçue sur la sortie standard : ses points
+        /// (par défaut) ou une ligne de mesures par balayage
+        #[arg(long, value_enum, value_name = "SORTIE", num_args = 0..=1)]
+        #[arg(default_missing_value = "points")]
+        stream: Option<StreamFormat>,
+    },
+
+    /// Lister les boîtiers connectés, les signatures de la bibliothèque ou
+    /// les styles d'export
+    List {
+        #[arg(value_enum, default_value_t = ListKind::Devices)]
+        kind: ListKind,
+    },
+
+    /// Configuration : fichier TOML, remplacé par les variables CT220S_* (`__`
+    /// entre les niveaux) puis par --set
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommand,
+    },
+
+    /// Envoyer un réglage au boîtier (ex. `cmd set-freq 500Hz`, `cmd set-volt 5`)
+    Cmd {
+        #[command(subcommand)]
+        setting: DeviceCommand,
+    },
+
+    /// Dérouler un plan de test YAML : points nommés, chacun avec ses
+    /// réglages, son export JSON de référence et sa tolérance, l'opérateur
+    /// étant invité entre deux points ; code de sortie 1 si un point échoue
+    /// ou n'est pas mesuré
+    RunPlan {
+        /// Plan de test YAML : `name`, `settings`, `tolerance` et `points`, chacun
+        /// avec `name`, `instructions`, `settings` (frequency, resistance,
+        /// mode, voltage), `reference`, `tolerance` et `capture` (fichier lu
+        /// à la place d'une acquisition)
+        plan: PathBuf,
+
+        /// Numéro de série de la carte testée, enregistré avec les résultats
+        #[arg(long, value_name = "SERIE")]
+        serial: Option<String>,
+
+        /// Écrire les résultats en JUnit XML
+        #[arg(long, value_name = "XML")]
+        junit: Option<PathBuf>,
+
+        /// Écrire le rapport de comparaison de tous les points (png, pdf ou html)
+        #[arg(long, value_name = "FICHIER")]
+        report: Option<PathBuf>,
+
+        /// Enchaîner les points sans attendre l'opérateur
+        #[arg(long)]
+        no_prompt: bool,
+    },
+}
+
+fn main() -> Result<(), eframe::Error> {
+    let args = Args::parse();
+    if let Err(e) = logging::init(args.log_file.as_deref()) {
+        eprintln!("Erreur: {}", e);
+        std::process::exit(1);
+    }
+    settings::set_cli_overrides(args.overrides);
+
+    let gui = match args.command {
+        None => args.gui,
+        Some(Command::Gui(gui)) => gui,
+        Some(command) => std::process::exit(run_headless(command, args.json)),
+    };
+    if gui.tui {
+        if let Err(e) = tui::run(gui.file.clone(), &gui.view_options()) {
+            eprintln!("Erreur: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([900.0, 700.0]),
+        ..Default::default()
+    };
+
+    eframe::run_native(
+        "CT220S V-I Curve Viewer",
+        options,
+        Box::new(move |cc| {
+            let view = gui.view_options();
+            Box::new(CT220SApp::new(cc, gui.file.clone(), gui.operator, &view))
+        }),
+    )
+}
+
+/// Exécuter une sous-commande sans interface, en JSON si `json` ; renvoie le
+/// code de sortie : 0 si la commande réussit, 1 pour un verdict FAIL ou une
+/// erreur (le message d'erreur les distingue)
+fn run_headless(command: Command, json: bool) -> i32 {
+    let result = match command {
+        Command::Gui(_) => Ok(true),
+        Command::Convert {
+            capture,
+            output,
+            batch,
+            out_dir,
+            format,
+            jobs,
+            mode,
+            sweep,
+            style,
+            size,
+            scale,
+        } => {
+            let style = style.as_deref();
+            let options = ConvertOptions {
+                mode,
+                sweep,
+                style,
+                size,
+                scale,
+            };
+            match (batch, capture, output) {
+                (Some(dir), _, _) => {
+                    cli::batch(&dir, out_dir.as_deref(), format, jobs, &options, json)
+                        .map(|_| true)
+                }
+                (None, Some(capture), Some(output)) => {
+                    cli::export(&capture, &output, &options, json).map(|_| true)
+                }
+                // Exclu par clap : capture et image requises sans --batch
+                _ => Err("Capture et image requises".to_string()),
+            }
+        }
+        Command::Test {
+            capture,
+            signature,
+            reference,
+            tolerance,
+            junit,
+            serial,
+            report,
+        } => cli::test(&TestOptions {
+            capture: capture.as_deref(),
+            signature: signature.as_deref(),
+            reference: reference.as_deref(),
+            tolerance,
+            serial: serial.as_deref().unwrap_or_default(),
+            junit: junit.as_deref(),
+            report: report.as_deref(),
+            json,
+        }),
+        Command::Compare {
+            capture,
+            reference,
+            sweep,
+            image,
+        } => cli::compare(&capture, &reference, sweep, image.as_deref(), json),
+        Command::Stats { capture } => cli::stats(&capture, json).map(|_| true),
+        Command::Shell => shell::run().map(|_| true),
+        Command::Validate { capture } => cli::validate(&capture, json),
+        Command::Capture {
+            seconds,
+            out,
+            curves,
+            stream,
+        } => cli::capture(&CaptureOptions {
+            seconds,
+            output: out.as_deref(),
+            curves: curves.as_deref(),
+            stream,
+            json,
+        })
+        .map(|_| true),
+        Command::List { kind } => cli::list(kind, json).map(|_| true),
+        Command::Config { action } => cli::config(&action, json).map(|_| true),
+        Command::Cmd { setting } => cli::command(&setting, json).map(|_| true),
+        Command::RunPlan {
+            plan,
+            serial,
+            junit,
+            report,
+            no_prompt,
+        } => plan::run(&PlanOptions {
+            plan: &plan,
+            serial: serial.as_deref().unwrap_or_default(),
+            junit: junit.as_deref(),
+            report: report.as_deref(),
+            prompt: !no_prompt,
+            json,
+        }),
+    };
+    match result {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(e) if json => {
+            eprintln!("{}", json_export::pretty(&serde_json::json!({ "error": e })));
+            1
+        }
+        Err(e) => {
+            eprintln!("Erreur: {}", e);
+            1
+        }
+    }
+}
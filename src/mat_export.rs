@@ -0,0 +1,190 @@
+// src/mat_export.rs
+
+use crate::curve::DualCurveData;
+use crate::timestamp::{format_timestamp, rfc3339_timestamp};
+use crate::session::Session;
+use crate::settings::Settings;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::info;
+
+/// Types des éléments de données d'un fichier MAT v5
+const MI_INT8: u32 = 1;
+const MI_UINT16: u32 = 4;
+const MI_INT32: u32 = 5;
+const MI_UINT32: u32 = 6;
+const MI_DOUBLE: u32 = 9;
+const MI_MATRIX: u32 = 14;
+
+/// Classes des tableaux MATLAB
+const MX_STRUCT_CLASS: u32 = 2;
+const MX_CHAR_CLASS: u32 = 4;
+const MX_DOUBLE_CLASS: u32 = 6;
+const MX_UINT16_CLASS: u32 = 11;
+
+/// Place réservée à chaque nom de champ d'une structure (31 caractères au plus)
+const FIELD_NAME_SIZE: usize = 32;
+
+/// Variable MATLAB minimale, suffisante pour l'export
+enum Mat {
+    /// Vecteur colonne de réels
+    Double(Vec<f64>),
+    /// Vecteur colonne d'entiers (valeurs brutes de l'ADC)
+    Uint16(Vec<u16>),
+    /// Chaîne de caractères
+    Char(String),
+    /// Structure 1×1
+    Struct(Vec<(&'static str, Mat)>),
+}
+
+impl Mat {
+    fn scalar(value: f64) -> Self {
+        Mat::Double(vec![value])
+    }
+
+    fn floats(values: &[f32]) -> Self {
+        Mat::Double(values.iter().map(|&v| v as f64).collect())
+    }
+
+    fn bool(value: bool) -> Self {
+        Mat::scalar(if value { 1.0 } else { 0.0 })
+    }
+
+    /// Élément miMATRIX complet : drapeaux, dimensions, nom et données
+    fn write(&self, out: &mut Vec<u8>, name: &str) {
+        let mut body = Vec::new();
+        let (class, dims) = match self {
+            Mat::Double(v) => (MX_DOUBLE_CLASS, [v.len(), 1]),
+            Mat::Uint16(v) => (MX_UINT16_CLASS, [v.len(), 1]),
+            Mat::Char(s) => {
+                let len = s.encode_utf16().count();
+                (MX_CHAR_CLASS, [len.min(1), len])
+            }
+            Mat::Struct(_) => (MX_STRUCT_CLASS, [1, 1]),
+        };
+        element(&mut body, MI_UINT32, &[class.to_le_bytes(), [0; 4]].concat());
+        let dims: Vec<u8> = dims.iter().flat_map(|&d| (d as i32).to_le_bytes()).collect();
+        element(&mut body, MI_INT32, &dims);
+        element(&mut body, MI_INT8, name.as_bytes());
+
+        match self {
+            Mat::Double(v) => {
+                let bytes: Vec<u8> = v.iter().flat_map(|x| x.to_le_bytes()).collect();
+                element(&mut body, MI_DOUBLE, &bytes);
+            }
+            Mat::Uint16(v) => {
+                let bytes: Vec<u8> = v.iter().flat_map(|x| x.to_le_bytes()).collect();
+                element(&mut body, MI_UINT16, &bytes);
+            }
+            Mat::Char(s) => {
+                let bytes: Vec<u8> = s.encode_utf16().flat_map(|c| c.to_le_bytes()).collect();
+                element(&mut body, MI_UINT16, &bytes);
+            }
+            Mat::Struct(fields) => {
+                // Longueur des noms de champs : élément compact (type et taille
+                // sur 4 octets, valeur sur les 4 suivants)
+                body.extend_from_slice(&(MI_INT32 | 4 << 16).to_le_bytes());
+                body.extend_from_slice(&(FIELD_NAME_SIZE as u32).to_le_bytes());
+                let mut names = Vec::new();
+                for (field, _) in fields {
+                    let mut name = field.as_bytes().to_vec();
+                    name.resize(FIELD_NAME_SIZE, 0);
+                    names.extend(name);
+                }
+                element(&mut body, MI_INT8, &names);
+                for (_, value) in fields {
+                    value.write(&mut body, "");
+                }
+            }
+        }
+        element(out, MI_MATRIX, &body);
+    }
+}
+
+/// Élément de données : type, taille, contenu complété à 8 octets
+fn element(out: &mut Vec<u8>, kind: u32, data: &[u8]) {
+    out.extend_from_slice(&kind.to_le_bytes());
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend_from_slice(data);
+    out.resize(out.len().next_multiple_of(8), 0);
+}
+
+/// Exporter les courbes en fichier MATLAB v5 (.mat) : pour chaque canal, les
+/// vecteurs `chN_voltage` et `chN_current` normalisés, `chN_raw_voltage` et
+/// `chN_raw_current` bruts de l'ADC et, si la calibration est active,
+/// `chN_voltage_v` et `chN_current_ma` ; la structure `metadata` reprend la
+/// source, les notes, les réglages et la calibration
+pub fn save_curves_as_mat(
+    data: &DualCurveData,
+    channels: &[u8],
+    settings: &Settings,
+    session: &Session,
+    path: &Path,
+) -> Result<(), String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let calibration = &settings.calibration;
+
+    let mut variables: Vec<(String, Mat)> = Vec::new();
+    for curve in channels.iter().filter_map(|&c| data.channel(c)) {
+        let name = |suffix: &str| format!("ch{}_{}", curve.channel, suffix);
+        variables.push((name("voltage"), Mat::floats(&curve.voltage)));
+        variables.push((name("current"), Mat::floats(&curve.current)));
+        if !curve.raw_voltage.is_empty() {
+            variables.push((name("raw_voltage"), Mat::Uint16(curve.raw_voltage.clone())));
+            variables.push((name("raw_current"), Mat::Uint16(curve.raw_current.clone())));
+        }
+        if calibration.enabled {
+            let scaled = |values: &[f32], factor: f32| {
+                Mat::Double(values.iter().map(|&v| (v * factor) as f64).collect())
+            };
+            let volts = scaled(&curve.voltage, calibration.volts_full_scale);
+            let milliamps = scaled(&curve.current, calibration.milliamps_full_scale);
+            variables.push((name("voltage_v"), volts));
+            variables.push((name("current_ma"), milliamps));
+        }
+    }
+    let metadata = Mat::Struct(vec![
+        ("application", Mat::Char(env!("CARGO_PKG_NAME").to_string())),
+        ("version", Mat::Char(env!("CARGO_PKG_VERSION").to_string())),
+        ("exported_at", Mat::scalar(now as f64)),
+        ("exported_at_utc", Mat::Char(rfc3339_timestamp(now))),
+        ("source", Mat::Char(session.title())),
+        ("notes", Mat::Char(session.notes.clone())),
+        ("channels", Mat::Double(channels.iter().map(|&c| c as f64).collect())),
+        ("display_mode", Mat::Char(format!("{:?}", settings.display_mode))),
+        ("log_current", Mat::bool(settings.axes.log_current)),
+        ("swap_axes", Mat::bool(settings.axes.swap)),
+        ("calibration_enabled", Mat::bool(calibration.enabled)),
+        ("volts_full_scale", Mat::scalar(calibration.volts_full_scale as f64)),
+        ("milliamps_full_scale", Mat::scalar(calibration.milliamps_full_scale as f64)),
+    ]);
+    variables.push(("metadata".to_string(), metadata));
+
+    // En-tête de 128 octets : texte, décalage du sous-système, version 0x0100
+    // et indicateur d'ordre des octets « IM » (petit-boutiste)
+    let mut content = format!(
+        "MATLAB 5.0 MAT-file, Platform: {}, Created by: {} {} on {}",
+        std::env::consts::OS,
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        format_timestamp(now)
+    )
+    .into_bytes();
+    content.resize(116, b' ');
+    content.extend_from_slice(&[0; 8]);
+    content.extend_from_slice(&0x0100u16.to_le_bytes());
+    content.extend_from_slice(b"IM");
+    for (name, value) in &variables {
+        value.write(&mut content, name);
+    }
+
+    fs::write(path, content)
+        .map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+
+    info!("MAT sauvegardé : {}", path.display());
+    Ok(())
+}
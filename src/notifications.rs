@@ -0,0 +1,113 @@
+// src/notifications.rs
+
+use eframe::egui;
+use std::time::{Duration, Instant};
+
+/// Nombre maximal de notifications affichées simultanément
+const MAX_TOASTS: usize = 5;
+
+/// Niveau d'une notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Level {
+    /// Durée d'affichage : les erreurs restent plus longtemps
+    fn timeout(self) -> Duration {
+        match self {
+            Level::Info | Level::Success => Duration::from_secs(3),
+            Level::Warning => Duration::from_secs(5),
+            Level::Error => Duration::from_secs(8),
+        }
+    }
+
+    fn icon(self) -> &'static str {
+        match self {
+            Level::Info => "ℹ",
+            Level::Success => "✔",
+            Level::Warning => "⚠",
+            Level::Error => "❌",
+        }
+    }
+
+    fn color(self) -> egui::Color32 {
+        match self {
+            Level::Info => egui::Color32::from_rgb(0, 120, 200),
+            Level::Success => egui::Color32::from_rgb(0, 150, 0),
+            Level::Warning => egui::Color32::from_rgb(220, 140, 0),
+            Level::Error => egui::Color32::from_rgb(200, 0, 0),
+        }
+    }
+}
+
+struct Toast {
+    level: Level,
+    text: String,
+    created: Instant,
+}
+
+/// Notifications éphémères affichées en bas à droite de la fenêtre
+#[derive(Default)]
+pub struct Notifications {
+    toasts: Vec<Toast>,
+}
+
+impl Notifications {
+    pub fn push(&mut self, level: Level, text: impl Into<String>) {
+        self.toasts.push(Toast {
+            level,
+            text: text.into(),
+            created: Instant::now(),
+        });
+        if self.toasts.len() > MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+    }
+
+    /// Afficher les notifications en cours ; un clic en ferme une
+    pub fn show(&mut self, ctx: &egui::Context) {
+        self.toasts
+            .retain(|toast| toast.created.elapsed() < toast.level.timeout());
+        // Se réveiller pour retirer la prochaine notification expirée
+        if let Some(remaining) = self
+            .toasts
+            .iter()
+            .map(|toast| toast.level.timeout().saturating_sub(toast.created.elapsed()))
+            .min()
+        {
+            ctx.request_repaint_after(remaining);
+        }
+
+        let mut closed = None;
+        for (i, toast) in self.toasts.iter().rev().enumerate() {
+            let offset = egui::vec2(-10.0, -40.0 - 44.0 * i as f32);
+            egui::Area::new(egui::Id::new("toast").with(toast.created))
+                .anchor(egui::Align2::RIGHT_BOTTOM, offset)
+                .order(egui::Order::Foreground)
+                .interactable(true)
+                .show(ctx, |ui| {
+                    let frame = egui::Frame::popup(ui.style())
+                        .stroke(egui::Stroke::new(1.5, toast.level.color()));
+                    let response = frame
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.colored_label(toast.level.color(), toast.level.icon());
+                                ui.label(&toast.text);
+                            });
+                        })
+                        .response
+                        .interact(egui::Sense::click());
+                    if response.clicked() {
+                        closed = Some(toast.created);
+                    }
+                });
+        }
+        if let Some(created) = closed {
+            self.toasts.retain(|toast| toast.created != created);
+        }
+    }
+}
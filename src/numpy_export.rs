@@ -0,0 +1,113 @@
+// src/numpy_export.rs
+
+use crate::curve::DualCurveData;
+use crate::settings::Settings;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use tracing::info;
+use zip::result::ZipResult;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Exporter les courbes en archive NumPy (.npz), lisible par `np.load` : pour
+/// chaque canal, les tableaux normalisés `chN_voltage` et `chN_current`
+/// (float32), les valeurs brutes de l'ADC `chN_raw_voltage` et
+/// `chN_raw_current` (uint16) et, si la calibration est active, les valeurs
+/// physiques `chN_voltage_v` et `chN_current_ma`
+pub fn save_curves_as_npz(
+    data: &DualCurveData,
+    channels: &[u8],
+    settings: &Settings,
+    path: &Path,
+) -> Result<(), String> {
+    let calibration = &settings.calibration;
+    let mut arrays = Vec::new();
+    for curve in channels.iter().filter_map(|&c| data.channel(c)) {
+        let name = |suffix: &str| format!("ch{}_{}", curve.channel, suffix);
+        arrays.push((name("voltage"), npy_f32(&curve.voltage)));
+        arrays.push((name("current"), npy_f32(&curve.current)));
+        if !curve.raw_voltage.is_empty() {
+            arrays.push((name("raw_voltage"), npy_u16(&curve.raw_voltage)));
+            arrays.push((name("raw_current"), npy_u16(&curve.raw_current)));
+        }
+        if calibration.enabled {
+            let scaled = |values: &[f32], factor: f32| -> Vec<f32> {
+                values.iter().map(|v| v * factor).collect()
+            };
+            let volts = scaled(&curve.voltage, calibration.volts_full_scale);
+            let milliamps = scaled(&curve.current, calibration.milliamps_full_scale);
+            arrays.push((name("voltage_v"), npy_f32(&volts)));
+            arrays.push((name("current_ma"), npy_f32(&milliamps)));
+        }
+    }
+
+    // Tableaux stockés sans compression, comme `np.savez`
+    let write = || -> ZipResult<()> {
+        let mut zip = ZipWriter::new(File::create(path)?);
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, data) in &arrays {
+            zip.start_file(format!("{}.npy", name), options)?;
+            zip.write_all(data)?;
+        }
+        zip.finish()?;
+        Ok(())
+    };
+    write().map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+
+    info!("NumPy sauvegardé : {}", path.display());
+    Ok(())
+}
+
+fn npy_f32(values: &[f32]) -> Vec<u8> {
+    npy("<f4", values.len(), values.iter().flat_map(|v| v.to_le_bytes()))
+}
+
+fn npy_u16(values: &[u16]) -> Vec<u8> {
+    npy("<u2", values.len(), values.iter().flat_map(|v| v.to_le_bytes()))
+}
+
+/// Fichier .npy (format 1.0) d'un tableau à une dimension de type `descr`
+fn npy(descr: &str, len: usize, bytes: impl Iterator<Item = u8>) -> Vec<u8> {
+    let mut header = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': ({},), }}",
+        descr, len
+    );
+    // Préambule (10 octets) et en-tête terminé par \n : multiple de 64 octets
+    let total = (10 + header.len() + 1).div_ceil(64) * 64;
+    header.push_str(&" ".repeat(total - 10 - header.len() - 1));
+    header.push('\n');
+
+    let mut out = b"\x93NUMPY\x01\x00".to_vec();
+    out.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    out.extend_from_slice(header.as_bytes());
+    out.extend(bytes);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn npy_header_is_aligned() {
+        let npy = npy_u16(&[1, 2, 0x0304]);
+        assert_eq!(&npy[..8], b"\x93NUMPY\x01\x00");
+        let header_len = u16::from_le_bytes([npy[8], npy[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+        let header = std::str::from_utf8(&npy[10..10 + header_len]).unwrap();
+        assert!(header.starts_with("{'descr': '<u2', 'fortran_order': False, 'shape': (3,), }"));
+        assert!(header.ends_with('\n'));
+        assert_eq!(&npy[10 + header_len..], [1, 0, 2, 0, 4, 3]);
+    }
+
+    #[test]
+    fn npy_data_is_little_endian_float32() {
+        let npy = npy_f32(&[1.0, -0.5]);
+        let header_len = u16::from_le_bytes([npy[8], npy[9]]) as usize;
+        let header = std::str::from_utf8(&npy[10..10 + header_len]).unwrap();
+        assert!(header.contains("'descr': '<f4'") && header.contains("'shape': (2,)"));
+        let data = [1.0f32.to_le_bytes(), (-0.5f32).to_le_bytes()].concat();
+        assert_eq!(&npy[10 + header_len..], data);
+    }
+}
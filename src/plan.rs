@@ -0,0 +1,677 @@
+// src/plan.rs
+
+use crate::backend::{
+    acquire_curves, load_capture_curves, Command, DeviceSetting, HidBackend, DEVICE_SETTINGS,
+};
+use crate::cli::{self, parse_tolerance, Reference, ACQUIRE_TIMEOUT};
+use crate::comparison_report::{save_comparison_report, ChannelDeviation, ReportFormat};
+use crate::curve::DualCurveData;
+use crate::i18n::{tr, tr_args};
+use crate::json_export::pretty;
+use crate::json_import::load_curves_from_json;
+use crate::junit::{save_junit, TestCase};
+use crate::report;
+use crate::results_db::{ResultsDb, TestRecord};
+use crate::session::Session;
+use crate::timestamp::format_timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufRead, Lines, StdinLock};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+/// Délai laissé au boîtier après l'envoi des réglages d'un point, avant
+/// l'acquisition
+const SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+/// Valeur saisie en nombre ou en texte dans le plan (`5` ou `5V`, `5` ou `5%`)
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Scalar {
+    Number(f64),
+    Text(String),
+}
+
+impl Scalar {
+    fn text(&self) -> String {
+        match self {
+            Scalar::Number(n) => n.to_string(),
+            Scalar::Text(s) => s.clone(),
+        }
+    }
+}
+
+/// Plan de test tel qu'écrit en YAML ; `settings` et `tolerance` valent pour
+/// tous les points qui ne les précisent pas
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlanFile {
+    name: Option<String>,
+    #[serde(default)]
+    settings: BTreeMap<String, Scalar>,
+    tolerance: Option<Scalar>,
+    points: Vec<PointFile>,
+}
+
+/// Point de test du fichier YAML ; chemins relatifs au plan
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PointFile {
+    name: String,
+    /// Consigne affichée à l'opérateur avant la mesure
+    instructions: Option<String>,
+    /// Réglages du boîtier (`frequency`, `resistance`, `mode`, `voltage`)
+    #[serde(default)]
+    settings: BTreeMap<String, Scalar>,
+    /// Export JSON (avec points) servant de référence
+    reference: PathBuf,
+    tolerance: Option<Scalar>,
+    /// Fichier de capture lu à la place d'une acquisition (essai sans boîtier)
+    capture: Option<PathBuf>,
+}
+
+/// Point de test prêt à dérouler : réglages résolus, référence chargée
+struct TestPoint {
+    name: String,
+    instructions: Option<String>,
+    /// Commande HID, réglage et libellé de chaque option, dans l'ordre de
+    /// `DEVICE_SETTINGS`
+    settings: Vec<(Command, &'static DeviceSetting, &'static str)>,
+    reference: Reference,
+    /// Seuil PASS/FAIL (%)
+    threshold: f32,
+    capture: Option<PathBuf>,
+}
+
+impl TestPoint {
+    /// Réglages du point, identifiant et libellé
+    fn device(&self) -> BTreeMap<String, String> {
+        self.settings
+            .iter()
+            .map(|&(_, setting, label)| (setting.id.to_string(), label.to_string()))
+            .collect()
+    }
+}
+
+/// Plan de test chargé : tout est vérifié avant le premier point, pour ne pas
+/// interrompre une série de mesures sur une faute de frappe
+struct Plan {
+    name: String,
+    points: Vec<TestPoint>,
+}
+
+impl Plan {
+    fn load(path: &Path, default_threshold: f32) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Impossible de lire {}: {}", path.display(), e))?;
+        let file: PlanFile = serde_yaml::from_str(&text)
+            .map_err(|e| format!("Plan de test invalide {}: {}", path.display(), e))?;
+        if file.points.is_empty() {
+            return Err(format!("Aucun point de test dans {}", path.display()));
+        }
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let threshold = |tolerance: Option<&Scalar>| -> Result<f32, String> {
+            Ok(match tolerance {
+                Some(tolerance) => 100.0 - parse_tolerance(&tolerance.text())?,
+                None => default_threshold,
+            })
+        };
+        let plan_threshold = threshold(file.tolerance.as_ref())?;
+
+        let mut points = Vec::new();
+        for point in file.points {
+            let context = |e: String| format!("Point {}: {}", point.name, e);
+            let mut values: BTreeMap<&str, String> =
+                file.settings.iter().map(|(id, value)| (id.as_str(), value.text())).collect();
+            values.extend(point.settings.iter().map(|(id, value)| (id.as_str(), value.text())));
+            let mut settings = values
+                .iter()
+                .map(|(id, value)| cli::resolve_option(id, value))
+                .collect::<Result<Vec<_>, String>>()
+                .map_err(context)?;
+            let order = |setting: &DeviceSetting| {
+                DEVICE_SETTINGS.iter().position(|s| s.id == setting.id)
+            };
+            settings.sort_by_key(|&(_, setting, _)| order(setting));
+
+            let reference = Reference {
+                name: point.name.clone(),
+                curves: load_curves_from_json(&dir.join(&point.reference)).map_err(context)?,
+            };
+            let threshold = match &point.tolerance {
+                Some(_) => threshold(point.tolerance.as_ref()).map_err(context)?,
+                None => plan_threshold,
+            };
+            points.push(TestPoint {
+                name: point.name,
+                instructions: point.instructions,
+                settings,
+                reference,
+                threshold,
+                capture: point.capture.map(|capture| dir.join(capture)),
+            });
+        }
+        let name = file.name.unwrap_or_else(|| {
+            path.file_stem()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+        Ok(Self { name, points })
+    }
+}
+
+/// Issue d'un point de test
+enum Outcome {
+    Measured(Vec<ChannelDeviation>),
+    /// Passé par l'opérateur
+    Skipped,
+    /// Acquisition ou comparaison impossible
+    Error(String),
+    /// Plan arrêté avant ce point
+    Untested,
+}
+
+impl Outcome {
+    fn pass(&self) -> bool {
+        matches!(self, Outcome::Measured(deviations) if deviations.iter().all(|d| d.pass))
+    }
+
+    fn status(&self) -> &'static str {
+        match self {
+            Outcome::Measured(_) if self.pass() => "pass",
+            Outcome::Measured(_) => "fail",
+            Outcome::Skipped => "skipped",
+            Outcome::Error(_) => "error",
+            Outcome::Untested => "untested",
+        }
+    }
+}
+
+/// Réponse de l'opérateur entre deux points
+enum Answer {
+    Continue,
+    Skip,
+    Retry,
+    Quit,
+}
+
+/// Options de la sous-commande `run-plan`
+pub struct PlanOptions<'a> {
+    pub plan: &'a Path,
+    /// Numéro de série de la carte testée, enregistré avec les résultats
+    pub serial: &'a str,
+    pub junit: Option<&'a Path>,
+    /// Rapport de comparaison de tous les points (png, pdf ou html)
+    pub report: Option<&'a Path>,
+    /// Attendre l'opérateur avant chaque point
+    pub prompt: bool,
+    /// Bilan en JSON, échanges avec l'opérateur sur la sortie d'erreur
+    pub json: bool,
+}
+
+/// Déroulement d'un plan : boîtier ouvert au premier point qui l'utilise,
+/// réponses de l'opérateur lues sur l'entrée standard
+struct Runner<'a> {
+    options: &'a PlanOptions<'a>,
+    backend: Option<HidBackend>,
+    input: Lines<StdinLock<'static>>,
+}
+
+impl Runner<'_> {
+    /// Message à l'opérateur ; sur la sortie d'erreur si la sortie standard
+    /// reçoit le JSON
+    fn say(&self, message: &str) {
+        if self.options.json {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message);
+        }
+    }
+
+    /// Demander la suite à l'opérateur ; arrêt à la fin de l'entrée
+    fn ask(&mut self, question: &str) -> Result<Answer, String> {
+        self.say(question);
+        let Some(line) = self.input.next() else {
+            return Ok(Answer::Quit);
+        };
+        let line = line.map_err(|e| format!("Erreur de lecture: {}", e))?;
+        Ok(match line.trim().to_lowercase().as_str() {
+            "s" => Answer::Skip,
+            "r" => Answer::Retry,
+            "q" => Answer::Quit,
+            _ => Answer::Continue,
+        })
+    }
+
+    fn backend(&mut self) -> Result<&HidBackend, String> {
+        if self.backend.is_none() {
+            self.backend = Some(HidBackend::new()?);
+        }
+        self.backend.as_ref().ok_or_else(|| "Boîtier indisponible".to_string())
+    }
+
+    /// Régler le boîtier, acquérir un balayage de chaque canal (ou lire la
+    /// capture du point) et le comparer à la référence
+    fn measure(&mut self, point: &TestPoint) -> Result<Vec<ChannelDeviation>, String> {
+        let curves = match &point.capture {
+            Some(path) => load_capture_curves(&path.to_string_lossy())?,
+            None => {
+                let backend = self.backend()?;
+                for &(cmd, _, _) in &point.settings {
+                    backend.send_cmd(cmd)?;
+                }
+                if !point.settings.is_empty() {
+                    thread::sleep(SETTLE_DELAY);
+                }
+                let device = backend.clone_device();
+                let device = device.lock().unwrap();
+                acquire_curves(&device, ACQUIRE_TIMEOUT)?
+            }
+        };
+        // Dernier balayage de chaque canal
+        let mut data = DualCurveData::new();
+        for curve in curves {
+            data.store(curve);
+        }
+        let deviations: Vec<ChannelDeviation> = [0, 1]
+            .into_iter()
+            .filter_map(|c| data.channel(c))
+            .filter_map(|curve| {
+                let reference = point.reference.for_channel(curve.channel)?;
+                Some(ChannelDeviation::new(curve, reference, &point.name, point.threshold))
+            })
+            .collect();
+        if deviations.is_empty() {
+            return Err("Aucun canal mesuré n'a de référence".to_string());
+        }
+        Ok(deviations)
+    }
+
+    /// Dérouler un point jusqu'à un résultat retenu, et poursuivre le plan ou
+    /// non selon l'opérateur
+    fn run_point(&mut self, point: &TestPoint) -> Result<(Outcome, bool), String> {
+        if self.options.prompt {
+            match self.ask(&tr("plan-ask-measure"))? {
+                Answer::Skip => return Ok((Outcome::Skipped, true)),
+                Answer::Quit => return Ok((Outcome::Untested, false)),
+                Answer::Continue | Answer::Retry => {}
+            }
+        }
+        loop {
+            let outcome = match self.measure(point) {
+                Ok(deviations) => Outcome::Measured(deviations),
+                Err(e) => Outcome::Error(e),
+            };
+            match &outcome {
+                Outcome::Measured(deviations) => {
+                    for deviation in deviations {
+                        let verdict = if deviation.pass { "PASS" } else { "FAIL" };
+                        self.say(&tr_args(
+                            "plan-channel-result",
+                            &[
+                                ("point", point.name.as_str().into()),
+                                ("channel", deviation.live.channel.into()),
+                                ("result", verdict.into()),
+                                ("score", format!("{:.1}", deviation.score).into()),
+                            ],
+                        ));
+                    }
+                }
+                Outcome::Error(e) => self.say(&tr_args(
+                    "plan-point-error",
+                    &[
+                        ("point", point.name.as_str().into()),
+                        ("error", e.as_str().into()),
+                    ],
+                )),
+                Outcome::Skipped | Outcome::Untested => {}
+            }
+            if outcome.pass() || !self.options.prompt {
+                return Ok((outcome, true));
+            }
+            match self.ask(&tr("plan-ask-retry"))? {
+                Answer::Retry => continue,
+                Answer::Quit => return Ok((outcome, false)),
+                Answer::Continue | Answer::Skip => return Ok((outcome, true)),
+            }
+        }
+    }
+}
+
+/// Dérouler un plan de test YAML : pour chaque point, consigne à l'opérateur,
+/// réglages du boîtier, acquisition et comparaison à la référence du point,
+/// puis bilan de tous les points (JUnit XML, base de résultats, rapport de
+/// comparaison). Renvoie `true` si tous les points sont mesurés et passent
+pub fn run(options: &PlanOptions) -> Result<bool, String> {
+    let mut settings = cli::load_settings();
+    let format = match options.report {
+        Some(path) => Some(
+            ReportFormat::from_path(path)
+                .ok_or_else(|| format!("Format de rapport non reconnu: {}", path.display()))?,
+        ),
+        None => None,
+    };
+    let default_threshold = settings.analysis.pass_threshold;
+    let plan = Plan::load(options.plan, default_threshold)?;
+    let mut runner = Runner {
+        options,
+        backend: None,
+        input: io::stdin().lock().lines(),
+    };
+
+    let started = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let count = plan.points.len();
+    let mut outcomes = Vec::new();
+    let mut records = Vec::new();
+    let mut cases = Vec::new();
+    for (i, point) in plan.points.iter().enumerate() {
+        let title = tr_args(
+            "plan-point",
+            &[
+                ("index", (i + 1).into()),
+                ("count", count.into()),
+                ("name", point.name.as_str().into()),
+            ],
+        );
+        runner.say(&format!("\n{}", title));
+        if let Some(instructions) = &point.instructions {
+            runner.say(&format!("  {}", instructions));
+        }
+        if !point.settings.is_empty() {
+            let labels: Vec<String> = point
+                .settings
+                .iter()
+                .map(|(_, setting, label)| format!("{}={}", setting.id, label))
+                .collect();
+            let labels = tr_args("plan-settings", &[("settings", labels.join(", ").into())]);
+            runner.say(&format!("  {}", labels));
+        }
+        let time = Instant::now();
+        let (outcome, go_on) = runner.run_point(point)?;
+        if let Outcome::Measured(deviations) = &outcome {
+            settings.analysis.pass_threshold = point.threshold;
+            let device = TestRecord::settings_text(&point.device(), &settings);
+            let elapsed = time.elapsed().as_secs_f32();
+            for deviation in deviations {
+                cases.push(TestCase {
+                    name: format!("{} CH{}", point.name, deviation.live.channel),
+                    classname: plan.name.clone(),
+                    score: deviation.score,
+                    pass: deviation.pass,
+                    threshold: point.threshold,
+                    time: elapsed,
+                });
+                records.push(TestRecord {
+                    time: started,
+                    serial: options.serial.to_string(),
+                    test_point: point.name.clone(),
+                    channel: deviation.live.channel,
+                    settings: device.clone(),
+                    score: deviation.score,
+                    pass: deviation.pass,
+                    curve: deviation.live.clone(),
+                });
+            }
+        }
+        outcomes.push(outcome);
+        if !go_on {
+            break;
+        }
+    }
+    outcomes.resize_with(count, || Outcome::Untested);
+    let pass = outcomes.iter().all(Outcome::pass);
+
+    let tally = |status: &str| outcomes.iter().filter(|o| o.status() == status).count();
+    let summary = tr_args(
+        "plan-summary",
+        &[
+            ("count", count.into()),
+            ("pass", tally("pass").into()),
+            ("fail", tally("fail").into()),
+            ("error", tally("error").into()),
+            ("skipped", tally("skipped").into()),
+            ("untested", tally("untested").into()),
+        ],
+    );
+    if options.json {
+        println!("{}", pretty(&plan_json(&plan, options.serial, &outcomes, pass)));
+    } else {
+        println!("\n{}", summary);
+        let result = if pass { "PASS" } else { "FAIL" };
+        println!("{}", tr_args("plan-result", &[("result", result.into())]));
+    }
+
+    // Seuil par défaut pour les bilans : chaque cas porte le sien
+    settings.analysis.pass_threshold = default_threshold;
+    if let Some(path) = options.junit {
+        save_junit(&plan.name, &cases, settings.analysis.pass_threshold, path)?;
+    }
+    if settings.log_results && !records.is_empty() {
+        // Le verdict est déjà affiché : un échec d'enregistrement ne le change pas
+        if let Err(e) = ResultsDb::open().and_then(|mut db| db.record(&records, &settings)) {
+            warn!("{}", e);
+        }
+    }
+    if let (Some(path), Some(format)) = (options.report, format) {
+        let deviations: Vec<ChannelDeviation> = outcomes
+            .into_iter()
+            .filter_map(|outcome| match outcome {
+                Outcome::Measured(deviations) => Some(deviations),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        let header = plan_header(&plan, options.serial, started, &summary);
+        let session = Session::snapshot(plan.name.clone(), DualCurveData::new(), &settings);
+        let mut fields = report::fields(&session, &DualCurveData::new(), &[], &settings, None);
+        fields.insert("summary", summary);
+        fields.insert("verdict", if pass { "PASS" } else { "FAIL" }.to_string());
+        save_comparison_report(&deviations, &header, fields, &settings, format, path)?;
+    }
+    Ok(pass)
+}
+
+/// En-tête du rapport du plan : plan, carte, date et bilan
+fn plan_header(plan: &Plan, serial: &str, time: u64, summary: &str) -> Vec<String> {
+    let title = tr_args("plan-title", &[("name", plan.name.as_str().into())]);
+    let mut header = vec![title];
+    if !serial.is_empty() {
+        header.push(tr_args("plan-board", &[("serial", serial.into())]));
+    }
+    header.push(tr_args("print-date", &[("date", format_timestamp(time).into())]));
+    header.push(summary.to_string());
+    header
+}
+
+/// Bilan de `run-plan --json`
+#[derive(Serialize)]
+struct PlanJson<'a> {
+    plan: &'a str,
+    serial: &'a str,
+    points: Vec<PointJson<'a>>,
+    pass: bool,
+}
+
+/// Issue, réglages et scores d'un point
+#[derive(Serialize)]
+struct PointJson<'a> {
+    name: &'a str,
+    status: &'static str,
+    threshold: f32,
+    settings: BTreeMap<String, String>,
+    channels: Vec<ChannelJson>,
+    error: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct ChannelJson {
+    channel: u8,
+    score: f32,
+    pass: bool,
+}
+
+/// Bilan de `run-plan --json` : issue, réglages et scores de chaque point
+fn plan_json<'a>(
+    plan: &'a Plan,
+    serial: &'a str,
+    outcomes: &'a [Outcome],
+    pass: bool,
+) -> PlanJson<'a> {
+    let points = plan
+        .points
+        .iter()
+        .zip(outcomes)
+        .map(|(point, outcome)| {
+            let channels = match outcome {
+                Outcome::Measured(deviations) => deviations
+                    .iter()
+                    .map(|deviation| ChannelJson {
+                        channel: deviation.live.channel,
+                        score: deviation.score,
+                        pass: deviation.pass,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            PointJson {
+                name: &point.name,
+                status: outcome.status(),
+                threshold: point.threshold,
+                settings: point.device(),
+                channels,
+                error: match outcome {
+                    Outcome::Error(e) => Some(e),
+                    _ => None,
+                },
+            }
+        })
+        .collect();
+    PlanJson { plan: &plan.name, serial, points, pass }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REFERENCE: &str = r#"{ "curves": [ { "channel": 0, "voltage": [1], "current": [2] } ] }"#;
+
+    /// Plan écrit dans un dossier temporaire, à côté de sa référence
+    fn load(yaml: &str) -> Result<Plan, String> {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("golden.json"), REFERENCE).unwrap();
+        let path = dir.path().join("carte.yaml");
+        fs::write(&path, yaml).unwrap();
+        Plan::load(&path, 90.0)
+    }
+
+    fn labels(point: &TestPoint) -> Vec<(&str, &str)> {
+        point
+            .settings
+            .iter()
+            .map(|&(_, setting, label)| (setting.id, label))
+            .collect()
+    }
+
+    #[test]
+    fn point_settings_override_plan_settings() {
+        let plan = load(
+            "name: Carte alim
+settings:
+  voltage: 5
+  frequency: 500Hz
+points:
+  - name: D3
+    reference: golden.json
+    capture: captures/d3.hex
+  - name: R12
+    reference: golden.json
+    settings:
+      voltage: 10V
+      resistance: 1K
+",
+        )
+        .unwrap();
+        assert_eq!(plan.name, "Carte alim");
+        assert_eq!(
+            labels(&plan.points[0]),
+            [("frequency", "500Hz"), ("voltage", "5V")]
+        );
+        assert_eq!(
+            labels(&plan.points[1]),
+            [
+                ("frequency", "500Hz"),
+                ("resistance", "1K"),
+                ("voltage", "10V")
+            ]
+        );
+        let reference = &plan.points[0].reference;
+        assert_eq!(reference.name, "D3");
+        assert_eq!(reference.curves[0].voltage, [1.0]);
+    }
+
+    #[test]
+    fn capture_is_relative_to_the_plan() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("golden.json"), REFERENCE).unwrap();
+        let path = dir.path().join("carte.yaml");
+        fs::write(
+            &path,
+            "points:
+  - name: D3
+    reference: golden.json
+    capture: captures/d3.hex
+  - name: R12
+    reference: golden.json
+",
+        )
+        .unwrap();
+        let plan = Plan::load(&path, 90.0).unwrap();
+        assert_eq!(plan.name, "carte");
+        assert_eq!(
+            plan.points[0].capture.as_deref(),
+            Some(dir.path().join("captures/d3.hex").as_path())
+        );
+        assert_eq!(plan.points[1].capture, None);
+    }
+
+    #[test]
+    fn point_tolerance_overrides_plan_tolerance() {
+        let plan = load(
+            "tolerance: 15
+points:
+  - name: D3
+    reference: golden.json
+  - name: R12
+    reference: golden.json
+    tolerance: 5%
+",
+        )
+        .unwrap();
+        assert_eq!(plan.points[0].threshold, 85.0);
+        assert_eq!(plan.points[1].threshold, 95.0);
+
+        let plan = load("points:\n  - name: D3\n    reference: golden.json\n").unwrap();
+        assert_eq!(plan.points[0].threshold, 90.0);
+    }
+
+    #[test]
+    fn invalid_plans_are_rejected() {
+        for yaml in [
+            "colour: red\npoints:\n  - name: D3\n    reference: golden.json\n",
+            "points:\n  - name: D3\n    reference: golden.json\n    voltag: 5\n",
+            "points:\n  - name: D3\n    reference: golden.json\n    settings:\n      volume: 5\n",
+            "points:\n  - name: D3\n    reference: golden.json\n    settings:\n      voltage: 7V\n",
+            "points:\n  - name: D3\n    reference: golden.json\n    tolerance: 150\n",
+            "points:\n  - name: D3\n    reference: absent.json\n",
+            "points: []\n",
+        ] {
+            assert!(load(yaml).is_err(), "{}", yaml);
+        }
+    }
+}
@@ -0,0 +1,324 @@
+// src/plot_renderer.rs
+
+use crate::annotation::Annotation;
+use crate::axes::PlotAxes;
+use crate::curve::CurveData;
+use crate::i18n::tr;
+use crate::settings::{Markers, Settings};
+use eframe::egui::{self, Align2, Color32, Pos2, Rect, Stroke, Vec2};
+
+/// Longueurs d'un trait et d'un blanc des courbes en pointillés
+const DASH: f32 = 6.0;
+const GAP: f32 = 4.0;
+
+/// Longueur des branches de la pointe des flèches d'annotation
+const ARROW_HEAD: f32 = 10.0;
+
+/// Couleur de la courbe de référence, distincte des deux canaux, à l'écran
+/// comme à l'export
+pub const REFERENCE_COLOR: Color32 = Color32::from_rgb(0, 170, 90);
+
+/// Cible de dessin d'un tracé : peintre egui à l'écran, image, SVG ou PDF à
+/// l'export. Grille, axes et courbes ne sont décrits qu'une fois, ici
+pub trait PlotTarget {
+    /// Segment de `stroke.width` pixels d'épaisseur
+    fn line(&mut self, from: Pos2, to: Pos2, stroke: Stroke);
+    /// Texte de hauteur `size`, placé autour de `pos` selon `anchor`
+    fn text(&mut self, pos: Pos2, anchor: Align2, text: &str, size: f32, color: Color32);
+
+    /// Ligne brisée
+    fn polyline(&mut self, points: &[Pos2], stroke: Stroke) {
+        for pair in points.windows(2) {
+            self.line(pair[0], pair[1], stroke);
+        }
+    }
+
+    /// Cercle (contour), approché par un polygone
+    fn circle(&mut self, center: Pos2, radius: f32, stroke: Stroke) {
+        let points: Vec<Pos2> = (0..=24)
+            .map(|k| {
+                let angle = k as f32 / 24.0 * std::f32::consts::TAU;
+                center + radius * egui::vec2(angle.cos(), angle.sin())
+            })
+            .collect();
+        self.polyline(&points, stroke);
+    }
+}
+
+impl PlotTarget for egui::Painter {
+    fn line(&mut self, from: Pos2, to: Pos2, stroke: Stroke) {
+        self.line_segment([from, to], stroke);
+    }
+
+    fn text(&mut self, pos: Pos2, anchor: Align2, text: &str, size: f32, color: Color32) {
+        let font = egui::FontId::proportional(size);
+        egui::Painter::text(self, pos, anchor, text, font, color);
+    }
+
+    fn polyline(&mut self, points: &[Pos2], stroke: Stroke) {
+        self.add(egui::Shape::line(points.to_vec(), stroke));
+    }
+
+    fn circle(&mut self, center: Pos2, radius: f32, stroke: Stroke) {
+        self.circle_stroke(center, radius, stroke);
+    }
+}
+
+/// Zone d'un tracé et pixels par unité normalisée : ±1 occupe 90 % de la zone
+#[derive(Debug, Clone, Copy)]
+pub struct PlotFrame {
+    pub rect: Rect,
+    pub scale: Vec2,
+}
+
+impl PlotFrame {
+    pub fn new(rect: Rect) -> Self {
+        Self {
+            rect,
+            scale: rect.size() * 0.45,
+        }
+    }
+
+    pub fn center(&self) -> Pos2 {
+        self.rect.center()
+    }
+
+    /// Position à l'écran d'une position du tracé (x, y dans [-1, 1])
+    pub fn screen_pos(&self, [x, y]: [f32; 2]) -> Pos2 {
+        self.center() + egui::vec2(x * self.scale.x, -y * self.scale.y)
+    }
+
+    /// Position du tracé d'un point à l'écran
+    pub fn plot_pos(&self, pos: Pos2) -> [f32; 2] {
+        let center = self.center();
+        [(pos.x - center.x) / self.scale.x, -(pos.y - center.y) / self.scale.y]
+    }
+
+    /// Position à l'écran d'un point (V, I) normalisé, selon les axes
+    fn project(&self, axes: &PlotAxes, voltage: f32, current: f32) -> Pos2 {
+        let (x, y) = axes.project(voltage, current);
+        self.screen_pos([x, y])
+    }
+}
+
+/// Couleurs, épaisseurs et tailles de texte de la grille et des axes
+#[derive(Debug, Clone, Copy)]
+pub struct PlotStyle {
+    pub grid: Color32,
+    pub minor_grid: Color32,
+    pub axis: Color32,
+    pub grid_width: f32,
+    pub axis_width: f32,
+    /// Taille du texte des graduations
+    pub tick_size: f32,
+    /// Taille du texte des noms des axes et des canaux
+    pub name_size: f32,
+}
+
+/// Grille (principale et secondaire), axes et graduations
+pub fn draw_grid(
+    target: &mut dyn PlotTarget,
+    settings: &Settings,
+    frame: PlotFrame,
+    style: &PlotStyle,
+) {
+    let rect = frame.rect;
+    let center = frame.center();
+    let axes = &settings.axes;
+    let grid_stroke = |major: bool| {
+        Stroke::new(style.grid_width, if major { style.grid } else { style.minor_grid })
+    };
+
+    for (pos, major) in axes.x_lines(&settings.grid) {
+        let x = frame.screen_pos([pos, 0.0]).x;
+        if rect.x_range().contains(x) {
+            let (top, bottom) = (egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom()));
+            target.line(top, bottom, grid_stroke(major));
+        }
+    }
+    for (pos, major) in axes.y_lines(&settings.grid) {
+        let y = frame.screen_pos([0.0, pos]).y;
+        if rect.y_range().contains(y) {
+            let (left, right) = (egui::pos2(rect.left(), y), egui::pos2(rect.right(), y));
+            target.line(left, right, grid_stroke(major));
+        }
+    }
+
+    let axis = Stroke::new(style.axis_width, style.axis);
+    target.line(
+        egui::pos2(rect.left(), center.y),
+        egui::pos2(rect.right(), center.y),
+        axis,
+    );
+    target.line(
+        egui::pos2(center.x, rect.top()),
+        egui::pos2(center.x, rect.bottom()),
+        axis,
+    );
+
+    let (x_ticks, y_ticks) = axes.tick_labels(&settings.calibration, &settings.grid);
+    for (pos, label) in x_ticks {
+        let at = egui::pos2(frame.screen_pos([pos, 0.0]).x, center.y + 4.0);
+        target.text(at, Align2::CENTER_TOP, &label, style.tick_size, style.axis);
+    }
+    for (pos, label) in y_ticks {
+        let at = egui::pos2(center.x + 4.0, frame.screen_pos([0.0, pos]).y);
+        target.text(at, Align2::LEFT_CENTER, &label, style.tick_size, style.axis);
+    }
+}
+
+/// Noms des axes, au bout de l'abscisse et en haut de l'ordonnée
+pub fn draw_axis_names(
+    target: &mut dyn PlotTarget,
+    axes: &PlotAxes,
+    frame: PlotFrame,
+    style: &PlotStyle,
+) {
+    let (x_key, y_key) = axes.name_keys();
+    let (rect, center) = (frame.rect, frame.center());
+    let x_pos = egui::pos2(rect.right() - 10.0, center.y - 6.0);
+    target.text(x_pos, Align2::RIGHT_BOTTOM, &tr(x_key), style.name_size, style.axis);
+    let y_pos = egui::pos2(center.x + 8.0, rect.top() + 10.0);
+    target.text(y_pos, Align2::LEFT_TOP, &tr(y_key), style.name_size, style.axis);
+}
+
+/// Nom d'un canal en haut à droite du tracé, `row` lignes sous le bord
+pub fn draw_channel_name(
+    target: &mut dyn PlotTarget,
+    channel: u8,
+    frame: PlotFrame,
+    row: usize,
+    size: f32,
+    color: Color32,
+) {
+    let pos = frame.rect.right_top() + egui::vec2(-10.0, 10.0 + row as f32 * size * 1.3);
+    target.text(pos, Align2::RIGHT_TOP, &format!("CH{}", channel), size, color);
+}
+
+/// Courbe d'un canal dans le style des préférences : trait (en tirets pour
+/// CH1 si demandé) et marqueurs des points
+pub fn draw_trace(
+    target: &mut dyn PlotTarget,
+    curve: &CurveData,
+    settings: &Settings,
+    frame: PlotFrame,
+    stroke: Stroke,
+) {
+    let style = &settings.plot;
+    // En haut contraste les deux canaux ont la même couleur : CH1 en tirets
+    let dashed = curve.channel == 1 && (style.dashed_ch1 || style.high_contrast);
+    if style.connect_points || style.markers == Markers::None {
+        draw_curve(target, curve, &settings.axes, frame, stroke, dashed);
+    }
+    draw_markers(target, curve, &settings.axes, frame, stroke, style.markers);
+}
+
+/// Tracer une courbe (V, I) normalisée, en trait plein ou en pointillés
+pub fn draw_curve(
+    target: &mut dyn PlotTarget,
+    curve: &CurveData,
+    axes: &PlotAxes,
+    frame: PlotFrame,
+    stroke: Stroke,
+    dashed: bool,
+) {
+    let points = screen_points(curve, axes, frame);
+    if points.len() < 2 {
+        return;
+    }
+    if !dashed {
+        target.polyline(&points, stroke);
+        return;
+    }
+
+    // Tirets mesurés le long de la courbe, d'un segment à l'autre
+    let mut drawing = true;
+    let mut left = DASH;
+    for pair in points.windows(2) {
+        let (mut from, to) = (pair[0], pair[1]);
+        let mut length = from.distance(to);
+        while length > 0.0 {
+            let step = left.min(length);
+            let next = from + (to - from) * (step / length);
+            if drawing {
+                target.line(from, next, stroke);
+            }
+            left -= step;
+            length -= step;
+            from = next;
+            if left <= 0.0 {
+                drawing = !drawing;
+                left = if drawing { DASH } else { GAP };
+            }
+        }
+    }
+}
+
+/// Marquer chaque point d'une courbe (cercle ou croix)
+fn draw_markers(
+    target: &mut dyn PlotTarget,
+    curve: &CurveData,
+    axes: &PlotAxes,
+    frame: PlotFrame,
+    stroke: Stroke,
+    markers: Markers,
+) {
+    if markers == Markers::None {
+        return;
+    }
+    let size = stroke.width + 1.5;
+    let thin = Stroke::new(1.0, stroke.color);
+    for p in screen_points(curve, axes, frame) {
+        if markers == Markers::Circles {
+            target.circle(p, size, thin);
+        } else {
+            let (a, b) = (egui::vec2(size, size), egui::vec2(size, -size));
+            target.line(p - a, p + a, thin);
+            target.line(p - b, p + b, thin);
+        }
+    }
+}
+
+/// Positions à l'écran des points d'une courbe
+fn screen_points(curve: &CurveData, axes: &PlotAxes, frame: PlotFrame) -> Vec<Pos2> {
+    curve
+        .voltage
+        .iter()
+        .zip(&curve.current)
+        .map(|(&v, &i)| frame.project(axes, v, i))
+        .collect()
+}
+
+/// Annotations : texte de hauteur `size` et flèche vers le point désigné
+pub fn draw_annotations(
+    target: &mut dyn PlotTarget,
+    annotations: &[Annotation],
+    frame: PlotFrame,
+    stroke: Stroke,
+    size: f32,
+) {
+    for annotation in annotations {
+        let pos = frame.screen_pos(annotation.position);
+        if let Some(point) = annotation.target {
+            draw_arrow(target, pos, frame.screen_pos(point), stroke);
+        }
+        let at = pos + egui::vec2(4.0, 0.0);
+        target.text(at, Align2::LEFT_BOTTOM, &annotation.text, size, stroke.color);
+    }
+}
+
+/// Flèche de `from` vers `to` (trait et pointe)
+fn draw_arrow(target: &mut dyn PlotTarget, from: Pos2, to: Pos2, stroke: Stroke) {
+    target.line(from, to, stroke);
+
+    let direction = to - from;
+    if direction.length() < 1.0 {
+        return;
+    }
+    let back = -direction.normalized() * ARROW_HEAD;
+    // Branches de la pointe à ±30°
+    for angle in [-30f32, 30.0] {
+        let rotated = egui::emath::Rot2::from_angle(angle.to_radians()) * back;
+        target.line(to, to + rotated, stroke);
+    }
+}
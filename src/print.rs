@@ -0,0 +1,39 @@
+// src/print.rs
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use tracing::warn;
+
+/// Fichier temporaire propre à chaque impression (`ct220s_print_XXXXXX.pdf`),
+/// conservé pour l'application qui l'imprime
+pub fn print_file() -> Result<PathBuf, String> {
+    let file = tempfile::Builder::new()
+        .prefix("ct220s_print_")
+        .suffix(".pdf")
+        .tempfile()
+        .map_err(|e| format!("Impossible de créer le fichier d'impression: {}", e))?;
+    let (_, path) = file
+        .keep()
+        .map_err(|e| format!("Impossible de créer le fichier d'impression: {}", e))?;
+    Ok(path)
+}
+
+/// Ouvrir un document dans l'application par défaut (`xdg-open`), d'où il
+/// est imprimé avec la boîte d'impression du système
+pub fn open_document(path: &Path) -> Result<(), String> {
+    let mut child = Command::new("xdg-open")
+        .arg(path)
+        .spawn()
+        .map_err(|e| format!("Impossible de lancer xdg-open: {}", e))?;
+    // Selon le bureau, xdg-open attend la fermeture du lecteur
+    let path = path.to_path_buf();
+    thread::spawn(move || match child.wait() {
+        Ok(status) if !status.success() => {
+            warn!("xdg-open {} : {}", path.display(), status);
+        }
+        Err(e) => warn!("xdg-open {} : {}", path.display(), e),
+        Ok(_) => {}
+    });
+    Ok(())
+}
@@ -0,0 +1,290 @@
+// src/report.rs
+
+use crate::analysis::{estimate_capacitance, format_capacitance, Analysis, Component};
+use crate::app::DISPLAY_MODES;
+use crate::backend::frequency_hz;
+use crate::curve::{CurveData, DualCurveData};
+use crate::i18n::{tr, tr_args};
+use crate::image_export::{save_page, ImageFormat};
+use crate::json_export;
+use crate::library::{match_score, LibraryEntry};
+use crate::timestamp::format_timestamp;
+use crate::session::Session;
+use crate::settings::Settings;
+use crate::template;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// En-tête des exports (page imprimée, CSV, rapport) : source, date, réglages,
+/// mesures et notes
+pub fn header(
+    session: &Session,
+    data: &DualCurveData,
+    channels: &[u8],
+    settings: &Settings,
+) -> Vec<String> {
+    let fields = fields(session, data, channels, settings, None);
+    let mut header = vec![
+        format!("{} — {}", fields["app"], fields["source"]),
+        tr_args("print-date", &[("date", fields["date"].clone().into())]),
+        format!("{} {}", tr("display-mode"), fields["mode"]),
+        tr_args("print-scale", &[("scale", fields["scale"].clone().into())]),
+    ];
+    if !fields["device"].is_empty() {
+        header.push(tr_args("print-device", &[("settings", fields["device"].clone().into())]));
+    }
+    header.push(fields["summary"].clone());
+    let notes = session.notes.lines().filter(|l| !l.trim().is_empty());
+    header.extend(notes.map(str::to_string));
+    header
+}
+
+/// Valeurs des champs des modèles de rapport (voir `template::FIELDS`) ; les
+/// champs `header` et `plot` dépendent du format et sont ajoutés par l'export
+pub fn fields(
+    session: &Session,
+    data: &DualCurveData,
+    channels: &[u8],
+    settings: &Settings,
+    signature: Option<&LibraryEntry>,
+) -> BTreeMap<&'static str, String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mode = DISPLAY_MODES
+        .iter()
+        .find(|(mode, _)| *mode == settings.display_mode)
+        .map(|(_, key)| tr(key))
+        .unwrap_or_default();
+    let calibration = &settings.calibration;
+    let scale = if calibration.enabled {
+        format!(
+            "{} V / {} mA",
+            calibration.volts_full_scale, calibration.milliamps_full_scale
+        )
+    } else {
+        tr("print-normalized")
+    };
+    let device: Vec<String> = session
+        .applied_device
+        .iter()
+        .map(|(id, label)| format!("{} = {}", id, label))
+        .collect();
+    let curves: Vec<(String, &CurveData)> = channels
+        .iter()
+        .filter_map(|&c| data.channel(c).map(|curve| (format!("CH{}", c), curve)))
+        .collect();
+    let result = signature.and_then(|entry| {
+        verdict(data, channels, &entry.curve, settings.analysis.pass_threshold)
+    });
+
+    BTreeMap::from([
+        ("app", tr("app-title")),
+        ("source", session.title()),
+        ("date", format_timestamp(now)),
+        ("mode", mode),
+        ("scale", scale),
+        ("device", device.join(", ")),
+        ("summary", plot_summary(&curves, settings)),
+        (
+            "verdict",
+            result
+                .map(|(pass, _)| tr(if pass { "operator-pass" } else { "operator-fail" }))
+                .unwrap_or_default(),
+        ),
+        (
+            "score",
+            result
+                .map(|(_, score)| format!("{:.0}", score))
+                .unwrap_or_default(),
+        ),
+        (
+            "signature",
+            signature.map(|entry| entry.name.clone()).unwrap_or_default(),
+        ),
+        ("notes", session.notes.clone()),
+    ])
+}
+
+/// Classification et mesures principales de chaque courbe nommée
+pub fn plot_summary(curves: &[(String, &CurveData)], settings: &Settings) -> String {
+    if curves.is_empty() {
+        return tr("plot-summary-none");
+    }
+    curves
+        .iter()
+        .map(|(name, curve)| {
+            let analysis = Analysis::of(curve);
+            let vf = analysis
+                .format_forward_voltage(&settings.calibration)
+                .unwrap_or_else(|| "—".to_string());
+            tr_args(
+                "plot-summary",
+                &[
+                    ("name", name.clone().into()),
+                    ("component", tr(analysis.component.key()).into()),
+                    ("resistance", analysis.format_resistance(&settings.calibration).into()),
+                    ("vf", vf.into()),
+                ],
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ; ")
+}
+
+/// Verdict PASS/FAIL : plus mauvais score des canaux contre la signature ;
+/// `None` sans courbe
+pub fn verdict(
+    data: &DualCurveData,
+    channels: &[u8],
+    signature: &CurveData,
+    pass_threshold: f32,
+) -> Option<(bool, f32)> {
+    let worst = channels
+        .iter()
+        .filter_map(|&channel| data.channel(channel))
+        .map(|curve| match_score(curve, signature))
+        .min_by(f32::total_cmp)?;
+    Some((worst >= pass_threshold, worst))
+}
+
+/// Rapport PDF d'une page : en-tête (ou modèle configuré, une ligne de texte
+/// par ligne du modèle), verdict contre la signature éventuelle puis tracés
+pub fn save_report(
+    session: &Session,
+    data: &DualCurveData,
+    channels: &[u8],
+    settings: &Settings,
+    signature: Option<&LibraryEntry>,
+    path: &Path,
+) -> Result<(), String> {
+    let mut lines = header(session, data, channels, settings);
+    let fields = fields(session, data, channels, settings, signature);
+    let result = if signature.is_none() {
+        tr("report-no-signature")
+    } else if fields["verdict"].is_empty() {
+        tr("operator-waiting")
+    } else {
+        tr_args(
+            "report-verdict",
+            &[
+                ("result", fields["verdict"].clone().into()),
+                ("score", fields["score"].clone().into()),
+                ("signature", fields["signature"].clone().into()),
+            ],
+        )
+    };
+    // Le verdict suit les réglages, avant les mesures et les notes
+    let position = lines.len().min(4);
+    lines.insert(position, result);
+
+    if let Some(custom) = template::load(settings.pdf_template.as_deref())? {
+        let mut fields = fields;
+        fields.insert("header", lines.join("\n"));
+        lines = template::render(&custom, &fields)?
+            .lines()
+            .map(str::to_string)
+            .collect();
+    }
+
+    save_page(
+        data,
+        channels,
+        settings,
+        &session.annotations,
+        &lines,
+        ImageFormat::Pdf,
+        path,
+    )
+}
+
+/// Légende des images exportées : date et réglages, puis mesures de chaque
+/// canal (R, Vf, capacité estimée, correspondance avec `match_target`)
+pub fn image_caption<'a>(
+    session: &Session,
+    data: &DualCurveData,
+    channels: &[u8],
+    settings: &Settings,
+    match_target: impl Fn(u8) -> Option<&'a CurveData>,
+) -> Vec<String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut caption = vec![format_timestamp(now)];
+    let device: Vec<String> = session
+        .applied_device
+        .iter()
+        .map(|(id, label)| format!("{} = {}", id, label))
+        .collect();
+    if !device.is_empty() {
+        caption.push(device.join(", "));
+    }
+
+    let calibration = &settings.calibration;
+    let frequency = session
+        .applied_device
+        .get("frequency")
+        .or_else(|| settings.device.get("frequency"))
+        .and_then(|label| frequency_hz(label));
+    for &channel in channels {
+        let Some(curve) = data.channel(channel) else {
+            continue;
+        };
+        let analysis = Analysis::of(curve);
+        let mut parts = vec![
+            format!("CH{} : {}", channel, tr(analysis.component.key())),
+            format!("{} {}", tr("measure-resistance"), analysis.format_resistance(calibration)),
+        ];
+        if let Some(vf) = analysis.format_forward_voltage(calibration) {
+            parts.push(format!("{} {}", tr("measure-forward-voltage"), vf));
+        }
+        if analysis.component == Component::Capacitor {
+            if let Some(farads) =
+                frequency.and_then(|f| estimate_capacitance(curve, calibration, f))
+            {
+                parts.push(format!("C {}", format_capacitance(farads)));
+            }
+        }
+        if let Some(target) = match_target(channel) {
+            let score = match_score(curve, target);
+            parts.push(format!("{} {:.0} %", tr("measure-match"), score));
+        }
+        caption.push(parts.join(" · "));
+    }
+    caption
+}
+
+/// Textes intégrés aux PNG exportés (mot-clé, valeur) : mots-clés standard du
+/// format, affichés par les visionneuses, puis métadonnées JSON complètes
+pub fn png_metadata<'a>(
+    session: &Session,
+    data: &DualCurveData,
+    channels: &[u8],
+    settings: &Settings,
+    match_target: impl Fn(u8) -> Option<&'a CurveData>,
+) -> Vec<(&'static str, String)> {
+    let fields = fields(session, data, channels, settings, None);
+    let scores: Vec<(u8, f32)> = channels
+        .iter()
+        .filter_map(|&c| Some((c, match_score(data.channel(c)?, match_target(c)?))))
+        .collect();
+
+    let mut entries = vec![
+        ("Title", format!("{} — {}", fields["app"], fields["source"])),
+        ("Software", format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))),
+        ("Creation Time", fields["date"].clone()),
+        ("Description", fields["summary"].clone()),
+    ];
+    if !fields["device"].is_empty() {
+        entries.push(("Source", format!("CT220S : {}", fields["device"])));
+    }
+    if !session.notes.trim().is_empty() {
+        entries.push(("Comment", session.notes.clone()));
+    }
+    let json = json_export::metadata(data, channels, settings, session, &scores);
+    entries.push(("CT220S JSON", json));
+    entries
+}
@@ -0,0 +1,271 @@
+// src/results_db.rs
+
+use crate::analysis::Analysis;
+use crate::curve::CurveData;
+use crate::settings::Settings;
+use rusqlite::{params, Connection};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Nombre de lignes affichées par le navigateur de résultats
+pub const BROWSE_LIMIT: usize = 500;
+
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS results (
+    id INTEGER PRIMARY KEY,
+    time INTEGER NOT NULL,
+    serial TEXT NOT NULL,
+    test_point TEXT NOT NULL,
+    channel INTEGER NOT NULL,
+    settings TEXT NOT NULL,
+    score REAL NOT NULL,
+    component TEXT NOT NULL,
+    resistance TEXT NOT NULL,
+    pass INTEGER NOT NULL,
+    curve BLOB
+);
+CREATE INDEX IF NOT EXISTS results_serial ON results (serial);
+";
+
+/// Test terminé, enregistré dans la base de résultats
+pub struct TestRecord {
+    /// Secondes depuis l'epoch
+    pub time: u64,
+    /// Numéro de série de la carte testée
+    pub serial: String,
+    /// Point de test (entrée de bibliothèque)
+    pub test_point: String,
+    pub channel: u8,
+    /// Réglages du boîtier et seuil, en texte
+    pub settings: String,
+    pub score: f32,
+    pub pass: bool,
+    pub curve: CurveData,
+}
+
+impl TestRecord {
+    /// Réglages du boîtier et seuil PASS/FAIL, en texte
+    pub fn settings_text(device: &BTreeMap<String, String>, settings: &Settings) -> String {
+        let mut text: Vec<String> =
+            device.iter().map(|(id, value)| format!("{}={}", id, value)).collect();
+        text.push(format!("threshold={}", settings.analysis.pass_threshold));
+        text.join(", ")
+    }
+}
+
+/// Ligne du navigateur de résultats
+pub struct ResultRow {
+    pub time: u64,
+    pub serial: String,
+    pub test_point: String,
+    pub channel: u8,
+    pub score: f32,
+    pub pass: bool,
+}
+
+/// Base SQLite des résultats de test (traçabilité en production)
+/// (~/.local/share/ct220s_viewer/results.sqlite)
+pub struct ResultsDb {
+    connection: Connection,
+}
+
+impl ResultsDb {
+    pub fn open() -> Result<Self, String> {
+        let dir = dirs::data_dir()
+            .ok_or("Répertoire de données introuvable")?
+            .join("ct220s_viewer");
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Impossible de créer {}: {}", dir.display(), e))?;
+        Self::open_path(&dir.join("results.sqlite"))
+    }
+
+    fn open_path(path: &Path) -> Result<Self, String> {
+        let connection = Connection::open(path)
+            .map_err(|e| format!("Impossible d'ouvrir {}: {}", path.display(), e))?;
+        connection.execute_batch(SCHEMA).map_err(sql_error)?;
+        Ok(Self { connection })
+    }
+
+    /// Enregistrer des tests terminés, en une transaction : réglages,
+    /// mesures, verdict et points de la courbe. Un score non défini (NaN) est
+    /// enregistré à 0
+    pub fn record(&mut self, records: &[TestRecord], settings: &Settings) -> Result<(), String> {
+        let transaction = self.connection.transaction().map_err(sql_error)?;
+        {
+            let mut insert = transaction
+                .prepare(
+                    "INSERT INTO results (time, serial, test_point, channel, settings, score, \
+                     component, resistance, pass, curve) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                )
+                .map_err(sql_error)?;
+            for record in records {
+                let analysis = Analysis::of(&record.curve);
+                let component = format!("{:?}", analysis.component).to_lowercase();
+                let resistance = analysis.format_resistance(&settings.calibration);
+                let score = if record.score.is_finite() { record.score } else { 0.0 };
+                insert
+                    .execute(params![
+                        record.time as i64,
+                        record.serial,
+                        record.test_point,
+                        record.channel,
+                        record.settings,
+                        score as f64,
+                        component,
+                        resistance,
+                        record.pass,
+                        curve_blob(&record.curve),
+                    ])
+                    .map_err(sql_error)?;
+            }
+        }
+        transaction.commit().map_err(sql_error)
+    }
+
+    /// Derniers résultats dont le numéro de série ou le point de test
+    /// contient `filter`, du plus récent au plus ancien
+    pub fn query(&self, filter: &str) -> Result<Vec<ResultRow>, String> {
+        let pattern = format!("%{}%", escape_like(filter));
+        let mut select = self
+            .connection
+            .prepare(
+                "SELECT time, serial, test_point, channel, score, pass FROM results \
+                 WHERE serial LIKE ?1 ESCAPE '\\' OR test_point LIKE ?1 ESCAPE '\\' \
+                 ORDER BY time DESC, id DESC LIMIT ?2",
+            )
+            .map_err(sql_error)?;
+        let rows = select
+            .query_map(params![pattern, BROWSE_LIMIT as i64], |row| {
+                Ok(ResultRow {
+                    time: row.get::<_, i64>(0)? as u64,
+                    serial: row.get(1)?,
+                    test_point: row.get(2)?,
+                    channel: row.get(3)?,
+                    score: row.get::<_, f64>(4)? as f32,
+                    pass: row.get(5)?,
+                })
+            })
+            .map_err(sql_error)?;
+        rows.collect::<Result<_, _>>().map_err(sql_error)
+    }
+}
+
+/// Navigateur des résultats : filtre et dernières lignes lues
+#[derive(Default)]
+pub struct ResultsBrowser {
+    pub open: bool,
+    /// Numéro de série ou point de test recherché
+    pub filter: String,
+    pub rows: Vec<ResultRow>,
+    pub error: Option<String>,
+}
+
+impl ResultsBrowser {
+    /// Relire les résultats correspondant au filtre
+    pub fn refresh(&mut self) {
+        match ResultsDb::open().and_then(|db| db.query(&self.filter)) {
+            Ok(rows) => {
+                self.rows = rows;
+                self.error = None;
+            }
+            Err(e) => {
+                self.rows.clear();
+                self.error = Some(e);
+            }
+        }
+    }
+}
+
+fn sql_error(e: rusqlite::Error) -> String {
+    format!("Erreur de la base de résultats: {}", e)
+}
+
+/// Motif `LIKE` recherchant `text` tel quel : `%`, `_` et `\` échappés
+fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Points de la courbe : tensions puis courants (float32 petit-boutiste)
+fn curve_blob(curve: &CurveData) -> Vec<u8> {
+    curve
+        .voltage
+        .iter()
+        .chain(&curve.current)
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(serial: &str, test_point: &str, score: f32) -> TestRecord {
+        TestRecord {
+            time: 1_700_000_000,
+            serial: serial.to_string(),
+            test_point: test_point.to_string(),
+            channel: 1,
+            settings: "frequency=500Hz, threshold=80".to_string(),
+            score,
+            pass: score >= 80.0,
+            curve: CurveData {
+                voltage: vec![-1.0, 0.0, 1.0],
+                current: vec![0.0, 0.0, 0.5],
+                channel: 1,
+                raw_voltage: Vec::new(),
+                raw_current: Vec::new(),
+            },
+        }
+    }
+
+    fn database(records: &[TestRecord]) -> ResultsDb {
+        let mut db = ResultsDb::open_path(Path::new(":memory:")).unwrap();
+        db.record(records, &Settings::default()).unwrap();
+        db
+    }
+
+    #[test]
+    fn records_are_read_back() {
+        let db = database(&[record("SN-1", "D3", 92.5), record("SN-2", "R12", 40.0)]);
+        let rows = db.query("SN-1").unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].test_point, "D3");
+        assert_eq!(rows[0].channel, 1);
+        assert_eq!(rows[0].score, 92.5);
+        assert!(rows[0].pass);
+        assert_eq!(db.query("").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn quotes_are_stored_as_text() {
+        let db = database(&[record("O'Brien'); DROP TABLE results; --", "D3", 90.0)]);
+        let rows = db.query("O'Brien").unwrap();
+        assert_eq!(rows[0].serial, "O'Brien'); DROP TABLE results; --");
+    }
+
+    #[test]
+    fn undefined_score_is_recorded_as_zero() {
+        let db = database(&[
+            record("SN-1", "D3", f32::NAN),
+            record("SN-2", "D3", f32::NEG_INFINITY),
+        ]);
+        let rows = db.query("D3").unwrap();
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|row| row.score == 0.0 && !row.pass));
+    }
+
+    #[test]
+    fn like_wildcards_are_literal() {
+        let db = database(&[record("SN_1", "D3", 90.0), record("SNX1", "50%", 90.0)]);
+        assert_eq!(db.query("SN_").unwrap().len(), 1);
+        assert_eq!(db.query("%").unwrap().len(), 1);
+        assert_eq!(db.query("0%").unwrap()[0].test_point, "50%");
+    }
+}
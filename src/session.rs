@@ -0,0 +1,514 @@
+// src/session.rs
+
+use crate::annotation::Annotation;
+use crate::backend::{run_file_reader, run_hid_reader, DeviceSetting, HidBackend, Playback};
+use crate::compression::{compress, decompress, Compression};
+use crate::curve::{CurveData, CurveHistory, DualCurveData};
+use crate::i18n::{tr, tr_args};
+use crate::notifications::Level;
+use crate::settings::Settings;
+use crate::status::{AcquisitionStatus, Connection};
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{error, info, warn};
+
+/// Intervalle de vérification du fichier de capture (rechargement automatique)
+pub const RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Version du format des fichiers de session ; à incrémenter à chaque
+/// changement de structure, avec l'étape correspondante dans `MIGRATIONS`
+pub const SESSION_VERSION: u32 = 1;
+
+/// Étapes de mise à niveau : `MIGRATIONS[n - 1]` passe un fichier de la
+/// version `n` à la version `n + 1`. Les fichiers enregistrés avant l'ajout du
+/// numéro de version ont la structure de la version 1 et sont lus comme tels
+const MIGRATIONS: [fn(&mut toml::Table); SESSION_VERSION as usize - 1] = [];
+
+/// Session enregistrée (TOML) : courbes des deux canaux, référence, réglages
+/// du boîtier, annotations et notes, pour reprendre ou partager un dépannage
+#[derive(Serialize, Deserialize)]
+pub struct SessionFile {
+    /// Version du format, voir `SESSION_VERSION`
+    pub version: u32,
+    /// Date d'enregistrement (secondes depuis l'epoch Unix)
+    #[serde(default)]
+    pub saved: u64,
+    /// Source d'origine (titre de l'onglet)
+    #[serde(default)]
+    pub source: String,
+    #[serde(default)]
+    pub notes: String,
+    /// Réglages du boîtier au moment de l'enregistrement : identifiant -> libellé
+    #[serde(default)]
+    pub device: BTreeMap<String, String>,
+    pub curves: DualCurveData,
+    #[serde(default)]
+    pub reference: Option<DualCurveData>,
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+}
+
+impl SessionFile {
+    /// Charger une session, compressée ou non ; les formats antérieurs sont
+    /// mis à niveau, ceux d'une version plus récente refusés
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let bytes =
+            fs::read(path).map_err(|e| format!("Impossible de lire {}: {}", path.display(), e))?;
+        let content = String::from_utf8(decompress(bytes)?)
+            .map_err(|e| format!("Session invalide: {}", e))?;
+        Self::parse(&content)
+    }
+
+    /// Lire le contenu TOML d'une session et le mettre à niveau
+    fn parse(content: &str) -> Result<Self, String> {
+        let mut table: toml::Table =
+            toml::from_str(content).map_err(|e| format!("Session invalide: {}", e))?;
+
+        let version = match table.get("version") {
+            None => 1,
+            Some(value) => value
+                .as_integer()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(|| format!("Session invalide: version {}", value))?
+                .max(1),
+        };
+        if version > SESSION_VERSION {
+            return Err(format!(
+                "Session au format {}, plus récent que celui de cette version ({}) : \
+                 mettre à jour le logiciel pour l'ouvrir",
+                version, SESSION_VERSION
+            ));
+        }
+        for (step, migrate) in MIGRATIONS.iter().enumerate().skip(version as usize - 1) {
+            info!("Mise à niveau de la session : format {} -> {}", step + 1, step + 2);
+            migrate(&mut table);
+        }
+        table.insert("version".to_string(), toml::Value::Integer(SESSION_VERSION as i64));
+
+        toml::Value::Table(table)
+            .try_into()
+            .map_err(|e| format!("Session invalide: {}", e))
+    }
+
+    /// Enregistrer la session, compressée selon l'extension du fichier
+    /// (`.gz`, `.zst`)
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content =
+            toml::to_string(self).map_err(|e| format!("Erreur sérialisation: {}", e))?;
+        let content = compress(content.into_bytes(), Compression::from_path(path))?;
+        fs::write(path, content)
+            .map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))
+    }
+}
+
+/// Un onglet : une source de données (boîtier USB, fichier ou instantané figé)
+/// avec son propre thread de lecture, son historique et sa référence
+pub struct Session {
+    /// Nom de l'instantané ; `None` pour une source live
+    pub snapshot_name: Option<String>,
+    pub curve_data: Arc<Mutex<DualCurveData>>,
+    /// État de l'acquisition en cours (connexion, débit, dernière erreur)
+    pub status: Arc<Mutex<AcquisitionStatus>>,
+    pub running: Arc<Mutex<bool>>,
+    pub use_file_mode: bool,
+    pub file_path: String,
+    pub hid_backend: Option<Arc<Mutex<HidBackend>>>,
+    pub paused: bool,
+    pub frozen_data: Option<DualCurveData>,
+    /// Courbes figées comme référence, tracées derrière les courbes live
+    pub reference: Option<DualCurveData>,
+    /// Dernières courbes reçues, parcourues sous forme de vignettes
+    pub history: Arc<Mutex<CurveHistory>>,
+    pub discard: Arc<Mutex<bool>>,
+    /// Contrôles de relecture du mode fichier
+    pub playback: Arc<Mutex<Playback>>,
+    /// Réglages acceptés par le boîtier depuis la connexion : identifiant -> libellé
+    pub applied_device: BTreeMap<String, String>,
+    /// Textes et flèches placés sur le tracé
+    pub annotations: Vec<Annotation>,
+    /// Notes libres (numéro de série, panne constatée), reprises dans les exports
+    pub notes: String,
+    /// Date de modification du fichier rejoué, pour le rechargement automatique
+    file_modified: Option<SystemTime>,
+    last_reload_check: Instant,
+}
+
+impl Session {
+    /// Session sans lecteur ; appeler ensuite `start_usb_reader` ou `start_file_reader`
+    pub fn new(file_path: String, settings: &Settings) -> Self {
+        Self {
+            snapshot_name: None,
+            curve_data: Arc::new(Mutex::new(DualCurveData::new())),
+            status: Arc::new(Mutex::new(AcquisitionStatus::default())),
+            running: Arc::new(Mutex::new(false)),
+            use_file_mode: false,
+            file_path,
+            hid_backend: None,
+            paused: false,
+            frozen_data: None,
+            reference: None,
+            history: Arc::new(Mutex::new(CurveHistory::new(settings.history_size))),
+            discard: Arc::new(Mutex::new(false)),
+            playback: Arc::new(Mutex::new(Playback::default())),
+            applied_device: BTreeMap::new(),
+            annotations: Vec::new(),
+            notes: String::new(),
+            file_modified: None,
+            last_reload_check: Instant::now(),
+        }
+    }
+
+    /// Instantané figé des courbes d'une autre session, sans source de données
+    pub fn snapshot(name: String, data: DualCurveData, settings: &Settings) -> Self {
+        let mut session = Self::new(String::new(), settings);
+        session.snapshot_name = Some(name);
+        session.paused = true;
+        session.frozen_data = Some(data);
+        session
+    }
+
+    /// Session rouverte depuis un fichier : instantané figé avec sa référence,
+    /// ses annotations et ses notes
+    pub fn from_file(name: String, file: SessionFile, settings: &Settings) -> Self {
+        let mut session = Self::snapshot(name, file.curves, settings);
+        session.reference = file.reference;
+        session.applied_device = file.device;
+        session.annotations = file.annotations;
+        session.notes = file.notes;
+        session
+    }
+
+    /// Contenu à enregistrer : courbes affichées et contexte de l'onglet
+    pub fn to_file(&self) -> SessionFile {
+        SessionFile {
+            version: SESSION_VERSION,
+            saved: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            source: self.title(),
+            notes: self.notes.clone(),
+            device: self.applied_device.clone(),
+            curves: self.displayed_data(),
+            reference: self.reference.clone(),
+            annotations: self.annotations.clone(),
+        }
+    }
+
+    /// Titre de l'onglet
+    pub fn title(&self) -> String {
+        if let Some(name) = &self.snapshot_name {
+            format!("📷 {}", name)
+        } else if self.use_file_mode {
+            let name = Path::new(&self.file_path)
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| self.file_path.clone());
+            format!("📁 {}", name)
+        } else {
+            "🔌 USB".to_string()
+        }
+    }
+
+    /// Arrêter le thread de lecture en cours et préparer le drapeau du suivant
+    pub fn stop_reader(&mut self) {
+        // Un instantané qui reçoit une source redevient une session live
+        if self.snapshot_name.take().is_some() {
+            self.paused = false;
+            self.frozen_data = None;
+        }
+        *self.running.lock().unwrap() = false;
+        self.running = Arc::new(Mutex::new(true));
+        // Nouvel état par lecteur : l'ancien thread, qui peut encore publier
+        // une courbe avant de voir `running`, n'atteint plus celui du suivant
+        self.status = Arc::new(Mutex::new(AcquisitionStatus::default()));
+        self.curve_data = Arc::new(Mutex::new(DualCurveData::new()));
+        let capacity = self.history.lock().unwrap().capacity;
+        self.history = Arc::new(Mutex::new(CurveHistory::new(capacity)));
+    }
+
+    /// Mode USB : créer le backend HID et lancer le thread de lecture
+    pub fn start_usb_reader(&mut self, settings: &Settings) {
+        self.stop_reader();
+        self.use_file_mode = false;
+        self.applied_device.clear();
+
+        let backend = match HidBackend::new() {
+            Ok(backend) => backend,
+            Err(e) => {
+                error!("Impossible de créer le backend HID: {}", e);
+                self.status
+                    .lock()
+                    .unwrap()
+                    .set_error(tr_args("status-usb-error", &[("error", e.into())]));
+                self.hid_backend = None;
+                return;
+            }
+        };
+
+        {
+            let mut status = self.status.lock().unwrap();
+            status.connection = Connection::Usb;
+            status.notify(Level::Success, tr("status-usb-connected"));
+        }
+        if settings.reapply_device_settings {
+            self.applied_device = reapply_device_settings(&backend, settings);
+        }
+
+        let device = backend.clone_device();
+        let curve_data = Arc::clone(&self.curve_data);
+        let history = Arc::clone(&self.history);
+        let status = Arc::clone(&self.status);
+        let running = Arc::clone(&self.running);
+        let discard = Arc::clone(&self.discard);
+
+        thread::spawn(move || {
+            info!("Mode périphérique USB - lecture démarrée");
+            let reader_status = Arc::clone(&status);
+            supervise(&status, || {
+                run_hid_reader(device, curve_data, history, reader_status, running, discard)
+            });
+        });
+
+        self.hid_backend = Some(Arc::new(Mutex::new(backend)));
+    }
+
+    /// Mode fichier : rejouer une capture dans un thread de lecture
+    pub fn start_file_reader(&mut self, path: String, settings: &mut Settings) {
+        self.stop_reader();
+        self.use_file_mode = true;
+        self.hid_backend = None;
+        self.file_path = path.clone();
+        self.file_modified = modified_time(&path);
+        settings.add_recent_file(&path);
+
+        let curve_data = Arc::clone(&self.curve_data);
+        let history = Arc::clone(&self.history);
+        let status = Arc::clone(&self.status);
+        let running = Arc::clone(&self.running);
+        let discard = Arc::clone(&self.discard);
+        let playback = Arc::clone(&self.playback);
+
+        thread::spawn(move || {
+            info!("Mode fichier: lecture de {}", path);
+            let reader_status = Arc::clone(&status);
+            supervise(&status, || {
+                run_file_reader(
+                    &path,
+                    curve_data,
+                    history,
+                    reader_status,
+                    running,
+                    discard,
+                    playback,
+                )
+            });
+        });
+    }
+
+    /// Relancer la relecture si le fichier de capture a été modifié
+    pub fn check_file_changed(&mut self, settings: &mut Settings) {
+        if !self.use_file_mode
+            || !settings.auto_reload
+            || self.last_reload_check.elapsed() < RELOAD_CHECK_INTERVAL
+        {
+            return;
+        }
+        self.last_reload_check = Instant::now();
+
+        let modified = modified_time(&self.file_path);
+        if modified.is_some() && modified != self.file_modified {
+            info!("Fichier modifié, rechargement: {}", self.file_path);
+            self.start_file_reader(self.file_path.clone(), settings);
+        }
+    }
+
+    /// Données affichées : l'instantané figé en pause, sinon les données live
+    pub fn displayed_data(&self) -> DualCurveData {
+        match &self.frozen_data {
+            Some(frozen) => frozen.clone(),
+            None => self.curve_data.lock().unwrap().clone(),
+        }
+    }
+
+    /// Bascule pause / lecture
+    pub fn set_paused(&mut self, paused: bool, record_while_paused: bool) {
+        // Un instantané n'a pas de données live à reprendre
+        if self.snapshot_name.is_some() {
+            return;
+        }
+        self.paused = paused;
+        self.frozen_data = if paused {
+            Some(self.curve_data.lock().unwrap().clone())
+        } else {
+            None
+        };
+        self.update_discard(record_while_paused);
+    }
+
+    pub fn update_discard(&self, record_while_paused: bool) {
+        *self.discard.lock().unwrap() = self.paused && !record_while_paused;
+    }
+
+    /// Charger une courbe de l'historique dans le tracé principal (affichage figé)
+    pub fn inspect_curve(&mut self, curve: CurveData, record_while_paused: bool) {
+        if !self.paused {
+            self.set_paused(true, record_while_paused);
+        }
+        if let Some(frozen) = &mut self.frozen_data {
+            frozen.store(curve);
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        *self.running.lock().unwrap() = false;
+    }
+}
+
+/// Exécuter un thread de lecture ; une erreur ou une panique est consignée
+/// dans l'état d'acquisition pour être signalée dans l'interface
+fn supervise(
+    status: &Arc<Mutex<AcquisitionStatus>>,
+    reader: impl FnOnce() -> Result<(), String>,
+) {
+    let failure = match panic::catch_unwind(AssertUnwindSafe(reader)) {
+        Ok(Ok(())) => return,
+        Ok(Err(e)) => {
+            error!("Erreur du thread de lecture: {}", e);
+            e
+        }
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_default();
+            tr_args("reader-panic", &[("message", message.into())])
+        }
+    };
+    // Le verrou peut être empoisonné si la panique l'a interrompu
+    let mut status = status.lock().unwrap_or_else(|e| e.into_inner());
+    status.connection = Connection::Disconnected;
+    status.assembling = None;
+    status.set_error(tr_args("status-error", &[("error", failure.clone().into())]));
+    status.failure = Some(failure);
+}
+
+/// Date de dernière modification d'un fichier, si disponible
+fn modified_time(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Renvoyer au boîtier les derniers réglages enregistrés ; renvoie ceux acceptés
+fn reapply_device_settings(backend: &HidBackend, settings: &Settings) -> BTreeMap<String, String> {
+    let mut applied = BTreeMap::new();
+    for (id, label) in &settings.device {
+        let Some(cmd) = DeviceSetting::find(id).and_then(|setting| setting.command_for(label))
+        else {
+            warn!("Réglage enregistré inconnu: {} = {}", id, label);
+            continue;
+        };
+        match backend.send_cmd(cmd) {
+            Ok(()) => {
+                applied.insert(id.clone(), label.clone());
+            }
+            Err(e) => warn!("Impossible de réappliquer {} = {}: {}", id, label, e),
+        }
+    }
+    applied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Session enregistrée avant le numéro de version, telle qu'écrite alors
+    const V0_SESSION: &str = r#"
+saved = 1700000000
+source = "CT220S"
+notes = "carte 42, D3 suspecte"
+
+[device]
+frequency = "500Hz"
+
+[curves.channel0]
+voltage = [-1.0, 0.0, 1.0]
+current = [0.0, 0.0, 0.5]
+channel = 0
+
+[reference.channel1]
+voltage = [-1.0, 1.0]
+current = [0.0, 0.5]
+channel = 1
+
+[[annotations]]
+text = "coude"
+position = [0.2, 0.1]
+"#;
+
+    /// Session minimale d'avant le numéro de version : courbes seules
+    const V0_MINIMAL: &str = r#"
+[curves.channel1]
+voltage = [0.5]
+current = [0.25]
+channel = 1
+"#;
+
+    #[test]
+    fn v0_session_is_upgraded() {
+        let file = SessionFile::parse(V0_SESSION).unwrap();
+        assert_eq!(file.version, SESSION_VERSION);
+        assert_eq!(file.saved, 1_700_000_000);
+        assert_eq!(file.notes, "carte 42, D3 suspecte");
+        assert_eq!(file.device.get("frequency").map(String::as_str), Some("500Hz"));
+        assert_eq!(file.curves.channel0.as_ref().map(|c| c.voltage.len()), Some(3));
+        assert!(file.curves.channel1.is_none());
+        let reference = file.reference.unwrap();
+        assert_eq!(reference.channel1.map(|c| c.channel), Some(1));
+        assert_eq!(file.annotations.len(), 1);
+        assert!(file.annotations[0].target.is_none());
+    }
+
+    #[test]
+    fn v0_minimal_session_is_upgraded() {
+        let file = SessionFile::parse(V0_MINIMAL).unwrap();
+        assert_eq!(file.version, SESSION_VERSION);
+        assert_eq!(file.saved, 0);
+        assert!(file.reference.is_none());
+        assert!(file.annotations.is_empty());
+        assert_eq!(file.curves.channel1.map(|c| c.current), Some(vec![0.25]));
+    }
+
+    #[test]
+    fn current_session_round_trips() {
+        let mut file = SessionFile::parse(V0_SESSION).unwrap();
+        file.notes = "relu".to_string();
+        let content = toml::to_string(&file).unwrap();
+        assert!(content.contains(&format!("version = {}", SESSION_VERSION)));
+        let file = SessionFile::parse(&content).unwrap();
+        assert_eq!(file.notes, "relu");
+        assert_eq!(file.annotations.len(), 1);
+    }
+
+    #[test]
+    fn newer_session_is_rejected() {
+        let content = format!("version = {}\n{}", SESSION_VERSION + 1, V0_MINIMAL);
+        let error = SessionFile::parse(&content).err().unwrap();
+        assert!(error.contains("plus récent"), "{}", error);
+    }
+
+    #[test]
+    fn invalid_version_is_rejected() {
+        let content = format!("version = \"2\"\n{}", V0_MINIMAL);
+        assert!(SessionFile::parse(&content).is_err());
+    }
+}
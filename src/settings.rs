@@ -0,0 +1,685 @@
+// src/settings.rs
+
+use crate::analysis::AnalysisOptions;
+use crate::axes::PlotAxes;
+use crate::calibration::Calibration;
+use crate::compression::Compression;
+use crate::i18n::Language;
+use crate::layout::PanelLayout;
+use crate::library::MergePolicy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Préfixe des variables d'environnement qui remplacent une préférence du
+/// fichier : `CT220S_EXPORT_DIR`, `CT220S_PLOT__CONNECT_POINTS` (`__` sépare
+/// les niveaux)
+pub const ENV_PREFIX: &str = "CT220S_";
+
+/// Préférences imposées en ligne de commande (`--set clé=valeur`), appliquées
+/// après l'environnement
+static CLI_OVERRIDES: OnceLock<Vec<(String, String)>> = OnceLock::new();
+
+/// Retenir les `--set` de la ligne de commande, avant le premier chargement
+pub fn set_cli_overrides(overrides: Vec<(String, String)>) {
+    let _ = CLI_OVERRIDES.set(overrides);
+}
+
+/// Préférence remplacée par l'environnement ou la ligne de commande
+#[derive(Debug, Clone, Serialize)]
+pub struct Override {
+    /// Chemin de la préférence (`plot.connect_points`)
+    pub key: String,
+    pub value: String,
+    /// Variable d'environnement, ou `--set`
+    pub source: String,
+}
+
+/// Mode d'affichage des courbes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DisplayMode {
+    SingleCh0,
+    #[default]
+    SingleCh1,
+    DualOverlay,
+    DualSideBySide,
+}
+
+/// Couleur de fond du tracé
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Background {
+    White,
+    Black,
+    /// Suit le thème de l'interface (clair ou sombre)
+    FollowTheme,
+}
+
+/// Palette de couleurs des canaux
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Palette {
+    /// Couleurs choisies par l'utilisateur
+    Custom,
+    /// Palette Okabe-Ito, lisible en cas de daltonisme rouge-vert
+    ColorBlind,
+}
+
+/// Proportions de la zone de tracé
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PlotAspect {
+    /// Tracé carré, mêmes échelles en X et Y
+    #[default]
+    Square,
+    /// Occupe tout l'espace disponible
+    Fill,
+}
+
+/// Marqueurs dessinés sur chaque point des courbes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Markers {
+    #[default]
+    None,
+    Circles,
+    Crosses,
+}
+
+/// Thème de l'interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+/// Style des courbes (couleurs, épaisseur, fond)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlotStyle {
+    pub palette: Palette,
+    pub ch0_color: [u8; 3],
+    pub ch1_color: [u8; 3],
+    pub dashed_ch1: bool,
+    pub line_width: f32,
+    /// Nombre de balayages précédents tracés en fondu (0 = désactivé)
+    pub ghosts: usize,
+    pub markers: Markers,
+    /// Relier les points par une ligne (toujours vrai sans marqueurs)
+    pub connect_points: bool,
+    pub background: Background,
+    pub aspect: PlotAspect,
+    /// Traits épais noir sur blanc (ou blanc sur noir) et grille renforcée,
+    /// pour les ateliers mal éclairés et les utilisateurs malvoyants
+    pub high_contrast: bool,
+}
+
+impl PlotStyle {
+    pub const HIGH_CONTRAST_WIDTH: f32 = 4.0;
+
+    /// Couleur effective d'un canal selon la palette
+    pub fn channel_rgb(&self, channel: u8) -> [u8; 3] {
+        match (self.palette, channel) {
+            (Palette::Custom, 0) => self.ch0_color,
+            (Palette::Custom, _) => self.ch1_color,
+            (Palette::ColorBlind, 0) => [230, 159, 0],
+            (Palette::ColorBlind, _) => [0, 114, 178],
+        }
+    }
+
+    /// Épaisseur effective des courbes, jamais fine en haut contraste
+    pub fn trace_width(&self) -> f32 {
+        if self.high_contrast {
+            self.line_width.max(Self::HIGH_CONTRAST_WIDTH)
+        } else {
+            self.line_width
+        }
+    }
+}
+
+impl Default for PlotStyle {
+    fn default() -> Self {
+        Self {
+            palette: Palette::Custom,
+            ch0_color: [255, 100, 0],
+            ch1_color: [0, 0, 255],
+            dashed_ch1: false,
+            line_width: 2.0,
+            ghosts: 0,
+            markers: Markers::None,
+            connect_points: true,
+            background: Background::White,
+            aspect: PlotAspect::Square,
+            high_contrast: false,
+        }
+    }
+}
+
+/// Grille du tracé, partagée entre l'affichage et l'export PNG
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GridStyle {
+    /// Nombre de divisions de chaque côté des axes
+    pub divisions: u32,
+    /// Lignes secondaires (5 par division)
+    pub minor: bool,
+}
+
+impl Default for GridStyle {
+    fn default() -> Self {
+        Self {
+            divisions: 10,
+            minor: false,
+        }
+    }
+}
+
+impl GridStyle {
+    pub const MINOR_PER_DIVISION: u32 = 5;
+
+    /// Positions des lignes dans [-1, 1], avec `true` pour les lignes principales
+    pub fn lines(&self) -> Vec<(f32, bool)> {
+        let n = self.divisions.max(1) as i32;
+        let sub = if self.minor {
+            Self::MINOR_PER_DIVISION as i32
+        } else {
+            1
+        };
+        (-n * sub..=n * sub)
+            .map(|i| (i as f32 / (n * sub) as f32, i % sub == 0))
+            .collect()
+    }
+}
+
+/// Style d'export nommé (rapport de labo, forum…) : taille, couleurs,
+/// légendes et fond des images exportées
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportStyle {
+    pub name: String,
+    pub size: u32,
+    pub scale: f32,
+    /// Couleurs de CH0 et CH1 ; `None` pour les couleurs par défaut
+    pub colors: Option<[[u8; 3]; 2]>,
+    /// Noms des axes et des canaux
+    pub labels: bool,
+    /// Mesures, réglages et date inscrits dans l'image
+    pub measurements: bool,
+    pub transparent: bool,
+}
+
+impl Default for ExportStyle {
+    fn default() -> Self {
+        Settings::default().export_style("")
+    }
+}
+
+/// Préférences persistées entre les sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub language: Language,
+    pub theme: Theme,
+    pub display_mode: DisplayMode,
+    pub show_ch0: bool,
+    pub show_ch1: bool,
+    pub record_while_paused: bool,
+    /// Derniers réglages appliqués au boîtier : identifiant -> libellé de l'option
+    pub device: BTreeMap<String, String>,
+    /// Renvoyer les derniers réglages à la connexion du boîtier
+    pub reapply_device_settings: bool,
+    pub export_dir: String,
+    /// Fichiers de capture récemment ouverts, le plus récent en premier
+    pub recent_files: Vec<String>,
+    /// Nombre de courbes conservées par canal dans l'historique
+    pub history_size: usize,
+    /// Relancer la relecture quand le fichier de capture change sur le disque
+    pub auto_reload: bool,
+    pub plot: PlotStyle,
+    pub grid: GridStyle,
+    pub calibration: Calibration,
+    pub axes: PlotAxes,
+    /// Analyses affichées dans le panneau de mesures
+    pub analysis: AnalysisOptions,
+    /// Emplacement des panneaux de l'interface
+    pub layout: PanelLayout,
+    /// Dernier plan de carte ouvert, rouvert au lancement
+    pub board_map: Option<String>,
+    /// Mode enseignement : explication de la forme des courbes sur le tracé
+    pub teaching: bool,
+    /// Facteur d'échelle de l'interface, appliqué en plus de la densité de l'écran
+    pub ui_scale: f32,
+    /// Fréquence maximale de rafraîchissement à l'arrivée des données (0 = illimitée)
+    pub max_fps: u32,
+    /// Modèles des rapports HTML et PDF (champs `{{ nom }}`) ; `None` pour le
+    /// modèle intégré
+    pub html_template: Option<String>,
+    pub pdf_template: Option<String>,
+    /// Inscrire les mesures, réglages et la date dans les images exportées
+    pub export_measurements: bool,
+    /// Côté d'un tracé exporté en pixels (PNG, SVG)
+    pub export_size: u32,
+    /// Facteur appliqué en plus de `export_size` (écrans haute densité)
+    pub export_scale: f32,
+    /// Images exportées sans fond, pour les incruster dans une présentation
+    pub export_transparent: bool,
+    /// Couleurs de CH0 et CH1 dans les exports ; `None` pour les couleurs par
+    /// défaut
+    pub export_colors: Option<[[u8; 3]; 2]>,
+    /// Noms des axes et des canaux dans les images exportées
+    pub export_labels: bool,
+    /// Styles d'export enregistrés, appliqués d'un clic ou par `convert --style`
+    pub export_styles: Vec<ExportStyle>,
+    /// Durée d'affichage de chaque courbe d'une animation GIF (ms)
+    pub animation_delay_ms: u32,
+    /// Enregistrer chaque test terminé dans la base de résultats
+    pub log_results: bool,
+    /// Conflits de nom à l'import d'une bibliothèque
+    pub library_merge: MergePolicy,
+    /// Compression proposée à l'enregistrement d'une session
+    pub session_compression: Compression,
+    /// Préférences remplacées au chargement, non enregistrées
+    #[serde(skip)]
+    pub overrides: Vec<Override>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            language: Language::default(),
+            theme: Theme::default(),
+            display_mode: DisplayMode::default(),
+            show_ch0: true,
+            show_ch1: true,
+            record_while_paused: true,
+            device: BTreeMap::new(),
+            reapply_device_settings: false,
+            export_dir: ".".to_string(),
+            recent_files: Vec::new(),
+            auto_reload: false,
+            history_size: 20,
+            plot: PlotStyle::default(),
+            grid: GridStyle::default(),
+            calibration: Calibration::default(),
+            axes: PlotAxes::default(),
+            analysis: AnalysisOptions::default(),
+            layout: PanelLayout::default(),
+            board_map: None,
+            teaching: false,
+            ui_scale: 1.0,
+            max_fps: 60,
+            html_template: None,
+            pdf_template: None,
+            export_measurements: true,
+            export_size: Self::EXPORT_SIZES[0].0,
+            export_scale: 1.0,
+            export_transparent: false,
+            export_colors: None,
+            export_labels: true,
+            export_styles: Vec::new(),
+            animation_delay_ms: 500,
+            log_results: false,
+            library_merge: MergePolicy::default(),
+            session_compression: Compression::default(),
+            overrides: Vec::new(),
+        }
+    }
+}
+
+impl Settings {
+    pub const MAX_RECENT_FILES: usize = 10;
+    pub const UI_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.5..=3.0;
+    /// Tailles d'export proposées : côté en pixels et libellé
+    pub const EXPORT_SIZES: [(u32, &'static str); 3] =
+        [(800, "800²"), (2048, "2048²"), (3840, "4K")];
+    pub const EXPORT_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.5..=4.0;
+    /// Côté maximal d'un tracé exporté, pour borner la mémoire de l'image
+    pub const EXPORT_MAX_SIDE: u32 = 8192;
+    /// Couleurs par défaut de CH0 (orange) et CH1 (bleu) dans les exports
+    pub const EXPORT_COLORS: [[u8; 3]; 2] = [[255, 100, 0], [0, 100, 255]];
+
+    /// Réglages d'export courants, enregistrés sous le nom `name`
+    pub fn export_style(&self, name: &str) -> ExportStyle {
+        ExportStyle {
+            name: name.to_string(),
+            size: self.export_size,
+            scale: self.export_scale,
+            colors: self.export_colors,
+            labels: self.export_labels,
+            measurements: self.export_measurements,
+            transparent: self.export_transparent,
+        }
+    }
+
+    /// Appliquer le style d'export nommé `name` aux réglages d'export courants
+    pub fn use_export_style(&mut self, name: &str) -> Result<(), String> {
+        let style = self
+            .export_styles
+            .iter()
+            .find(|s| s.name == name)
+            .cloned()
+            .ok_or_else(|| format!("Style d'export inconnu: {}", name))?;
+        self.export_size = style.size.clamp(100, Self::EXPORT_MAX_SIDE);
+        self.export_scale = style.scale;
+        self.export_colors = style.colors;
+        self.export_labels = style.labels;
+        self.export_measurements = style.measurements;
+        self.export_transparent = style.transparent;
+        Ok(())
+    }
+
+    /// Enregistrer les réglages d'export courants sous `name`, en remplaçant
+    /// le style du même nom
+    pub fn save_export_style(&mut self, name: &str) {
+        let style = self.export_style(name);
+        match self.export_styles.iter_mut().find(|s| s.name == name) {
+            Some(existing) => *existing = style,
+            None => self.export_styles.push(style),
+        }
+    }
+
+    /// Facteur entre la mise en page des exports (tracés de 800 pixels) et
+    /// l'image produite
+    pub fn export_factor(&self) -> f32 {
+        let side = (self.export_size as f32 * self.export_scale)
+            .clamp(1.0, Self::EXPORT_MAX_SIDE as f32);
+        side / 800.0
+    }
+
+    /// Intervalle minimal entre deux rafraîchissements déclenchés par les données
+    pub fn frame_interval(&self) -> Duration {
+        if self.max_fps == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f32(1.0 / self.max_fps as f32)
+        }
+    }
+
+    /// Placer un fichier en tête de la liste des fichiers récents
+    pub fn add_recent_file(&mut self, path: &str) {
+        self.recent_files.retain(|p| p != path);
+        self.recent_files.insert(0, path.to_string());
+        self.recent_files.truncate(Self::MAX_RECENT_FILES);
+    }
+
+    /// Chemin du fichier de configuration (~/.config/ct220s_viewer/settings.toml)
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("ct220s_viewer").join("settings.toml"))
+    }
+
+    /// Charger les préférences : valeurs par défaut, remplacées par celles du
+    /// fichier, puis par les variables `CT220S_*`, puis par les `--set`
+    pub fn load() -> Self {
+        let mut settings = Self::load_file();
+        for requested in Self::requested_overrides() {
+            match settings.apply_override(&requested.key, &requested.value) {
+                Ok(()) => settings.overrides.push(requested),
+                Err(e) => warn!("{} ignoré: {}", requested.source, e),
+            }
+        }
+        settings
+    }
+
+    /// Préférences du fichier seul, ou les valeurs par défaut si absent/invalide
+    fn load_file() -> Self {
+        match Self::file_table() {
+            Some(table) => toml::Value::Table(table).try_into().unwrap_or_else(|e| {
+                warn!("Configuration invalide: {}", e);
+                Self::default()
+            }),
+            None => Self::default(),
+        }
+    }
+
+    /// Contenu du fichier de configuration ; `None` s'il est absent ou illisible
+    fn file_table() -> Option<toml::Table> {
+        let path = Self::path()?;
+        let content = fs::read_to_string(&path).ok()?;
+        toml::from_str(&content)
+            .map_err(|e| warn!("Configuration invalide {}: {}", path.display(), e))
+            .ok()
+    }
+
+    /// Remplacements demandés, dans l'ordre d'application : environnement
+    /// (par nom de variable), puis ligne de commande
+    fn requested_overrides() -> Vec<Override> {
+        let mut variables: Vec<(String, String)> = env::vars()
+            .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+            .collect();
+        variables.sort();
+        let from_env = variables
+            .into_iter()
+            .filter_map(|(name, value)| env_override(name, value));
+        let from_cli = CLI_OVERRIDES
+            .get()
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| Override {
+                key: key.clone(),
+                value: value.clone(),
+                source: "--set".to_string(),
+            });
+        from_env.chain(from_cli).collect()
+    }
+
+    /// Remplacer la préférence `key` (chemin pointé) par `value`, lue comme une
+    /// valeur TOML (`true`, `2`, `[255, 0, 0]`) ou à défaut comme un texte
+    fn apply_override(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let value = toml::from_str::<toml::Table>(&format!("v = {}", value))
+            .ok()
+            .and_then(|mut table| table.remove("v"))
+            .unwrap_or_else(|| toml::Value::String(value.to_string()));
+        let mut table = self.to_table()?;
+        set_path(&mut table, key, Some(value.clone()))?;
+        let settings: Settings = toml::Value::Table(table)
+            .try_into()
+            .map_err(|e| format!("valeur invalide pour {}: {}", key, e.message()))?;
+        // Une clé inconnue est ignorée par la lecture : elle n'en ressort pas
+        let applied = get_path(&settings.to_table()?, key).cloned();
+        if !applied.is_some_and(|applied| same_value(&applied, &value)) {
+            return Err(format!("préférence inconnue: {}", key));
+        }
+        *self = Self {
+            overrides: std::mem::take(&mut self.overrides),
+            ..settings
+        };
+        Ok(())
+    }
+
+    pub fn to_table(&self) -> Result<toml::Table, String> {
+        match toml::Value::try_from(self) {
+            Ok(toml::Value::Table(table)) => Ok(table),
+            Ok(_) => Err("Préférences non représentables en TOML".to_string()),
+            Err(e) => Err(format!("Erreur sérialisation: {}", e)),
+        }
+    }
+
+    /// Préférences effectives au format du fichier de configuration
+    pub fn to_toml(&self) -> Result<String, String> {
+        toml::to_string_pretty(self).map_err(|e| format!("Erreur sérialisation: {}", e))
+    }
+
+    /// Contenu à enregistrer : les préférences remplacées au chargement
+    /// reprennent leur valeur de `file` (ou en sont absentes)
+    fn saved_table(&self, file: &toml::Table) -> Result<toml::Table, String> {
+        let mut table = self.to_table()?;
+        for key in &self.overrides {
+            set_path(&mut table, &key.key, get_path(file, &key.key).cloned())?;
+        }
+        Ok(table)
+    }
+
+    /// Sauvegarder les préférences ; les préférences remplacées au chargement
+    /// gardent la valeur du fichier
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::path().ok_or("Répertoire de configuration introuvable")?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Impossible de créer {}: {}", parent.display(), e))?;
+        }
+
+        let table = self.saved_table(&Self::file_table().unwrap_or_default())?;
+        let content =
+            toml::to_string_pretty(&table).map_err(|e| format!("Erreur sérialisation: {}", e))?;
+        fs::write(&path, content)
+            .map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+
+        info!("Configuration sauvegardée : {}", path.display());
+        Ok(())
+    }
+}
+
+/// Remplacement demandé par une variable `CT220S_*` : `CT220S_PLOT__CONNECT_POINTS`
+/// vise `plot.connect_points` ; `None` pour les autres variables
+fn env_override(name: String, value: String) -> Option<Override> {
+    let key = name
+        .strip_prefix(ENV_PREFIX)?
+        .to_lowercase()
+        .replace("__", ".");
+    Some(Override {
+        key,
+        value,
+        source: name,
+    })
+}
+
+/// Valeur au chemin pointé `key` d'une table TOML
+fn get_path<'a>(table: &'a toml::Table, key: &str) -> Option<&'a toml::Value> {
+    let (parents, name) = match key.rsplit_once('.') {
+        Some((parents, name)) => (Some(parents), name),
+        None => (None, key),
+    };
+    let table = match parents {
+        Some(parents) => get_path(table, parents)?.as_table()?,
+        None => table,
+    };
+    table.get(name)
+}
+
+/// Remplacer (ou retirer, avec `None`) la valeur au chemin pointé `key`
+fn set_path(table: &mut toml::Table, key: &str, value: Option<toml::Value>) -> Result<(), String> {
+    let mut table = table;
+    let mut parts = key.split('.').peekable();
+    while let Some(part) = parts.next() {
+        if part.is_empty() {
+            return Err(format!("clé invalide: {}", key));
+        }
+        if parts.peek().is_none() {
+            match value {
+                Some(value) => table.insert(part.to_string(), value),
+                None => table.remove(part),
+            };
+            return Ok(());
+        }
+        table = table
+            .entry(part)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| format!("{} n'est pas une section", part))?;
+    }
+    Err(format!("clé invalide: {}", key))
+}
+
+/// Valeurs égales, un entier valant le nombre à virgule correspondant
+fn same_value(a: &toml::Value, b: &toml::Value) -> bool {
+    match (a, b) {
+        (toml::Value::Float(x), toml::Value::Integer(n))
+        | (toml::Value::Integer(n), toml::Value::Float(x)) => *x == *n as f64,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overridden(key: &str, value: &str) -> Result<Settings, String> {
+        let mut settings = Settings::default();
+        settings.apply_override(key, value)?;
+        Ok(settings)
+    }
+
+    #[test]
+    fn env_names_map_to_keys() {
+        let mapped = |name: &str| env_override(name.to_string(), "1".to_string()).map(|o| o.key);
+        assert_eq!(mapped("CT220S_EXPORT_DIR").as_deref(), Some("export_dir"));
+        assert_eq!(
+            mapped("CT220S_PLOT__CONNECT_POINTS").as_deref(),
+            Some("plot.connect_points")
+        );
+        assert_eq!(mapped("HOME"), None);
+        let variable = env_override("CT220S_HISTORY_SIZE".to_string(), "8".to_string()).unwrap();
+        assert_eq!(variable.source, "CT220S_HISTORY_SIZE");
+        assert_eq!(variable.value, "8");
+    }
+
+    #[test]
+    fn values_are_read_as_toml() {
+        assert!(
+            !overridden("plot.connect_points", "false")
+                .unwrap()
+                .plot
+                .connect_points
+        );
+        assert_eq!(overridden("history_size", "8").unwrap().history_size, 8);
+        let colors = overridden("export_colors", "[[1, 2, 3], [4, 5, 6]]").unwrap();
+        assert_eq!(colors.export_colors, Some([[1, 2, 3], [4, 5, 6]]));
+    }
+
+    #[test]
+    fn bare_words_are_text() {
+        assert_eq!(
+            overridden("export_dir", "/tmp/exports").unwrap().export_dir,
+            "/tmp/exports"
+        );
+        assert_eq!(
+            overridden("display_mode", "DualOverlay")
+                .unwrap()
+                .display_mode,
+            DisplayMode::DualOverlay
+        );
+    }
+
+    #[test]
+    fn invalid_overrides_are_rejected() {
+        assert!(overridden("history_size", "beaucoup").is_err());
+        assert!(overridden("plot.connect_points", "2").is_err());
+        assert!(overridden("no_such_setting", "1").is_err());
+        assert!(overridden("plot..connect_points", "true").is_err());
+    }
+
+    #[test]
+    fn save_keeps_file_values_of_overrides() {
+        let file: toml::Table =
+            toml::from_str("history_size = 12\n[plot]\nconnect_points = true\n").unwrap();
+        let mut settings = Settings::default();
+        settings
+            .apply_override("plot.connect_points", "false")
+            .unwrap();
+        settings
+            .apply_override("export_dir", "/tmp/exports")
+            .unwrap();
+        for key in ["plot.connect_points", "export_dir"] {
+            settings.overrides.push(Override {
+                key: key.to_string(),
+                value: String::new(),
+                source: "--set".to_string(),
+            });
+        }
+        settings.history_size = 20;
+
+        let saved = settings.saved_table(&file).unwrap();
+        let value = |key: &str| get_path(&saved, key).cloned();
+        assert_eq!(
+            value("plot.connect_points"),
+            Some(toml::Value::Boolean(true))
+        );
+        // Absente du fichier : non enregistrée
+        assert_eq!(value("export_dir"), None);
+        // Modifiée dans l'interface, sans remplacement : enregistrée
+        assert_eq!(value("history_size"), Some(toml::Value::Integer(20)));
+    }
+}
@@ -0,0 +1,234 @@
+// src/shell.rs
+
+use crate::backend::{
+    acquire_curves, hex_line, parse_hex_line, read_capture_reports, read_raw_reports,
+    split_curves, CurveAssembler, HidBackend,
+};
+use crate::cli::{self, DeviceCommand};
+use crate::curve::CurveData;
+use crate::i18n::{tr, tr_args};
+use crate::settings::Settings;
+use clap::{Parser, Subcommand};
+use std::fs::File;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Délai d'attente des commandes `acquire` et `raw`
+const READ_LIMIT: Duration = Duration::from_secs(5);
+
+/// Commandes du shell CT220S
+#[derive(Parser, Debug)]
+#[command(name = "", no_binary_name = true, disable_version_flag = true)]
+struct ShellLine {
+    #[command(subcommand)]
+    command: ShellCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum ShellCommand {
+    #[command(flatten)]
+    Setting(DeviceCommand),
+
+    /// Envoyer un rapport brut (octets hex après l'identifiant de rapport,
+    /// complétés par des zéros), ex. `send fc 02`
+    Send {
+        #[arg(required = true)]
+        bytes: Vec<String>,
+    },
+
+    /// Afficher les prochains rapports bruts reçus, en hex
+    Raw {
+        #[arg(default_value_t = 1)]
+        count: usize,
+    },
+
+    /// Enregistrer les rapports du boîtier pendant N secondes
+    Capture { seconds: f32 },
+
+    /// Acquérir un balayage de chaque canal
+    Acquire,
+
+    /// Charger un fichier de capture
+    Load { path: String },
+
+    /// Mesures des courbes en mémoire
+    Stats {
+        /// Sortie en JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Enregistrer les rapports en mémoire en fichier de capture
+    Save { path: PathBuf },
+
+    /// Exporter l'image (png, svg, pdf) des dernières courbes de chaque canal
+    Export { path: PathBuf },
+
+    /// Quitter le shell
+    #[command(alias = "exit")]
+    Quit,
+}
+
+/// État du shell : boîtier ouvert à la première commande qui l'utilise,
+/// rapports et courbes de la dernière capture
+struct Shell {
+    settings: Settings,
+    backend: Option<HidBackend>,
+    reports: Vec<Vec<u8>>,
+    curves: Vec<CurveData>,
+}
+
+impl Shell {
+    fn backend(&mut self) -> Result<&HidBackend, String> {
+        if self.backend.is_none() {
+            self.backend = Some(HidBackend::new()?);
+        }
+        self.backend.as_ref().ok_or_else(|| "Boîtier indisponible".to_string())
+    }
+
+    /// Exécuter une commande ; `false` pour quitter
+    fn run(&mut self, command: ShellCommand) -> Result<bool, String> {
+        match command {
+            ShellCommand::Setting(setting) => {
+                let (cmd, setting, label) = cli::resolve_setting(&setting)?;
+                self.backend()?.send_cmd(cmd)?;
+                println!("{}", cli::setting_confirmation(setting, label));
+            }
+            ShellCommand::Send { bytes } => {
+                let is_hex = |word: &&String| word.chars().all(|c| c.is_ascii_hexdigit());
+                if let Some(word) = bytes.iter().find(|word| !is_hex(word)) {
+                    return Err(format!("Octets hex attendus: {}", word));
+                }
+                let payload = parse_hex_line(&bytes.join(""))?;
+                self.backend()?.send_report(&payload)?;
+                println!("{}", tr_args("shell-sent", &[("report", hex_line(&payload).into())]));
+            }
+            ShellCommand::Raw { count } => {
+                let device = self.backend()?.clone_device();
+                let device = device.lock().unwrap();
+                let mut left = count;
+                read_raw_reports(&device, Some(READ_LIMIT), |report| {
+                    println!("{}", hex_line(report));
+                    left -= 1;
+                    Ok(left > 0)
+                })?;
+                if left > 0 {
+                    let seconds = READ_LIMIT.as_secs();
+                    let message = tr_args(
+                        "shell-missing-reports",
+                        &[("count", left.into()), ("seconds", seconds.into())],
+                    );
+                    println!("{}", message);
+                }
+            }
+            ShellCommand::Capture { seconds } => {
+                if !(seconds.is_finite() && seconds > 0.0) {
+                    return Err(format!("Durée invalide: {}", seconds));
+                }
+                let device = self.backend()?.clone_device();
+                let device = device.lock().unwrap();
+                let mut assembler = CurveAssembler::default();
+                let (mut reports, mut curves) = (Vec::new(), Vec::new());
+                read_raw_reports(&device, Some(Duration::from_secs_f32(seconds)), |report| {
+                    reports.push(report.to_vec());
+                    curves.extend(assembler.push(report));
+                    Ok(true)
+                })?;
+                println!("{}", loaded(reports.len(), curves.len()));
+                (self.reports, self.curves) = (reports, curves);
+            }
+            ShellCommand::Acquire => {
+                let device = self.backend()?.clone_device();
+                let curves = acquire_curves(&device.lock().unwrap(), READ_LIMIT)?;
+                // Pas de rapports bruts : `save` n'a rien à enregistrer
+                self.reports.clear();
+                self.curves = curves;
+                cli::print_stats(&self.curves, &self.settings, false);
+            }
+            ShellCommand::Load { path } => {
+                let reports = read_capture_reports(&path)?;
+                self.curves = split_curves(&reports)?;
+                self.reports = reports;
+                println!("{}", loaded(self.reports.len(), self.curves.len()));
+            }
+            ShellCommand::Stats { json } => {
+                if self.curves.is_empty() {
+                    return Err(tr("shell-no-curves"));
+                }
+                cli::print_stats(&self.curves, &self.settings, json);
+            }
+            ShellCommand::Save { path } => self.save(&path)?,
+            ShellCommand::Export { path } => {
+                cli::export_curves(&self.curves, &self.settings, &path)?
+            }
+            ShellCommand::Quit => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Enregistrer les rapports en mémoire au format des fichiers de capture
+    fn save(&self, path: &Path) -> Result<(), String> {
+        if self.reports.is_empty() {
+            return Err(tr("shell-no-reports"));
+        }
+        let write_error = |e: io::Error| format!("Impossible d'écrire {}: {}", path.display(), e);
+        let mut file = File::create(path).map_err(write_error)?;
+        writeln!(file, "# Capture CT220S").map_err(write_error)?;
+        for report in &self.reports {
+            writeln!(file, "{}", hex_line(report)).map_err(write_error)?;
+        }
+        let message = tr_args(
+            "shell-saved",
+            &[
+                ("path", path.display().to_string().into()),
+                ("reports", self.reports.len().into()),
+            ],
+        );
+        println!("{}", message);
+        Ok(())
+    }
+}
+
+/// Nombre de rapports et de courbes en mémoire
+fn loaded(reports: usize, curves: usize) -> String {
+    tr_args("shell-loaded", &[("reports", reports.into()), ("curves", curves.into())])
+}
+
+/// Shell interactif : une commande par ligne (`help` pour la liste),
+/// jusqu'à `quit` ou la fin de l'entrée
+pub fn run() -> Result<(), String> {
+    let mut shell = Shell {
+        settings: cli::load_settings(),
+        backend: None,
+        reports: Vec::new(),
+        curves: Vec::new(),
+    };
+    println!("{}", tr("shell-welcome"));
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("ct220s> ");
+        let _ = io::stdout().flush();
+        let Some(line) = lines.next() else {
+            println!();
+            return Ok(());
+        };
+        let line = line.map_err(|e| format!("Erreur de lecture: {}", e))?;
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if words.is_empty() || words[0].starts_with('#') {
+            continue;
+        }
+        match ShellLine::try_parse_from(words) {
+            Ok(parsed) => match shell.run(parsed.command) {
+                Ok(true) => {}
+                Ok(false) => return Ok(()),
+                Err(e) => eprintln!("{}", tr_args("shell-error", &[("error", e.into())])),
+            },
+            // Aide et erreurs de syntaxe, mises en forme par clap
+            Err(e) => {
+                let _ = e.print();
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+// src/sigrok_export.rs
+
+use crate::config::POINTS_PER_CURVE;
+use crate::curve::CurveData;
+use crate::settings::Settings;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::Write as _;
+use std::path::Path;
+use tracing::info;
+use zip::result::ZipResult;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+/// Exporter des balayages en session sigrok (.sr), ouvrable dans PulseView :
+/// deux voies analogiques par canal (`CHn V` et `CHn I`), les balayages mis
+/// bout à bout. Les valeurs sont en V et mA si la calibration est active,
+/// normalisées sinon. `frequency` (Hz) donne la cadence d'échantillonnage :
+/// un balayage par période du signal de test, une par seconde si inconnue.
+pub fn save_sweeps_as_sigrok(
+    sweeps: &[(usize, CurveData)],
+    settings: &Settings,
+    frequency: Option<f32>,
+    path: &Path,
+) -> Result<(), String> {
+    let calibration = &settings.calibration;
+    let (volts, milliamps) = if calibration.enabled {
+        (calibration.volts_full_scale, calibration.milliamps_full_scale)
+    } else {
+        (1.0, 1.0)
+    };
+    let samplerate = (POINTS_PER_CURVE as f32 * frequency.unwrap_or(1.0)).round() as u64;
+
+    let mut names = Vec::new();
+    let mut files = vec![("version".to_string(), b"2".to_vec())];
+    for channel in [0, 1] {
+        let curves: Vec<&CurveData> = sweeps
+            .iter()
+            .map(|(_, curve)| curve)
+            .filter(|curve| curve.channel == channel)
+            .collect();
+        if curves.is_empty() {
+            continue;
+        }
+        let samples = |values: fn(&CurveData) -> &[f32], factor: f32| -> Vec<u8> {
+            curves
+                .iter()
+                .flat_map(|curve| values(curve))
+                .flat_map(|v| (v * factor).to_le_bytes())
+                .collect()
+        };
+        let voltage = samples(|curve| &curve.voltage, volts);
+        let current = samples(|curve| &curve.current, milliamps);
+        for (suffix, data) in [("V", voltage), ("I", current)] {
+            names.push(format!("CH{} {}", channel, suffix));
+            // Une voie par fichier, en un seul bloc : analog-1-<voie>-1
+            files.push((format!("analog-1-{}-1", names.len()), data));
+        }
+    }
+    if names.is_empty() {
+        return Err("Aucune courbe à exporter".to_string());
+    }
+
+    let mut metadata = String::from("[global]\nsigrok version=0.5.2\n\n[device 1]\n");
+    let _ = writeln!(metadata, "samplerate={}", samplerate);
+    let _ = writeln!(metadata, "total analog={}", names.len());
+    for (i, name) in names.iter().enumerate() {
+        let _ = writeln!(metadata, "analog{}={}", i + 1, name);
+    }
+    files.insert(1, ("metadata".to_string(), metadata.into_bytes()));
+
+    let write = || -> ZipResult<()> {
+        let mut zip = ZipWriter::new(File::create(path)?);
+        for (name, data) in &files {
+            zip.start_file(name.as_str(), SimpleFileOptions::default())?;
+            zip.write_all(data)?;
+        }
+        zip.finish()?;
+        Ok(())
+    };
+    write().map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+
+    info!("Session sigrok sauvegardée : {}", path.display());
+    Ok(())
+}
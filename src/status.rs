@@ -0,0 +1,108 @@
+// src/status.rs
+
+use crate::notifications::Level;
+use eframe::egui;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Fenêtre glissante pour le calcul du débit de courbes
+const RATE_WINDOW: Duration = Duration::from_secs(2);
+
+/// État de la connexion au boîtier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Connection {
+    #[default]
+    Disconnected,
+    Usb,
+    File,
+}
+
+/// Courbe en cours de réception
+#[derive(Debug, Clone, Copy)]
+pub struct CurveProgress {
+    pub channel: u8,
+    /// Rapports de données reçus depuis le header
+    pub reports: usize,
+}
+
+/// État de l'acquisition, partagé entre l'interface et le thread de lecture
+#[derive(Debug, Default)]
+pub struct AcquisitionStatus {
+    pub connection: Connection,
+    /// Nombre de courbes reçues depuis le lancement de la lecture
+    pub total_curves: u64,
+    /// Dernière erreur de lecture ou de commande
+    pub last_error: Option<String>,
+    /// Courbe en cours d'assemblage (mode USB)
+    pub assembling: Option<CurveProgress>,
+    /// Arrêt du thread de lecture sur erreur ou panique, affiché dans une
+    /// boîte de dialogue
+    pub failure: Option<String>,
+    /// Notifications émises par le thread de lecture, en attente d'affichage
+    pending: Vec<(Level, String)>,
+    recent: VecDeque<Instant>,
+    /// Interface à réveiller à chaque changement, avec l'intervalle minimal
+    /// entre deux rafraîchissements
+    repaint: Option<(egui::Context, Duration)>,
+}
+
+impl AcquisitionStatus {
+    /// Comptabiliser une courbe reçue
+    pub fn record_curve(&mut self) {
+        self.request_repaint();
+        let now = Instant::now();
+        self.total_curves += 1;
+        self.recent.push_back(now);
+        while self
+            .recent
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > RATE_WINDOW)
+        {
+            self.recent.pop_front();
+        }
+    }
+
+    /// Débit moyen sur les deux dernières secondes
+    pub fn curves_per_second(&self) -> f32 {
+        let now = Instant::now();
+        let count = self
+            .recent
+            .iter()
+            .filter(|t| now.duration_since(**t) <= RATE_WINDOW)
+            .count();
+        count as f32 / RATE_WINDOW.as_secs_f32()
+    }
+
+    /// Enregistrer une erreur ; notifiée seulement si elle diffère de la précédente
+    pub fn set_error(&mut self, error: String) {
+        self.request_repaint();
+        if self.last_error.as_ref() != Some(&error) {
+            self.pending.push((Level::Error, error.clone()));
+        }
+        self.last_error = Some(error);
+    }
+
+    pub fn notify(&mut self, level: Level, text: String) {
+        self.request_repaint();
+        self.pending.push((level, text));
+    }
+
+    /// Contexte de l'interface à réveiller ; mis à jour par l'interface à
+    /// chaque image pour suivre la fréquence maximale choisie
+    pub fn set_repaint(&mut self, ctx: &egui::Context, min_interval: Duration) {
+        self.repaint = Some((ctx.clone(), min_interval));
+    }
+
+    /// Demander un rafraîchissement de l'interface ; les demandes rapprochées
+    /// sont regroupées selon l'intervalle minimal
+    pub fn request_repaint(&self) {
+        if let Some((ctx, min_interval)) = &self.repaint {
+            ctx.request_repaint_after(*min_interval);
+        }
+    }
+
+    /// Récupérer les notifications en attente
+    pub fn take_pending(&mut self) -> Vec<(Level, String)> {
+        std::mem::take(&mut self.pending)
+    }
+}
@@ -0,0 +1,35 @@
+// src/template.rs
+
+use handlebars::{no_escape, Handlebars};
+use std::collections::BTreeMap;
+use std::fs;
+
+/// Champs disponibles dans les modèles de rapport (`plot` : tracé interactif,
+/// modèles HTML uniquement)
+pub const FIELDS: [&str; 13] = [
+    "app", "source", "date", "mode", "scale", "device", "summary", "verdict", "score",
+    "signature", "notes", "header", "plot",
+];
+
+/// Remplir un modèle Handlebars (`{{ nom }}`, `{{#if notes}}…{{/if}}`) ; les
+/// valeurs sont insérées telles quelles, déjà échappées par l'appelant. Un
+/// champ inconnu est une erreur, pour ne pas produire un rapport incomplet
+/// sans le voir
+pub fn render(template: &str, fields: &BTreeMap<&str, String>) -> Result<String, String> {
+    let mut registry = Handlebars::new();
+    registry.set_strict_mode(true);
+    registry.register_escape_fn(no_escape);
+    registry
+        .render_template(template, fields)
+        .map_err(|e| format!("Modèle de rapport invalide: {}", e))
+}
+
+/// Lire le modèle configuré ; `None` pour le modèle intégré
+pub fn load(path: Option<&str>) -> Result<Option<String>, String> {
+    let Some(path) = path.filter(|p| !p.is_empty()) else {
+        return Ok(None);
+    };
+    fs::read_to_string(path)
+        .map(Some)
+        .map_err(|e| format!("Impossible de lire {}: {}", path, e))
+}
@@ -0,0 +1,50 @@
+// src/timestamp.rs
+
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+
+/// Instant en secondes depuis l'epoch Unix
+fn utc(secs: u64) -> DateTime<Utc> {
+    DateTime::from_timestamp(secs as i64, 0).unwrap_or_default()
+}
+
+fn local(secs: u64) -> DateTime<Local> {
+    utc(secs).with_timezone(&Local)
+}
+
+/// Date et heure locales (AAAA-MM-JJ HH:MM) d'un instant en secondes depuis
+/// l'epoch, pour l'affichage
+pub fn format_timestamp(secs: u64) -> String {
+    local(secs).format("%Y-%m-%d %H:%M").to_string()
+}
+
+/// Horodatage local des noms de fichiers (AAAA-MM-JJ_HHMMSS), trié comme la
+/// date
+pub fn file_timestamp(secs: u64) -> String {
+    local(secs).format("%Y-%m-%d_%H%M%S").to_string()
+}
+
+/// Date et heure UTC au format RFC 3339 (AAAA-MM-JJTHH:MM:SSZ), pour les
+/// fichiers lus par d'autres programmes
+pub fn rfc3339_timestamp(secs: u64) -> String {
+    utc(secs).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn machine_timestamps_are_utc() {
+        assert_eq!(rfc3339_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339_timestamp(1_709_210_096), "2024-02-29T12:34:56Z");
+    }
+
+    #[test]
+    fn local_timestamps_keep_their_layout() {
+        let date = format_timestamp(1_709_210_096);
+        assert_eq!(date.len(), "2024-02-29 12:34".len());
+        let name = file_timestamp(1_709_210_096);
+        assert_eq!(name.len(), "2024-02-29_123456".len());
+        assert_eq!(name.as_bytes()[10], b'_');
+    }
+}
@@ -0,0 +1,344 @@
+// src/tui.rs
+
+use crate::analysis::Analysis;
+use crate::backend::{DeviceSetting, DEVICE_SETTINGS};
+use crate::cli::{self, ViewOptions};
+use crate::curve::CurveData;
+use crate::i18n::{tr, tr_args};
+use crate::logging;
+use crate::session::Session;
+use crate::settings::{DisplayMode, Settings};
+use crate::status::Connection;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::symbols::Marker;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::canvas::{Canvas, Line as Segment, Points};
+use ratatui::widgets::{Paragraph, Widget};
+use ratatui::{DefaultTerminal, Frame};
+use std::io::{self, IsTerminal};
+use std::time::Duration;
+
+/// Largeur du panneau de commandes, à droite du tracé
+const PANEL_WIDTH: u16 = 34;
+
+/// Touches des réglages du boîtier, dans l'ordre de `DEVICE_SETTINGS`
+const SETTING_KEYS: [char; 4] = ['f', 'r', 'm', 'v'];
+
+/// Attente d'une touche avant de redessiner le tracé
+const REFRESH: Duration = Duration::from_millis(100);
+
+/// Bornes du tracé : ±1 occupe 90 % de la zone, comme dans l'interface
+/// graphique
+const BOUND: f64 = 1.0 / 0.9;
+
+/// Canaux tracés
+#[derive(Clone, Copy, PartialEq)]
+enum Channels {
+    Ch0,
+    Ch1,
+    Both,
+}
+
+impl Channels {
+    fn from_display_mode(mode: DisplayMode) -> Self {
+        match mode {
+            DisplayMode::SingleCh0 => Channels::Ch0,
+            DisplayMode::SingleCh1 => Channels::Ch1,
+            DisplayMode::DualOverlay | DisplayMode::DualSideBySide => Channels::Both,
+        }
+    }
+
+    fn shows(self, channel: u8) -> bool {
+        match self {
+            Channels::Ch0 => channel == 0,
+            Channels::Ch1 => channel == 1,
+            Channels::Both => true,
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Channels::Ch0 => Channels::Ch1,
+            Channels::Ch1 => Channels::Both,
+            Channels::Both => Channels::Ch0,
+        }
+    }
+}
+
+struct Tui {
+    settings: Settings,
+    session: Session,
+    channels: Channels,
+    /// Résultat de la dernière commande, affiché dans le panneau
+    message: String,
+}
+
+impl Tui {
+    /// Traiter une touche ; `false` pour quitter
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Char('q' | 'Q') | KeyCode::Esc => return false,
+            KeyCode::Char(' ') => {
+                let paused = !self.session.paused;
+                self.session.set_paused(paused, self.settings.record_while_paused);
+            }
+            KeyCode::Char('c') => self.channels = self.channels.next(),
+            KeyCode::Right => self.step(1),
+            KeyCode::Left => self.step(-1),
+            KeyCode::Char(key) => {
+                let index = SETTING_KEYS.iter().position(|&k| k == key);
+                if let Some(setting) = index.map(|i| &DEVICE_SETTINGS[i]) {
+                    self.cycle_setting(setting);
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Balayage précédent ou suivant d'un fichier rejoué
+    fn step(&mut self, step: i32) {
+        if self.session.use_file_mode {
+            let mut playback = self.session.playback.lock().unwrap();
+            playback.playing = false;
+            playback.step += step;
+        }
+    }
+
+    /// Passer le réglage à l'option suivante et l'envoyer au boîtier
+    fn cycle_setting(&mut self, setting: &DeviceSetting) {
+        let Some(backend) = self.session.hid_backend.clone() else {
+            self.message = tr("tui-no-device");
+            return;
+        };
+        let current = self.session.applied_device.get(setting.id);
+        let position = setting
+            .options
+            .iter()
+            .position(|(label, _)| Some(&label.to_string()) == current);
+        let next = position.map_or(0, |p| (p + 1) % setting.options.len());
+        let (label, index) = setting.options[next];
+        let result = backend.lock().unwrap().send_cmd((setting.command)(index));
+        self.message = match result {
+            Ok(()) => {
+                for device in [&mut self.settings.device, &mut self.session.applied_device] {
+                    device.insert(setting.id.to_string(), label.to_string());
+                }
+                let args = [("setting", tr(setting.key).into()), ("value", label.into())];
+                tr_args("cmd-applied", &args)
+            }
+            Err(e) => tr_args("cmd-error", &[("error", e.into())]),
+        };
+    }
+
+    fn channel_color(&self, channel: u8) -> Color {
+        let [r, g, b] = self.settings.plot.channel_rgb(channel);
+        Color::Rgb(r, g, b)
+    }
+
+    /// Écran complet : titre, tracé à gauche, panneau de commandes à droite
+    /// et ligne d'état en bas
+    fn draw(&self, frame: &mut Frame) {
+        let [title, main, status] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let panel_width = PANEL_WIDTH.min(main.width / 2);
+        let [plot, panel] =
+            Layout::horizontal([Constraint::Min(1), Constraint::Length(panel_width)])
+                .spacing(1)
+                .areas(main);
+
+        let data = self.session.displayed_data();
+        let curves: Vec<&CurveData> = [&data.channel0, &data.channel1]
+            .into_iter()
+            .flatten()
+            .filter(|curve| self.channels.shows(curve.channel))
+            .collect();
+
+        frame.render_widget(Paragraph::new(self.title()).bold(), title);
+        frame.render_widget(self.plot(&curves), plot);
+        frame.render_widget(Paragraph::new(self.panel(&curves)), panel);
+        frame.render_widget(Paragraph::new(self.status_line()).reversed(), status);
+    }
+
+    /// Tracé V-I en braille, axes en gris sous les courbes
+    fn plot<'a>(&'a self, curves: &'a [&CurveData]) -> impl Widget + 'a {
+        Canvas::default()
+            .marker(Marker::Braille)
+            .x_bounds([-BOUND, BOUND])
+            .y_bounds([-BOUND, BOUND])
+            .paint(move |ctx| {
+                ctx.draw(&Segment::new(-BOUND, 0.0, BOUND, 0.0, Color::DarkGray));
+                ctx.draw(&Segment::new(0.0, -BOUND, 0.0, BOUND, Color::DarkGray));
+                ctx.layer();
+                for curve in curves {
+                    let color = self.channel_color(curve.channel);
+                    let points: Vec<(f64, f64)> = curve
+                        .voltage
+                        .iter()
+                        .zip(&curve.current)
+                        .filter(|(v, i)| v.is_finite() && i.is_finite())
+                        .map(|(&v, &i)| {
+                            let (x, y) = self.settings.axes.project(v, i);
+                            (x as f64, y as f64)
+                        })
+                        .collect();
+                    if self.settings.plot.connect_points {
+                        for pair in points.windows(2) {
+                            let [(x1, y1), (x2, y2)] = [pair[0], pair[1]];
+                            ctx.draw(&Segment::new(x1, y1, x2, y2, color));
+                        }
+                    }
+                    ctx.draw(&Points {
+                        coords: &points,
+                        color,
+                    });
+                }
+            })
+    }
+
+    fn title(&self) -> String {
+        let mut title = format!("CT220S — {}", self.session.title());
+        if self.session.paused {
+            title.push_str(" — ");
+            title.push_str(&tr("tui-paused"));
+        }
+        title
+    }
+
+    /// Lignes du panneau de commandes
+    fn panel(&self, curves: &[&CurveData]) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        for (setting, key) in DEVICE_SETTINGS.iter().zip(SETTING_KEYS) {
+            lines.push(Line::from(format!("[{}] {}", key, tr(setting.key))));
+            let current = self.session.applied_device.get(setting.id);
+            let mut options = vec![Span::raw("   ")];
+            for &(label, _) in setting.options {
+                options.push(Span::raw(" "));
+                options.push(match current {
+                    Some(current) if current == label => Span::raw(label).reversed(),
+                    _ => Span::raw(label),
+                });
+            }
+            lines.push(Line::from(options));
+        }
+        lines.push(Line::default());
+
+        let calibration = &self.settings.calibration;
+        for curve in curves {
+            let analysis = Analysis::of(curve);
+            let color = Style::new().fg(self.channel_color(curve.channel));
+            lines.push(Line::from(vec![
+                Span::styled(format!("CH{}", curve.channel), color),
+                Span::raw(format!(" {}", tr(analysis.component.key()))),
+            ]));
+            let mut values = format!("    R {}", analysis.format_resistance(calibration));
+            if let Some(vf) = analysis.format_forward_voltage(calibration) {
+                values.push_str(&format!(", Vf {}", vf));
+            }
+            lines.push(Line::from(values));
+        }
+        lines.push(Line::default());
+
+        lines.push(Line::from(tr("tui-keys")));
+        if self.session.use_file_mode {
+            lines.push(Line::from(tr("tui-keys-sweep")));
+        }
+        lines.push(Line::from(tr("tui-keys-quit")));
+        if !self.message.is_empty() {
+            lines.push(Line::default());
+            lines.push(Line::from(self.message.clone()));
+        }
+        lines
+    }
+
+    fn status_line(&self) -> String {
+        let status = self.session.status.lock().unwrap();
+        let connection = match status.connection {
+            Connection::Disconnected => tr("connection-none"),
+            Connection::Usb => tr("connection-usb"),
+            Connection::File => tr("connection-file"),
+        };
+        let mut line = tr_args(
+            "tui-status",
+            &[
+                ("connection", connection.into()),
+                ("count", status.total_curves.into()),
+                ("rate", format!("{:.1}", status.curves_per_second()).into()),
+            ],
+        );
+        if self.session.use_file_mode {
+            let playback = self.session.playback.lock().unwrap();
+            let sweep = tr_args(
+                "tui-sweep",
+                &[
+                    ("position", (playback.position + 1).into()),
+                    ("total", playback.total.into()),
+                ],
+            );
+            line = format!("{} — {}", line, sweep);
+        }
+        if let Some(error) = status.failure.as_ref().or(status.last_error.as_ref()) {
+            line = format!("{} — {}", line, error);
+        }
+        format!(" {}", line)
+    }
+
+    /// Redessiner et lire le clavier jusqu'à `q`
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<(), String> {
+        let keyboard_error = |e: io::Error| format!("Erreur de lecture du clavier: {}", e);
+        loop {
+            terminal
+                .draw(|frame| self.draw(frame))
+                .map_err(|e| format!("Erreur d'affichage: {}", e))?;
+            if !event::poll(REFRESH).map_err(keyboard_error)? {
+                continue;
+            }
+            if let Event::Key(key) = event::read().map_err(keyboard_error)? {
+                if key.kind == KeyEventKind::Press && !self.handle_key(key) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+/// Interface en mode texte : tracé V-I en braille et panneau de commandes,
+/// pour les postes sans écran (SSH). Rejoue `file`, sinon lit le boîtier
+pub fn run(file: Option<String>, view: &ViewOptions) -> Result<(), String> {
+    let mut settings = cli::load_settings();
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return Err(tr("tui-no-terminal"));
+    }
+    let mut terminal =
+        ratatui::try_init().map_err(|e| format!("Impossible d'ouvrir le terminal: {}", e))?;
+    // Les journaux écrits sur la sortie d'erreur déformeraient l'écran ; la
+    // ligne d'état montre les erreurs de lecture
+    logging::set_stderr(false);
+
+    let mut session = Session::new(file.clone().unwrap_or_default(), &settings);
+    view.apply(&session, &mut settings);
+    match file {
+        Some(path) => session.start_file_reader(path, &mut settings),
+        None => session.start_usb_reader(&settings),
+    }
+    let mut tui = Tui {
+        session,
+        channels: Channels::from_display_mode(settings.display_mode),
+        message: String::new(),
+        settings,
+    };
+    let result = tui.event_loop(&mut terminal);
+    ratatui::restore();
+    logging::set_stderr(true);
+
+    *tui.session.running.lock().unwrap() = false;
+    result?;
+    tui.settings.save()
+}
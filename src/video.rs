@@ -0,0 +1,104 @@
+// src/video.rs
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Images par seconde de la vidéo
+pub const FPS: u32 = 10;
+
+/// Hauteur des images de la vidéo ; chaque canal occupe une colonne carrée
+pub const HEIGHT: u32 = 800;
+
+/// Enregistrement vidéo du tracé : les images RGBA sont envoyées à `ffmpeg`
+/// par un tube, encodées en MP4 (H.264) ou WebM (VP9) selon l'extension
+pub struct VideoRecorder {
+    child: Child,
+    path: PathBuf,
+    /// Canaux enregistrés, un par colonne, fixés au début de l'enregistrement
+    pub channels: Vec<u8>,
+    started: Instant,
+    frames: u64,
+}
+
+impl VideoRecorder {
+    /// Lancer `ffmpeg` pour écrire `path`
+    pub fn start(path: &Path, channels: Vec<u8>) -> Result<Self, String> {
+        let width = HEIGHT * channels.len().max(1) as u32;
+        let webm = path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("webm"));
+        let codec: &[&str] = if webm {
+            &["-c:v", "libvpx-vp9", "-b:v", "0", "-crf", "32"]
+        } else {
+            &["-c:v", "libx264", "-preset", "veryfast"]
+        };
+        let child = Command::new("ffmpeg")
+            .args(["-y", "-loglevel", "error", "-f", "rawvideo", "-pix_fmt", "rgba"])
+            .args(["-s", &format!("{}x{}", width, HEIGHT)])
+            .args(["-r", &FPS.to_string(), "-i", "-"])
+            .args(codec)
+            .args(["-pix_fmt", "yuv420p"])
+            .arg(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Impossible de lancer ffmpeg: {}", e))?;
+
+        info!("Enregistrement vidéo : {}", path.display());
+        Ok(Self {
+            child,
+            path: path.to_path_buf(),
+            channels,
+            started: Instant::now(),
+            frames: 0,
+        })
+    }
+
+    /// Durée enregistrée
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Nombre d'images à écrire pour rattraper l'horloge : la vidéo garde un
+    /// rythme constant même si l'interface est rafraîchie irrégulièrement
+    pub fn frames_due(&self) -> u64 {
+        let expected = (self.elapsed().as_secs_f64() * FPS as f64) as u64 + 1;
+        expected.saturating_sub(self.frames)
+    }
+
+    /// Écrire `count` fois la même image (RGBA, dimensions de l'enregistrement)
+    pub fn push(&mut self, rgba: &[u8], count: u64) -> Result<(), String> {
+        let stdin = self
+            .child
+            .stdin
+            .as_mut()
+            .ok_or_else(|| "Tube vers ffmpeg fermé".to_string())?;
+        for _ in 0..count {
+            stdin
+                .write_all(rgba)
+                .map_err(|e| format!("Erreur d'écriture vers ffmpeg: {}", e))?;
+            self.frames += 1;
+        }
+        Ok(())
+    }
+
+    /// Terminer la vidéo : fermer le tube et attendre la fin de l'encodage
+    pub fn finish(mut self) -> Result<PathBuf, String> {
+        drop(self.child.stdin.take());
+        let output = self
+            .child
+            .wait_with_output()
+            .map_err(|e| format!("Erreur ffmpeg: {}", e))?;
+        if !output.status.success() {
+            let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(format!("Erreur ffmpeg: {}", message));
+        }
+
+        info!("Vidéo sauvegardée : {} ({} images)", self.path.display(), self.frames);
+        Ok(std::path::absolute(&self.path).unwrap_or(self.path))
+    }
+}